@@ -0,0 +1,28 @@
+//! Injects build metadata reported by `GET /health/version`.
+//!
+//! - `TAGBLAZE_GIT_SHA`: Short commit hash, or `unknown` outside a git checkout.
+//! - `TAGBLAZE_BUILT_AT`: RFC 3339 UTC timestamp of the build.
+
+use std::process::Command;
+
+fn main() {
+    let git_sha = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|sha| sha.trim().to_string())
+        .filter(|sha| !sha.is_empty())
+        .unwrap_or_else(|| "unknown".into());
+
+    let built_at = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+
+    println!("cargo:rustc-env=TAGBLAZE_GIT_SHA={git_sha}");
+    println!("cargo:rustc-env=TAGBLAZE_BUILT_AT={built_at}");
+
+    // Re-run when the checked-out commit changes
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=../.git/HEAD");
+    println!("cargo:rerun-if-changed=../.git/refs/heads");
+}
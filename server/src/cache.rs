@@ -0,0 +1,73 @@
+//! In-memory read cache for the tag list.
+//!
+//! Tags change rarely but `GET /tags` is hit constantly, so the full table is
+//! kept in [`AppState`](crate::state::AppState) and served from memory. Entries
+//! expire after a short TTL, and every tag write invalidates them right away.
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use sea_orm::{DatabaseConnection, DbErr, EntityTrait, QueryOrder};
+use tokio::sync::RwLock;
+
+use crate::models::tag;
+
+/// How long a cached tag list is trusted without a write invalidating it.
+pub const TAG_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Snapshot of every tag row, deleted ones included, ordered by ID.
+#[derive(Debug)]
+pub struct TagCache {
+    ttl: Duration,
+    entry: RwLock<Option<(Instant, Arc<Vec<tag::Model>>)>>,
+}
+
+impl Default for TagCache {
+    fn default() -> Self {
+        Self::new(TAG_CACHE_TTL)
+    }
+}
+
+impl TagCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entry: RwLock::new(None),
+        }
+    }
+
+    /// The cached tags, or `None` if empty or expired.
+    pub async fn get(&self) -> Option<Arc<Vec<tag::Model>>> {
+        let entry = self.entry.read().await;
+        entry
+            .as_ref()
+            .filter(|(stored, _)| stored.elapsed() < self.ttl)
+            .map(|(_, tags)| Arc::clone(tags))
+    }
+
+    /// Replace the snapshot and return it.
+    pub async fn set(&self, tags: Vec<tag::Model>) -> Arc<Vec<tag::Model>> {
+        let tags = Arc::new(tags);
+        *self.entry.write().await = Some((Instant::now(), Arc::clone(&tags)));
+        tags
+    }
+
+    /// The cached tags, reloading them from `db` on a miss.
+    pub async fn load(&self, db: &DatabaseConnection) -> Result<Arc<Vec<tag::Model>>, DbErr> {
+        if let Some(tags) = self.get().await {
+            return Ok(tags);
+        }
+        let tags = tag::Entity::find()
+            .order_by_asc(tag::Column::Id)
+            .all(db)
+            .await?;
+        Ok(self.set(tags).await)
+    }
+
+    /// Drop the snapshot so the next read goes to the database.
+    pub async fn invalidate(&self) {
+        *self.entry.write().await = None;
+    }
+}
@@ -1,14 +1,239 @@
-// use std::env;
-
-// pub struct Config {
-//     pub database_url: String,
-// }
-
-// impl Config {
-//     pub fn from_env() -> Self {
-//         dotenvy::dotenv().ok(); // You’ll add `.env` later
-//         let database_url =
-//             env::var("DATABASE_URL").expect("DATABASE_URL must be set in .env");
-//         Self { database_url }
-//     }
-// }
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+use axum::http::HeaderName;
+
+use crate::{
+    middleware::security_headers::DEFAULT_CSP,
+    models::user::Role,
+    utils::{client_ip::Cidr, jwt::TOKEN_TTL},
+};
+
+/// Application configuration resolved from the environment at startup.
+///
+/// # Fields
+/// - `database_url`: Connection string for the primary database (`DATABASE_URL`).
+/// - `database_read_url`: Optional read replica for read-only listings and stats; unset sends every query to the primary (`DATABASE_READ_URL`).
+/// - `jwt_secret`: Secret used to sign and verify JWTs (`JWT_SECRET`).
+/// - `jwt_header`: Extra header carrying a bare JWT, checked before `Authorization: Bearer`, for gateways that forward tokens elsewhere (`JWT_HEADER`, e.g. `X-Auth-Token`).
+/// - `run_migrations`: Apply pending schema migrations at startup (`RUN_MIGRATIONS`).
+/// - `seed_on_empty`: Seed sample data at startup if the database has no users (`SEED_ON_EMPTY`).
+/// - `cors_allowed_origins`: Origins allowed by CORS; empty means any origin (`CORS_ALLOWED_ORIGINS`, comma-separated).
+/// - `cors_allow_credentials`: Allow credentialed CORS requests; only honoured with explicit origins (`CORS_ALLOW_CREDENTIALS`).
+/// - `cors_max_age_secs`: How long browsers may cache a preflight response (`CORS_MAX_AGE_SECS`).
+/// - `trusted_proxies`: Networks whose `X-Forwarded-For` / `X-Real-IP` are believed when working out the client IP; empty trusts nobody (`TRUSTED_PROXIES`, comma-separated CIDRs).
+/// - `rate_limit_requests`: Requests per client per window on limited routes; `0` disables (`RATE_LIMIT_REQUESTS`, default 60).
+/// - `rate_limit_window_secs`: Length of the rate-limit window (`RATE_LIMIT_WINDOW_SECS`, default 60).
+/// - `maintenance_mode`: Start in maintenance mode, answering `503` to all but health checks, admin routes, and admins; switchable at runtime (`MAINTENANCE_MODE`).
+/// - `maintenance_retry_after_secs`: `Retry-After` sent with those `503`s (`MAINTENANCE_RETRY_AFTER_SECS`, default 120).
+/// - `read_only`: Refuse writes with `403`; reads, login, and admin routes keep working, for demo deployments (`READ_ONLY`).
+/// - `default_page_size`: Page size for list endpoints when `per_page` is omitted (`DEFAULT_PAGE_SIZE`, default 20).
+/// - `max_page_size`: Largest `per_page` honoured; larger requests are clamped (`MAX_PAGE_SIZE`, default 100).
+/// - `max_description_len`: Longest ticket description, in characters (`MAX_DESCRIPTION_LEN`, default 10000).
+/// - `max_tickets_per_user`: Most tickets a non-admin may hold before `create_ticket` refuses more; unset means no limit (`MAX_TICKETS_PER_USER`).
+/// - `db_max_connections`: Size of the connection pool; `None` keeps the driver default (`DB_MAX_CONNECTIONS`).
+/// - `db_acquire_timeout_secs`: How long a request waits for a free pooled connection before failing with `503` (`DB_ACQUIRE_TIMEOUT_SECS`, default 5).
+/// - `slow_query_ms`: Statements taking at least this many milliseconds are logged at `warn`; unset disables the log (`SLOW_QUERY_MS`).
+/// - `require_email_verification`: Refuse to log in users who haven't verified their email (`REQUIRE_EMAIL_VERIFICATION`).
+/// - `bcrypt_cost`: Work factor for new password hashes, between 4 and 31; older hashes are upgraded on login (`BCRYPT_COST`, default 12).
+/// - `password_reset_ttl_secs`: How long a password reset token stays valid (`PASSWORD_RESET_TTL_SECS`, default 3600).
+/// - `impersonation_ttl_secs`: Lifetime of tokens an admin gets from `POST /admin/users/{id}/impersonate` (`IMPERSONATION_TTL_SECS`, default 900).
+/// - `default_role`: Role given to users who register without asking for one (`DEFAULT_ROLE`, default `agent`).
+/// - `allow_self_admin_registration`: Let anyone register as an admin; otherwise only an admin's token may create one (`ALLOW_SELF_ADMIN_REGISTRATION`).
+/// - `tag_ownership`: Let agents change or delete only the tags they own; global tags become admin-only (`TAG_OWNERSHIP`).
+/// - `content_security_policy`: `Content-Security-Policy` sent on every response; empty disables it (`CONTENT_SECURITY_POLICY`, default `default-src 'none'; frame-ancestors 'none'`).
+/// - `smtp_url`: SMTP relay for outgoing mail, used when built with the `smtp` feature; unset logs mail instead (`SMTP_URL`).
+/// - `email_from`: `From` address on outgoing mail (`EMAIL_FROM`, default `TagBlaze <no-reply@tagblaze.dev>`).
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    pub database_url: String,
+    pub database_read_url: Option<String>,
+    pub jwt_secret: String,
+    pub jwt_header: Option<String>,
+    pub run_migrations: bool,
+    pub seed_on_empty: bool,
+    pub cors_allowed_origins: Vec<String>,
+    pub cors_allow_credentials: bool,
+    pub cors_max_age_secs: Option<u64>,
+    pub trusted_proxies: Vec<Cidr>,
+    pub rate_limit_requests: u64,
+    pub rate_limit_window_secs: u64,
+    pub maintenance_mode: bool,
+    pub maintenance_retry_after_secs: u64,
+    pub read_only: bool,
+    pub default_page_size: u64,
+    pub max_page_size: u64,
+    pub max_description_len: usize,
+    pub max_tickets_per_user: Option<u64>,
+    pub db_max_connections: Option<u32>,
+    pub db_acquire_timeout_secs: u64,
+    pub slow_query_ms: Option<u64>,
+    pub require_email_verification: bool,
+    pub bcrypt_cost: u32,
+    pub password_reset_ttl_secs: u64,
+    pub impersonation_ttl_secs: u64,
+    pub default_role: Role,
+    pub allow_self_admin_registration: bool,
+    pub tag_ownership: bool,
+    pub content_security_policy: String,
+    pub smtp_url: Option<String>,
+    pub email_from: String,
+}
+
+impl Config {
+    /// Build the configuration from environment variables.
+    ///
+    /// Call [`load_env_file`] once beforehand to pick up a dotenv file.
+    ///
+    /// # Panics
+    /// Panics if `DATABASE_URL` or `JWT_SECRET` is not set.
+    pub fn from_env() -> Self {
+        let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let jwt_secret = env::var("JWT_SECRET").expect("JWT_SECRET must be set");
+        Self {
+            database_url,
+            database_read_url: env::var("DATABASE_READ_URL")
+                .ok()
+                .filter(|v| !v.trim().is_empty()),
+            jwt_secret,
+            jwt_header: env::var("JWT_HEADER")
+                .ok()
+                .map(|v| v.trim().to_ascii_lowercase())
+                .filter(|v| HeaderName::from_bytes(v.as_bytes()).is_ok()),
+            run_migrations: env_flag("RUN_MIGRATIONS"),
+            seed_on_empty: env_flag("SEED_ON_EMPTY"),
+            cors_allowed_origins: env_list("CORS_ALLOWED_ORIGINS"),
+            cors_allow_credentials: env_flag("CORS_ALLOW_CREDENTIALS"),
+            cors_max_age_secs: env_parse("CORS_MAX_AGE_SECS"),
+            trusted_proxies: env_list("TRUSTED_PROXIES")
+                .iter()
+                .filter_map(|v| v.parse().ok())
+                .collect(),
+            rate_limit_requests: env_parse("RATE_LIMIT_REQUESTS").unwrap_or(60),
+            rate_limit_window_secs: env_parse("RATE_LIMIT_WINDOW_SECS").unwrap_or(60),
+            maintenance_mode: env_flag("MAINTENANCE_MODE"),
+            maintenance_retry_after_secs: env_parse("MAINTENANCE_RETRY_AFTER_SECS").unwrap_or(120),
+            read_only: env_flag("READ_ONLY"),
+            default_page_size: env_parse("DEFAULT_PAGE_SIZE").unwrap_or(20),
+            max_page_size: env_parse("MAX_PAGE_SIZE").unwrap_or(100),
+            max_description_len: env_parse("MAX_DESCRIPTION_LEN").unwrap_or(10_000),
+            max_tickets_per_user: env_parse("MAX_TICKETS_PER_USER"),
+            db_max_connections: env_parse("DB_MAX_CONNECTIONS"),
+            db_acquire_timeout_secs: env_parse("DB_ACQUIRE_TIMEOUT_SECS").unwrap_or(5),
+            slow_query_ms: env_parse("SLOW_QUERY_MS"),
+            require_email_verification: env_flag("REQUIRE_EMAIL_VERIFICATION"),
+            bcrypt_cost: env_parse("BCRYPT_COST")
+                .unwrap_or(bcrypt::DEFAULT_COST)
+                .clamp(4, 31),
+            password_reset_ttl_secs: env_parse("PASSWORD_RESET_TTL_SECS").unwrap_or(3600),
+            impersonation_ttl_secs: env_parse("IMPERSONATION_TTL_SECS").unwrap_or(900),
+            default_role: env::var("DEFAULT_ROLE")
+                .ok()
+                .and_then(|v| Role::parse(&v))
+                .unwrap_or_default(),
+            allow_self_admin_registration: env_flag("ALLOW_SELF_ADMIN_REGISTRATION"),
+            tag_ownership: env_flag("TAG_OWNERSHIP"),
+            content_security_policy: env::var("CONTENT_SECURITY_POLICY")
+                .unwrap_or_else(|_| DEFAULT_CSP.into()),
+            smtp_url: env::var("SMTP_URL").ok().filter(|v| !v.trim().is_empty()),
+            email_from: env::var("EMAIL_FROM")
+                .unwrap_or_else(|_| "TagBlaze <no-reply@tagblaze.dev>".into()),
+        }
+    }
+}
+
+impl Config {
+    /// Log the resolved configuration at `info`, once at startup.
+    ///
+    /// Secrets never reach the log: database and SMTP URLs are cut down to
+    /// their host, and `jwt_secret` only shows as `[redacted]`.
+    pub fn log_effective(&self) {
+        tracing::info!(
+            database = url_host(&self.database_url),
+            read_database = self.database_read_url.as_deref().map_or("none", url_host),
+            db_max_connections = ?self.db_max_connections,
+            db_acquire_timeout_secs = self.db_acquire_timeout_secs,
+            slow_query_ms = ?self.slow_query_ms,
+            jwt_secret = "[redacted]",
+            jwt_header = self.jwt_header.as_deref().unwrap_or("none"),
+            jwt_ttl_secs = TOKEN_TTL.num_seconds(),
+            run_migrations = self.run_migrations,
+            seed_on_empty = self.seed_on_empty,
+            cors_allowed_origins = ?self.cors_allowed_origins,
+            cors_allow_credentials = self.cors_allow_credentials,
+            trusted_proxies = self.trusted_proxies.len(),
+            rate_limit = format!("{}/{}s", self.rate_limit_requests, self.rate_limit_window_secs),
+            maintenance_mode = self.maintenance_mode,
+            read_only = self.read_only,
+            max_tickets_per_user = ?self.max_tickets_per_user,
+            require_email_verification = self.require_email_verification,
+            bcrypt_cost = self.bcrypt_cost,
+            default_role = self.default_role.as_str(),
+            allow_self_admin_registration = self.allow_self_admin_registration,
+            tag_ownership = self.tag_ownership,
+            smtp = self.smtp_url.as_deref().map_or("none", url_host),
+            "⚙️ Effective configuration"
+        );
+    }
+}
+
+/// The `host[:port]` part of a URL, without scheme, credentials, or path.
+fn url_host(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host)
+}
+
+/// Pick the dotenv file in `dir` for an `APP_ENV` value.
+///
+/// Prefers `.env.{app_env}` and falls back to `.env`; returns `None` when
+/// neither exists.
+pub fn select_env_file(dir: &Path, app_env: Option<&str>) -> Option<PathBuf> {
+    let specific = app_env
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| dir.join(format!(".env.{name}")));
+
+    specific
+        .into_iter()
+        .chain(std::iter::once(dir.join(".env")))
+        .find(|path| path.is_file())
+}
+
+/// Load the dotenv file selected by `APP_ENV` from the working directory.
+///
+/// Call once at startup, before anything reads the environment. Variables
+/// already set in the process environment win over the file.
+pub fn load_env_file() -> Option<PathBuf> {
+    let app_env = env::var("APP_ENV").ok();
+    let path = select_env_file(Path::new("."), app_env.as_deref())?;
+    dotenvy::from_path(&path).ok()?;
+    Some(path)
+}
+
+/// Read a boolean flag; `true`, `1`, and `yes` (any case) enable it.
+fn env_flag(name: &str) -> bool {
+    env::var(name)
+        .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "true" | "1" | "yes"))
+        .unwrap_or(false)
+}
+
+/// Read a comma-separated list, dropping empty entries and `*` (which means "any").
+fn env_list(name: &str) -> Vec<String> {
+    env::var(name)
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty() && *v != "*")
+        .map(String::from)
+        .collect()
+}
+
+/// Read and parse a value, treating missing or unparsable values as unset.
+fn env_parse<T: std::str::FromStr>(name: &str) -> Option<T> {
+    env::var(name).ok().and_then(|v| v.trim().parse().ok())
+}
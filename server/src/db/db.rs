@@ -1,29 +1,65 @@
-/// Establishes a connection to the database using the URL specified in the `DATABASE_URL` environment variable.
-/// 
-/// This function loads environment variables from a `.env` file (if present) and attempts to connect to the database.
-/// 
+use std::time::Duration;
+
+use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+
+use crate::{config::Config, db::instrument::log_slow_queries};
+
+/// Opens the connection pool described by `config`.
+///
+/// The pool waits at most `db_acquire_timeout_secs` for a free connection;
+/// handlers surface a timeout as `503` through
+/// [`DbError`](crate::middleware::error::DbError).
+///
+/// With `slow_query_ms` set, statements taking at least that long are
+/// logged at `warn` (see [`log_slow_queries`]).
+///
+/// Call this once at startup and share the returned handle through
+/// [`AppState`](crate::state::AppState); it is a pool, so cloning it is cheap.
+///
 /// # Panics
-/// 
-/// Panics if the `DATABASE_URL` environment variable is not set or if the connection to the database fails.
-/// 
+///
+/// Panics if the connection to the database fails.
+///
 /// # Returns
-/// 
+///
 /// Returns an active [`DatabaseConnection`] on success.
-/// 
+///
 /// # Example
-/// 
-/// ```rust
-/// let connection = connect().await;
+///
+/// ```rust,ignore
+/// let connection = connect(&config).await;
 /// ```
+pub async fn connect(config: &Config) -> DatabaseConnection {
+    connect_to(&config.database_url, config).await
+}
 
-use sea_orm::{Database, DatabaseConnection};
-use std::env;
+/// Opens the read replica pool when `database_read_url` is set.
+///
+/// Pool settings match [`connect`]. Hand the result to
+/// [`AppState::with_read_db`](crate::state::AppState::with_read_db).
+///
+/// # Panics
+///
+/// Panics if the replica is configured but can't be reached.
+pub async fn connect_read(config: &Config) -> Option<DatabaseConnection> {
+    match &config.database_read_url {
+        Some(url) => Some(connect_to(url, config).await),
+        None => None,
+    }
+}
 
-pub async fn connect() -> DatabaseConnection {
-    dotenvy::dotenv().ok();
-    let db_url = env::var("DATABASE_URL").expect("Missing DATABASE_URL");
+async fn connect_to(url: &str, config: &Config) -> DatabaseConnection {
+    let mut options = ConnectOptions::new(url.to_owned());
+    options.acquire_timeout(Duration::from_secs(config.db_acquire_timeout_secs));
+    if let Some(max) = config.db_max_connections {
+        options.max_connections(max);
+    }
 
-    Database::connect(&db_url)
+    let mut db = Database::connect(options)
         .await
-        .expect("❌ Failed to connect to database")
+        .expect("❌ Failed to connect to database");
+    if let Some(ms) = config.slow_query_ms {
+        log_slow_queries(&mut db, Duration::from_millis(ms));
+    }
+    db
 }
@@ -0,0 +1,46 @@
+//! Tracing helpers for database calls.
+
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
+
+use sea_orm::DatabaseConnection;
+use tracing::{Instrument, field};
+
+/// Run a database future inside a `db_query` span and record how long it took.
+///
+/// The span carries `query` (a short label such as `"tickets.list"`) and
+/// `duration_ms`, so slow operations show up in traces attributed to the
+/// handler span that issued them.
+///
+/// # Example
+/// ```rust,ignore
+/// let tickets = timed("tickets.list", ticket::Entity::find().all(db)).await?;
+/// ```
+pub async fn timed<F: Future>(query: &'static str, fut: F) -> F::Output {
+    let span = tracing::info_span!("db_query", query, duration_ms = field::Empty);
+    let started = Instant::now();
+    let output = fut.instrument(span.clone()).await;
+    span.record("duration_ms", started.elapsed().as_millis() as u64);
+    output
+}
+
+/// Log every statement on `db` that takes at least `threshold` at `warn`.
+///
+/// Unlike [`timed`], this sees each SQL statement rather than a labelled
+/// operation, so it also catches queries nobody thought to wrap. The event
+/// carries the statement text, `duration_ms`, and whether it `failed`.
+/// Raw SQL sent through `execute_unprepared` bypasses the hook.
+pub fn log_slow_queries(db: &mut DatabaseConnection, threshold: Duration) {
+    db.set_metric_callback(move |info| {
+        if info.elapsed >= threshold {
+            tracing::warn!(
+                statement = %info.statement,
+                duration_ms = info.elapsed.as_millis() as u64,
+                failed = info.failed,
+                "🐢 slow query"
+            );
+        }
+    });
+}
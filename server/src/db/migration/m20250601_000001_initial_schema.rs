@@ -0,0 +1,161 @@
+use sea_orm_migration::prelude::*;
+
+/// Baseline: the tables as they were when migrations were introduced for
+/// the original schema (`user`, `tag`, `ticket` and `ticket_tag`).
+///
+/// Spelled out rather than derived from the entities, so later entity
+/// changes can't alter what this creates; those land in their own
+/// migrations. Tables are created only if missing, so databases that
+/// predate migrations adopt this one as is and are brought up to date by
+/// `m20250601_000002_pre_migration_changes`.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(User::Table)
+                    .if_not_exists()
+                    .col(id(User::Id))
+                    .col(ColumnDef::new(User::Email).text().not_null().unique_key())
+                    .col(ColumnDef::new(User::Name).text().not_null())
+                    .col(ColumnDef::new(User::Password).text().not_null())
+                    .col(ColumnDef::new(User::Role).text().not_null())
+                    .col(ColumnDef::new(User::CreatedAt).timestamp())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Tag::Table)
+                    .if_not_exists()
+                    .col(id(Tag::Id))
+                    .col(ColumnDef::new(Tag::Name).text().not_null())
+                    .col(ColumnDef::new(Tag::CreatedAt).timestamp())
+                    .col(ColumnDef::new(Tag::UpdatedAt).timestamp())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Ticket::Table)
+                    .if_not_exists()
+                    .col(id(Ticket::Id))
+                    .col(ColumnDef::new(Ticket::Title).text().not_null())
+                    .col(ColumnDef::new(Ticket::Description).text())
+                    .col(ColumnDef::new(Ticket::Status).text())
+                    .col(ColumnDef::new(Ticket::UserId).integer())
+                    .col(ColumnDef::new(Ticket::CreatedAt).timestamp())
+                    .col(ColumnDef::new(Ticket::UpdatedAt).timestamp())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-ticket-user_id")
+                            .from(Ticket::Table, Ticket::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TicketTag::Table)
+                    .if_not_exists()
+                    .col(id(TicketTag::Id))
+                    .col(ColumnDef::new(TicketTag::TicketId).integer().not_null())
+                    .col(ColumnDef::new(TicketTag::TagId).integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-ticket_tag-tag_id")
+                            .from(TicketTag::Table, TicketTag::TagId)
+                            .to(Tag::Table, Tag::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-ticket_tag-ticket_id")
+                            .from(TicketTag::Table, TicketTag::TicketId)
+                            .to(Ticket::Table, Ticket::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in [
+            TicketTag::Table.into_iden(),
+            Ticket::Table.into_iden(),
+            Tag::Table.into_iden(),
+            User::Table.into_iden(),
+        ] {
+            manager
+                .drop_table(Table::drop().table(table).if_exists().to_owned())
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+/// A `serial` primary key.
+fn id(column: impl IntoIden) -> ColumnDef {
+    ColumnDef::new(column)
+        .integer()
+        .not_null()
+        .auto_increment()
+        .primary_key()
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+    Email,
+    Name,
+    Password,
+    Role,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Tag {
+    Table,
+    Id,
+    Name,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Ticket {
+    Table,
+    Id,
+    Title,
+    Description,
+    Status,
+    UserId,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum TicketTag {
+    Table,
+    Id,
+    TicketId,
+    TagId,
+}
@@ -0,0 +1,285 @@
+use sea_orm_migration::prelude::*;
+
+/// Schema changes made before migrations existed, when the entities were
+/// the only definition of the schema.
+///
+/// - `user`: `tokens_valid_after`, `email_verified` (existing users count as
+///   verified) and `email_verification_token`
+/// - `tag`: `description`, `owner_id` (set to `NULL` when the owner is
+///   deleted) and `deleted_at`
+/// - `ticket`: `created_by`
+/// - the `password_reset` and `ticket_tag_event` tables
+/// - a unique index so a ticket carries each tag at most once
+///
+/// Databases kept in step with the entities by hand may already have any
+/// of these, so every change is made only if missing.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .add_column_if_not_exists(ColumnDef::new(User::TokensValidAfter).timestamp())
+                    .add_column_if_not_exists(
+                        ColumnDef::new(User::EmailVerified)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .add_column_if_not_exists(ColumnDef::new(User::EmailVerificationToken).text())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tag::Table)
+                    .add_column_if_not_exists(ColumnDef::new(Tag::Description).text())
+                    .add_column_if_not_exists(ColumnDef::new(Tag::DeletedAt).timestamp())
+                    .to_owned(),
+            )
+            .await?;
+        // A foreign key can't be added "if not exists", so it comes with the column
+        if !manager.has_column("tag", "owner_id").await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Tag::Table)
+                        .add_column(ColumnDef::new(Tag::OwnerId).integer())
+                        .add_foreign_key(
+                            TableForeignKey::new()
+                                .name("fk-tag-owner_id")
+                                .from_tbl(Tag::Table)
+                                .from_col(Tag::OwnerId)
+                                .to_tbl(User::Table)
+                                .to_col(User::Id)
+                                .on_delete(ForeignKeyAction::SetNull)
+                                .on_update(ForeignKeyAction::NoAction),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Ticket::Table)
+                    .add_column_if_not_exists(ColumnDef::new(Ticket::CreatedBy).integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PasswordReset::Table)
+                    .if_not_exists()
+                    .col(id(PasswordReset::Id))
+                    .col(ColumnDef::new(PasswordReset::UserId).integer().not_null())
+                    .col(
+                        ColumnDef::new(PasswordReset::TokenHash)
+                            .text()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(PasswordReset::ExpiresAt)
+                            .timestamp()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(PasswordReset::UsedAt).timestamp())
+                    .col(
+                        ColumnDef::new(PasswordReset::CreatedAt)
+                            .timestamp()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-password_reset-user_id")
+                            .from(PasswordReset::Table, PasswordReset::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TicketTagEvent::Table)
+                    .if_not_exists()
+                    .col(id(TicketTagEvent::Id))
+                    .col(
+                        ColumnDef::new(TicketTagEvent::TicketId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(TicketTagEvent::TagId).integer().not_null())
+                    .col(ColumnDef::new(TicketTagEvent::UserId).integer())
+                    .col(ColumnDef::new(TicketTagEvent::Action).text().not_null())
+                    .col(
+                        ColumnDef::new(TicketTagEvent::CreatedAt)
+                            .timestamp()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-ticket_tag_event-ticket_id")
+                            .from(TicketTagEvent::Table, TicketTagEvent::TicketId)
+                            .to(Ticket::Table, Ticket::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-ticket_tag_event-tag_id")
+                            .from(TicketTagEvent::Table, TicketTagEvent::TagId)
+                            .to(Tag::Table, Tag::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-ticket_tag_event-user_id")
+                            .from(TicketTagEvent::Table, TicketTagEvent::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("ticket_tag_ticket_id_tag_id")
+                    .table(TicketTag::Table)
+                    .col(TicketTag::TicketId)
+                    .col(TicketTag::TagId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .if_exists()
+                    .name("ticket_tag_ticket_id_tag_id")
+                    .to_owned(),
+            )
+            .await?;
+        for table in [
+            TicketTagEvent::Table.into_iden(),
+            PasswordReset::Table.into_iden(),
+        ] {
+            manager
+                .drop_table(Table::drop().table(table).if_exists().to_owned())
+                .await?;
+        }
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Ticket::Table)
+                    .drop_column(Ticket::CreatedBy)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tag::Table)
+                    .drop_column(Tag::Description)
+                    .drop_column(Tag::OwnerId)
+                    .drop_column(Tag::DeletedAt)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .drop_column(User::TokensValidAfter)
+                    .drop_column(User::EmailVerified)
+                    .drop_column(User::EmailVerificationToken)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// A `serial` primary key.
+fn id(column: impl IntoIden) -> ColumnDef {
+    ColumnDef::new(column)
+        .integer()
+        .not_null()
+        .auto_increment()
+        .primary_key()
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+    TokensValidAfter,
+    EmailVerified,
+    EmailVerificationToken,
+}
+
+#[derive(DeriveIden)]
+enum Tag {
+    Table,
+    Id,
+    Description,
+    OwnerId,
+    DeletedAt,
+}
+
+#[derive(DeriveIden)]
+enum Ticket {
+    Table,
+    Id,
+    CreatedBy,
+}
+
+#[derive(DeriveIden)]
+enum TicketTag {
+    Table,
+    TicketId,
+    TagId,
+}
+
+#[derive(DeriveIden)]
+enum PasswordReset {
+    Table,
+    Id,
+    UserId,
+    TokenHash,
+    ExpiresAt,
+    UsedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum TicketTagEvent {
+    Table,
+    Id,
+    TicketId,
+    TagId,
+    UserId,
+    Action,
+    CreatedAt,
+}
@@ -0,0 +1,96 @@
+use sea_orm_migration::prelude::*;
+
+/// Add `tag_subscription`, one row per user following a tag.
+///
+/// A user follows each tag at most once. The table and index are created
+/// only if missing, like the baseline's.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TagSubscription::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TagSubscription::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(TagSubscription::UserId).integer().not_null())
+                    .col(ColumnDef::new(TagSubscription::TagId).integer().not_null())
+                    .col(
+                        ColumnDef::new(TagSubscription::CreatedAt)
+                            .timestamp()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-tag_subscription-user_id")
+                            .from(TagSubscription::Table, TagSubscription::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-tag_subscription-tag_id")
+                            .from(TagSubscription::Table, TagSubscription::TagId)
+                            .to(Tag::Table, Tag::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("tag_subscription_user_id_tag_id")
+                    .table(TagSubscription::Table)
+                    .col(TagSubscription::UserId)
+                    .col(TagSubscription::TagId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(TagSubscription::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TagSubscription {
+    Table,
+    Id,
+    UserId,
+    TagId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Tag {
+    Table,
+    Id,
+}
@@ -0,0 +1,38 @@
+use sea_orm_migration::prelude::*;
+
+/// Make tag names unique per owner, ignoring case.
+///
+/// An agent's tags are unique on `(owner_id, lower(name))`; global tags
+/// (`owner_id IS NULL`) on `lower(name)` alone, since `NULL`s never collide
+/// in a unique index. Soft-deleted tags keep their name so they can be
+/// restored. Databases that already hold clashing names must merge them
+/// (`POST /tags/merge`) before this runs.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "CREATE UNIQUE INDEX IF NOT EXISTS tag_owner_id_lower_name \
+             ON tag (owner_id, lower(name)) WHERE owner_id IS NOT NULL",
+        )
+        .await?;
+        db.execute_unprepared(
+            "CREATE UNIQUE INDEX IF NOT EXISTS tag_global_lower_name \
+             ON tag (lower(name)) WHERE owner_id IS NULL",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DROP INDEX IF EXISTS tag_owner_id_lower_name")
+            .await?;
+        db.execute_unprepared("DROP INDEX IF EXISTS tag_global_lower_name")
+            .await?;
+        Ok(())
+    }
+}
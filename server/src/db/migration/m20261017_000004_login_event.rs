@@ -0,0 +1,81 @@
+use sea_orm_migration::prelude::*;
+
+/// Add `login_event`, one row per login attempt on an existing account.
+///
+/// Indexed on `(user_id, created_at)` for the newest-first history. The
+/// table and index are created only if missing, like the baseline's.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(LoginEvent::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(LoginEvent::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(LoginEvent::UserId).integer().not_null())
+                    .col(ColumnDef::new(LoginEvent::Ip).text())
+                    .col(ColumnDef::new(LoginEvent::UserAgent).text())
+                    .col(ColumnDef::new(LoginEvent::Success).boolean().not_null())
+                    .col(ColumnDef::new(LoginEvent::CreatedAt).timestamp().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-login_event-user_id")
+                            .from(LoginEvent::Table, LoginEvent::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("login_event_user_id_created_at")
+                    .table(LoginEvent::Table)
+                    .col(LoginEvent::UserId)
+                    .col(LoginEvent::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(LoginEvent::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum LoginEvent {
+    Table,
+    Id,
+    UserId,
+    Ip,
+    UserAgent,
+    Success,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,39 @@
+//! Versioned schema changes, applied with [`Migrator`].
+//!
+//! Each migration lives in its own `mYYYYMMDD_HHMMSS_name.rs` module and is
+//! listed in [`Migrator::migrations`] in the order it must run. Applied
+//! migrations are recorded in the `seaql_migrations` table.
+
+use sea_orm_migration::prelude::*;
+
+mod m20250601_000001_initial_schema;
+mod m20250601_000002_pre_migration_changes;
+mod m20261016_000001_ticket_updated_by;
+mod m20261017_000001_user_is_active;
+mod m20261017_000002_tag_subscription;
+mod m20261017_000003_tag_name_per_owner;
+mod m20261017_000004_login_event;
+
+/// Every migration this binary knows about.
+///
+/// # Example
+/// ```rust,ignore
+/// Migrator::up(&db, None).await?;
+/// let pending = Migrator::get_pending_migrations(&db).await?;
+/// ```
+pub struct Migrator;
+
+#[async_trait::async_trait]
+impl MigratorTrait for Migrator {
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        vec![
+            Box::new(m20250601_000001_initial_schema::Migration),
+            Box::new(m20250601_000002_pre_migration_changes::Migration),
+            Box::new(m20261016_000001_ticket_updated_by::Migration),
+            Box::new(m20261017_000001_user_is_active::Migration),
+            Box::new(m20261017_000002_tag_subscription::Migration),
+            Box::new(m20261017_000003_tag_name_per_owner::Migration),
+            Box::new(m20261017_000004_login_event::Migration),
+        ]
+    }
+}
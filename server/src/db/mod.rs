@@ -1,8 +1,12 @@
+#[allow(clippy::module_inception)]
 pub mod db;
+pub mod instrument;
+pub mod migration;
+pub mod seed;
 
 // #[tokio::main]
 // async fn main() {
 //     // ...
-//     let _db = db::connect().await;
+//     let _db = db::connect(&config.database_url).await;
 //     // ...
 // }
@@ -0,0 +1,174 @@
+//! Sample data used by `POST /admin/dev/reset-db` and the `SEED_ON_EMPTY` startup option.
+
+use crate::models::{tag, ticket, ticket_tag, user};
+use bcrypt::{DEFAULT_COST, hash};
+use chrono::Utc;
+use futures::future::join_all;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, Set};
+
+/// Users inserted by [`seed_users`].
+pub const SEED_USERS: usize = 3;
+/// Tags inserted by [`seed_tags_and_tickets`].
+pub const SEED_TAGS: usize = 3;
+/// Tickets inserted by [`seed_tags_and_tickets`].
+pub const SEED_TICKETS: usize = 2;
+/// `ticket_tag` rows inserted by [`seed_tags_and_tickets`].
+pub const SEED_RELATIONS: usize = 3;
+
+/// Seed sample data, but only when the `user` table is empty.
+///
+/// Never truncates or modifies existing rows, so it is safe to call on every
+/// startup.
+///
+/// Returns `true` if the database was seeded, `false` if it already had users.
+pub async fn seed_if_empty(db: &DatabaseConnection) -> Result<bool, DbErr> {
+    if user::Entity::find().count(db).await? > 0 {
+        return Ok(false);
+    }
+
+    let users = seed_users(db).await?;
+    seed_tags_and_tickets(db, &users).await?;
+    Ok(true)
+}
+
+/// Seed the database with default users.
+///
+/// Creates 3 users (Zoya, Ankit, Divya) with pre-defined roles and hashed passwords.
+///
+/// Returns a `Vec` of inserted user models on success.
+pub async fn seed_users(db: &DatabaseConnection) -> Result<Vec<user::Model>, DbErr> {
+    let now = Utc::now().naive_utc();
+
+    // 🔐 Secure default password (same for all)
+    let hashed = hash("devpass123", DEFAULT_COST).expect("Password hashing failed");
+
+    // 👤 Define user entries
+    let users = vec![
+        user::ActiveModel {
+            email: Set("zoya@tagblaze.dev".into()),
+            name: Set("Zoya".into()),
+            password: Set(hashed.clone()),
+            role: Set("agent".into()),
+            created_at: Set(Some(now)),
+            ..Default::default()
+        },
+        user::ActiveModel {
+            email: Set("ankit@tagblaze.dev".into()),
+            name: Set("Ankit".into()),
+            password: Set(hashed.clone()),
+            role: Set("admin".into()),
+            created_at: Set(Some(now)),
+            ..Default::default()
+        },
+        user::ActiveModel {
+            email: Set("divya@tagblaze.dev".into()),
+            name: Set("Divya Singh".into()),
+            password: Set(hashed.clone()),
+            role: Set("agent".into()),
+            created_at: Set(Some(now)),
+            ..Default::default()
+        },
+    ];
+
+    // 📥 Insert users concurrently
+    let inserted = join_all(users.into_iter().map(|u| u.insert(db)))
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(inserted)
+}
+
+/// Seed default tags, tickets, and their relations to users and each other.
+///
+/// - Inserts a list of tags
+/// - Inserts example tickets assigned to users
+/// - Creates ticket-tag associations (many-to-many)
+///
+/// Returns a tuple with counts: (tags, tickets, ticket_tag relations)
+pub async fn seed_tags_and_tickets(
+    db: &DatabaseConnection,
+    users: &[user::Model],
+) -> Result<(usize, usize, usize), DbErr> {
+    let now = Utc::now().naive_utc();
+
+    // 🏷️ Tags
+    let tags = vec![
+        tag::ActiveModel {
+            name: Set("Bug".into()),
+            created_at: Set(Some(now)),
+            updated_at: Set(Some(now)),
+            ..Default::default()
+        },
+        tag::ActiveModel {
+            name: Set("Feature".into()),
+            created_at: Set(Some(now)),
+            updated_at: Set(Some(now)),
+            ..Default::default()
+        },
+        tag::ActiveModel {
+            name: Set("Urgent".into()),
+            created_at: Set(Some(now)),
+            updated_at: Set(Some(now)),
+            ..Default::default()
+        },
+    ];
+
+    let saved_tags = join_all(tags.into_iter().map(|t| t.insert(db)))
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // 🎫 Tickets
+    let tickets = vec![
+        ticket::ActiveModel {
+            title: Set("Fix navbar overflow bug".into()),
+            description: Set(Some("Navbar overlaps on mobile screens".into())),
+            user_id: Set(Some(users[0].id)),
+            created_by: Set(Some(users[0].id)),
+            created_at: Set(Some(now)),
+            updated_at: Set(Some(now)),
+            ..Default::default()
+        },
+        ticket::ActiveModel {
+            title: Set("Add dark mode toggle".into()),
+            description: Set(Some("Users should be able to switch themes".into())),
+            user_id: Set(Some(users[1].id)),
+            created_by: Set(Some(users[1].id)),
+            created_at: Set(Some(now)),
+            updated_at: Set(Some(now)),
+            ..Default::default()
+        },
+    ];
+
+    let saved_tickets = join_all(tickets.into_iter().map(|t| t.insert(db)))
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // 🔗 Ticket-Tag Relations
+    let relations = vec![
+        ticket_tag::ActiveModel {
+            ticket_id: Set(saved_tickets[0].id),
+            tag_id: Set(saved_tags[0].id),
+            ..Default::default()
+        },
+        ticket_tag::ActiveModel {
+            ticket_id: Set(saved_tickets[0].id),
+            tag_id: Set(saved_tags[2].id),
+            ..Default::default()
+        },
+        ticket_tag::ActiveModel {
+            ticket_id: Set(saved_tickets[1].id),
+            tag_id: Set(saved_tags[1].id),
+            ..Default::default()
+        },
+    ];
+
+    let saved_relations = join_all(relations.into_iter().map(|r| r.insert(db)))
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((saved_tags.len(), saved_tickets.len(), saved_relations.len()))
+}
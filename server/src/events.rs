@@ -0,0 +1,65 @@
+//! In-process broadcast of ticket changes.
+//!
+//! Ticket handlers publish a [`TicketEvent`] on the channel held in
+//! [`AppState`](crate::state::AppState) after every successful write, and the
+//! SSE endpoint (`GET /tickets/stream`) forwards them to connected clients.
+
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use crate::models::ticket;
+
+/// How many events a slow subscriber may fall behind before it starts lagging.
+const CHANNEL_CAPACITY: usize = 256;
+
+/// The kind of change that happened to a ticket.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TicketEventKind {
+    Created,
+    Updated,
+    Deleted,
+}
+
+impl TicketEventKind {
+    /// Name used for the SSE `event:` field.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TicketEventKind::Created => "ticket_created",
+            TicketEventKind::Updated => "ticket_updated",
+            TicketEventKind::Deleted => "ticket_deleted",
+        }
+    }
+}
+
+/// A single ticket change, carrying the ticket as it looked after the write
+/// (or right before it was deleted).
+#[derive(Clone, Debug, Serialize)]
+pub struct TicketEvent {
+    pub kind: TicketEventKind,
+    pub ticket: ticket::Model,
+}
+
+impl TicketEvent {
+    /// Whether a user may see this event, using the same rules as `get_tickets`:
+    /// admins see everything, everyone else only tickets assigned to them.
+    pub fn visible_to(&self, user_id: i32, is_admin: bool) -> bool {
+        is_admin || self.ticket.user_id == Some(user_id)
+    }
+}
+
+/// Create the sender half of a fresh ticket event channel.
+pub fn channel() -> broadcast::Sender<TicketEvent> {
+    broadcast::channel(CHANNEL_CAPACITY).0
+}
+
+/// Publish a ticket event to all current subscribers.
+///
+/// Having no subscribers is not an error; the event is simply dropped.
+pub fn publish(
+    sender: &broadcast::Sender<TicketEvent>,
+    kind: TicketEventKind,
+    ticket: ticket::Model,
+) {
+    let _ = sender.send(TicketEvent { kind, ticket });
+}
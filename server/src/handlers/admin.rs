@@ -1,56 +1,208 @@
-use crate::db::db::connect;
-use crate::models::{tag, ticket, ticket_tag, user};
-use axum::{Json, http::StatusCode, response::IntoResponse};
-use bcrypt::{DEFAULT_COST, hash};
-use chrono::Local;
-use futures::future::join_all;
+use crate::db::{
+    instrument::timed,
+    seed::{
+        SEED_RELATIONS, SEED_TAGS, SEED_TICKETS, SEED_USERS, seed_tags_and_tickets, seed_users,
+    },
+};
+use crate::events::{self, TicketEventKind};
+use crate::handlers::{
+    tag::{MAX_TAG_NAME_LEN, find_named},
+    ticket::{StatusCount, count_by_status},
+};
+use crate::jobs::JobStatus;
+use crate::middleware::{
+    auth::{AuthUser, Impersonator},
+    error::DbError,
+};
+use crate::models::{
+    login_event, password_reset, tag, tag_subscription, ticket, ticket_tag, ticket_tag_event,
+    user::{self, Role, UserPublic},
+};
+use crate::state::AppState;
+use crate::utils::{
+    jwt::create_impersonation_jwt, pagination::Pagination, path::Path, token,
+    validation::ValidationErrors,
+};
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
 use sea_orm::{
-    ActiveModelTrait, ConnectionTrait, DatabaseBackend, DatabaseConnection, DbErr, Set, Statement,
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseBackend, DatabaseConnection,
+    DatabaseTransaction, DbErr, EntityTrait, FromQueryResult, IntoActiveModel, JoinType,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, RelationTrait, Set, Statement,
+    TransactionTrait, sea_query::Expr,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::Arc,
 };
 
+/// Query parameters accepted by `reset_db`.
+#[derive(Debug, Default, Deserialize)]
+pub struct ResetDbQuery {
+    /// Run the reset on a background task and return a job ID (`?async=true`).
+    #[serde(default, rename = "async")]
+    pub background: bool,
+    /// Report what the reset would do without touching any rows (`?dry_run=true`).
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
 /// Reset and reseed the database with initial sample data.
 ///
 /// This endpoint performs the following operations:
-/// - Truncates all relevant tables (`user`, `tag`, `ticket`, `ticket_tag`)
+/// - Truncates all relevant tables (`user`, `tag`, `ticket`, `ticket_tag`, ...)
 /// - Seeds default users
 /// - Seeds default tags and tickets
 /// - Establishes ticket-tag relationships
 ///
 /// Returns a JSON response indicating success or failure, along with a summary of seeded data.
-pub async fn reset_db() -> impl IntoResponse {
-    let db = connect().await;
+///
+/// With `?async=true` the work runs on a spawned task instead: the response
+/// is `202 ACCEPTED` with `{ "job_id", "status": "pending" }` and a
+/// `Location` header pointing at `GET /admin/dev/jobs/{id}`, whose `result`
+/// is the summary above once the job is `done` or `failed`.
+///
+/// With `?dry_run=true` nothing is truncated or seeded. The response is
+/// `200 OK` with `{ "dry_run": true, "would_truncate": { table: rows },
+/// "would_seed": { "users", "tags", "tickets", "relations" } }`, computed
+/// from the current row counts; `async` is ignored.
+#[tracing::instrument(skip_all)]
+pub async fn reset_db(
+    State(state): State<AppState>,
+    Query(query): Query<ResetDbQuery>,
+) -> impl IntoResponse {
+    if query.dry_run {
+        return match plan_reset(&state.db).await {
+            Ok(plan) => Json(plan).into_response(),
+            Err(e) => DbError(e).into_response(),
+        };
+    }
+
+    if !query.background {
+        let (status, summary) = run_reset(&state).await;
+        return (status, Json(summary)).into_response();
+    }
+
+    let job_id = state.jobs.create().await;
+    let jobs = Arc::clone(&state.jobs);
+    tokio::spawn(async move {
+        jobs.update(job_id, JobStatus::Running, None).await;
+        let (status, summary) = run_reset(&state).await;
+        let outcome = if status.is_success() {
+            JobStatus::Done
+        } else {
+            JobStatus::Failed
+        };
+        jobs.update(job_id, outcome, Some(summary)).await;
+    });
+
+    (
+        StatusCode::ACCEPTED,
+        [(header::LOCATION, format!("/admin/dev/jobs/{job_id}"))],
+        Json(serde_json::json!({ "job_id": job_id, "status": JobStatus::Pending })),
+    )
+        .into_response()
+}
+
+/// Report the status of a background job started by an admin endpoint.
+///
+/// # Path Parameters
+/// - `id`: The `job_id` returned when the job was started
+///
+/// # Returns
+/// - `200 OK` with `{ "id", "status": "pending|running|done|failed", "result" }`
+/// - `404 NOT_FOUND` if no job has that ID (jobs don't survive a restart)
+pub async fn get_job(State(state): State<AppState>, Path(id): Path<u64>) -> impl IntoResponse {
+    match state.jobs.get(id).await {
+        Some(job) => Json(job).into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// What `reset_db` would remove and insert, for `?dry_run=true`.
+async fn plan_reset(db: &DatabaseConnection) -> Result<serde_json::Value, DbErr> {
+    let mut would_truncate = BTreeMap::new();
+    would_truncate.insert("user", user::Entity::find().count(db).await?);
+    would_truncate.insert("login_event", login_event::Entity::find().count(db).await?);
+    would_truncate.insert(
+        "password_reset",
+        password_reset::Entity::find().count(db).await?,
+    );
+    would_truncate.insert("tag", tag::Entity::find().count(db).await?);
+    would_truncate.insert(
+        "tag_subscription",
+        tag_subscription::Entity::find().count(db).await?,
+    );
+    would_truncate.insert("ticket", ticket::Entity::find().count(db).await?);
+    would_truncate.insert("ticket_tag", ticket_tag::Entity::find().count(db).await?);
+    would_truncate.insert(
+        "ticket_tag_event",
+        ticket_tag_event::Entity::find().count(db).await?,
+    );
+
+    Ok(serde_json::json!({
+        "dry_run": true,
+        "would_truncate": would_truncate,
+        "would_seed": {
+            "users": SEED_USERS,
+            "tags": SEED_TAGS,
+            "tickets": SEED_TICKETS,
+            "relations": SEED_RELATIONS
+        }
+    }))
+}
+
+/// Truncate and reseed, returning the status and JSON summary for `reset_db`.
+async fn run_reset(state: &AppState) -> (StatusCode, serde_json::Value) {
+    let db = &state.db;
 
     // ⛔ Step 1: Reset all relevant tables (cascading to clear dependencies)
     let reset_query = r#"
-        TRUNCATE "user", tag, ticket, ticket_tag RESTART IDENTITY CASCADE;
+        TRUNCATE "user", login_event, password_reset, tag, tag_subscription, ticket, ticket_tag, ticket_tag_event RESTART IDENTITY CASCADE;
     "#;
 
     // Execute the raw SQL query
-    if let Err(e) = db
-        .execute(Statement::from_string(
+    if let Err(e) = timed(
+        "admin.truncate",
+        db.execute(Statement::from_string(
             DatabaseBackend::Postgres,
             reset_query,
-        ))
-        .await
+        )),
+    )
+    .await
     {
         eprintln!("❌ DB reset failed: {:?}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({
+            serde_json::json!({
                 "reset": false,
                 "error": e.to_string()
-            })),
+            }),
         );
     }
 
+    // 🗃️ The cached tag list no longer matches the tables
+    state.tag_cache.invalidate().await;
+
     // 🌱 Step 2: Seed users
-    let saved_users = match seed_users(&db).await {
+    let saved_users = match timed("admin.seed_users", seed_users(db)).await {
         Ok(users) => users,
         Err(e) => return db_error_response("users", e),
     };
 
     // 🌱 Step 3: Seed tags, tickets, and ticket-tag relations
-    let (tags, tickets, relations) = match seed_tags_and_tickets(&db, &saved_users).await {
+    let (tags, tickets, relations) = match timed(
+        "admin.seed_tags_and_tickets",
+        seed_tags_and_tickets(db, &saved_users),
+    )
+    .await
+    {
         Ok(data) => data,
         Err(e) => return db_error_response("tags & tickets", e),
     };
@@ -64,157 +216,1121 @@ pub async fn reset_db() -> impl IntoResponse {
         "relations_seeded": relations
     });
 
-    (StatusCode::OK, Json(summary_json))
+    (StatusCode::OK, summary_json)
 }
 
 /// Helper to return consistent JSON error responses when seeding fails.
-fn db_error_response(label: &str, e: DbErr) -> (StatusCode, Json<serde_json::Value>) {
+fn db_error_response(label: &str, e: DbErr) -> (StatusCode, serde_json::Value) {
     eprintln!("❌ Failed to seed {}: {:?}", label, e);
     (
         StatusCode::INTERNAL_SERVER_ERROR,
-        Json(serde_json::json!({
+        serde_json::json!({
             "reset": false,
             "error": format!("{} seeding error: {}", label, e.to_string())
-        })),
+        }),
     )
 }
 
-/// Seed the database with default users.
+/// Payload for changing a user's role.
+#[derive(Debug, Deserialize)]
+pub struct UpdateRole {
+    pub role: Role,
+}
+
+/// Change a user's role (admin only).
+///
+/// The last remaining active admin cannot be demoted, so the system always
+/// keeps at least one account able to log in and manage the others.
+/// Disabled admins don't count.
 ///
-/// Creates 3 users (Zoya, Ankit, Divya) with pre-defined roles and hashed passwords.
+/// # JSON Payload
+/// - `role`: `"admin"` or `"agent"`
 ///
-/// Returns a `Vec` of inserted user models on success.
-async fn seed_users(db: &DatabaseConnection) -> Result<Vec<user::Model>, DbErr> {
-    let now = Local::now().naive_local();
+/// # Returns
+/// - `200 OK` with the updated user (without the password hash)
+/// - `401 UNAUTHORIZED` if token is missing/invalid
+/// - `403 FORBIDDEN` if the caller is not an admin
+/// - `404 NOT_FOUND` if the user doesn't exist
+/// - `409 CONFLICT` if this would demote the last active admin
+/// - `422 UNPROCESSABLE_ENTITY` if `role` is not a known role
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn update_user_role(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    AuthUser(caller): AuthUser,
+    Json(payload): Json<UpdateRole>,
+) -> impl IntoResponse {
+    let db = &state.db;
 
-    // 🔐 Secure default password (same for all)
-    let hashed = hash("devpass123", DEFAULT_COST).expect("Password hashing failed");
+    if caller.role != Role::Admin.as_str() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
 
-    // 👤 Define user entries
-    let users = vec![
-        user::ActiveModel {
-            email: Set("zoya@tagblaze.dev".into()),
-            name: Set("Zoya".into()),
-            password: Set(hashed.clone()),
-            role: Set("agent".into()),
-            created_at: Set(Some(now)),
-            ..Default::default()
-        },
-        user::ActiveModel {
-            email: Set("ankit@tagblaze.dev".into()),
-            name: Set("Ankit".into()),
-            password: Set(hashed.clone()),
-            role: Set("admin".into()),
-            created_at: Set(Some(now)),
-            ..Default::default()
-        },
-        user::ActiveModel {
-            email: Set("divya@tagblaze.dev".into()),
-            name: Set("Divya Singh".into()),
-            password: Set(hashed.clone()),
-            role: Set("agent".into()),
-            created_at: Set(Some(now)),
-            ..Default::default()
+    let target = match user::Entity::find_by_id(id).one(db).await {
+        Ok(Some(u)) => u,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    // 🛡️ Never demote the last admin able to log in
+    if target.role == Role::Admin.as_str() && target.is_active && payload.role != Role::Admin {
+        let admins = user::Entity::find()
+            .filter(user::Column::Role.eq(Role::Admin.as_str()))
+            .filter(user::Column::IsActive.eq(true))
+            .count(db)
+            .await;
+        match admins {
+            Ok(n) if n <= 1 => {
+                return (
+                    StatusCode::CONFLICT,
+                    Json(serde_json::json!({ "error": "last_admin" })),
+                )
+                    .into_response();
+            }
+            Ok(_) => {}
+            Err(e) => return DbError(e).into_response(),
+        }
+    }
+
+    let mut active = target.into_active_model();
+    active.role = Set(payload.role.as_str().to_string());
+
+    match active.update(db).await {
+        Ok(updated) => Json(UserPublic::from(updated)).into_response(),
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// Invalidate every token issued to a user so far (admin only).
+///
+/// Sets the user's `tokens_valid_after` to now (UTC, whole seconds); any
+/// token whose `iat` is earlier is rejected with `token_revoked`. Tokens
+/// issued afterwards, e.g. by logging in again, work normally.
+///
+/// # Returns
+/// - `204 NO_CONTENT` on success
+/// - `401 UNAUTHORIZED` if token is missing/invalid
+/// - `403 FORBIDDEN` if the caller is not an admin
+/// - `404 NOT_FOUND` if the user doesn't exist
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn logout_all(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    AuthUser(caller): AuthUser,
+) -> impl IntoResponse {
+    let db = &state.db;
+
+    if caller.role != Role::Admin.as_str() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    let target = match user::Entity::find_by_id(id).one(db).await {
+        Ok(Some(u)) => u,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    // 🕒 Whole seconds, to line up with the JWT `iat` claim
+    let now = Utc::now().naive_utc().with_nanosecond(0);
+
+    let mut active = target.into_active_model();
+    active.tokens_valid_after = Set(now);
+
+    match active.update(db).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// Payload for enabling or disabling an account.
+#[derive(Debug, Deserialize)]
+pub struct UpdateActive {
+    pub active: bool,
+}
+
+/// Enable or disable a user's account (admin only).
+///
+/// A disabled user keeps their data but can't log in, and every token they
+/// already hold is revoked as with `logout-all`. Enabling lets them log in
+/// again. The last active admin can't be disabled.
+///
+/// # JSON Payload
+/// - `active`: `false` to disable, `true` to enable
+///
+/// # Returns
+/// - `200 OK` with the updated user (without the password hash)
+/// - `401 UNAUTHORIZED` if token is missing/invalid
+/// - `403 FORBIDDEN` if the caller is not an admin
+/// - `404 NOT_FOUND` if the user doesn't exist
+/// - `409 CONFLICT` with `{ "error": "last_admin" }` if this would disable the last active admin
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn set_user_active(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    AuthUser(caller): AuthUser,
+    Json(payload): Json<UpdateActive>,
+) -> impl IntoResponse {
+    let db = &state.db;
+
+    if caller.role != Role::Admin.as_str() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    let target = match user::Entity::find_by_id(id).one(db).await {
+        Ok(Some(u)) => u,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    // 🛡️ Keep at least one admin able to log in
+    if !payload.active && target.is_active && target.role == Role::Admin.as_str() {
+        let admins = user::Entity::find()
+            .filter(user::Column::Role.eq(Role::Admin.as_str()))
+            .filter(user::Column::IsActive.eq(true))
+            .count(db)
+            .await;
+        match admins {
+            Ok(n) if n <= 1 => {
+                return (
+                    StatusCode::CONFLICT,
+                    Json(serde_json::json!({ "error": "last_admin" })),
+                )
+                    .into_response();
+            }
+            Ok(_) => {}
+            Err(e) => return DbError(e).into_response(),
+        }
+    }
+
+    let was_active = target.is_active;
+    let mut active = target.into_active_model();
+    active.is_active = Set(payload.active);
+    if was_active && !payload.active {
+        // 🕒 Whole seconds, to line up with the JWT `iat` claim
+        active.tokens_valid_after = Set(Utc::now().naive_utc().with_nanosecond(0));
+    }
+
+    match active.update(db).await {
+        Ok(updated) => Json(UserPublic::from(updated)).into_response(),
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// Payload for an admin password reset; omit `password` to generate one.
+#[derive(Debug, Default, Deserialize)]
+pub struct AdminResetPassword {
+    pub password: Option<String>,
+}
+
+/// Set a new password for a user (admin only).
+///
+/// For locked-out users, without wiping the database. The password is
+/// taken from the body or, when none is given, generated and returned once.
+/// Either way every token issued to the user so far is revoked, as with
+/// [`logout_all`].
+///
+/// # JSON Payload (optional)
+/// - `password`: The new password
+///
+/// # Returns
+/// - `200 OK` with `{ "password": "..." }` when the password was generated
+/// - `204 NO_CONTENT` when the given password was set
+/// - `401 UNAUTHORIZED` if token is missing/invalid
+/// - `403 FORBIDDEN` if the caller is not an admin
+/// - `404 NOT_FOUND` if the user doesn't exist
+/// - `422 UNPROCESSABLE_ENTITY` if the given password is empty
+/// - `500 INTERNAL_SERVER_ERROR` on hashing or DB failure
+pub async fn reset_user_password(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    AuthUser(caller): AuthUser,
+    payload: Option<Json<AdminResetPassword>>,
+) -> impl IntoResponse {
+    let db = &state.db;
+
+    if caller.role != Role::Admin.as_str() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    let provided = payload.and_then(|Json(p)| p.password);
+    if provided.as_deref() == Some("") {
+        let mut errors = ValidationErrors::default();
+        errors.add("password", "must not be empty");
+        return errors.into_response();
+    }
+
+    let target = match user::Entity::find_by_id(id).one(db).await {
+        Ok(Some(u)) => u,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    // 🎲 Use the given password or make one up
+    let generated = provided.is_none().then(token::password);
+    let password = provided.or_else(|| generated.clone()).unwrap_or_default();
+    let password_hash = match bcrypt::hash(&password, state.config.bcrypt_cost) {
+        Ok(h) => h,
+        Err(e) => {
+            tracing::error!(error = ?e, "password hashing failed");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    // 🚪 Old sessions die with the old password (whole seconds, like `logout_all`)
+    let mut active = target.into_active_model();
+    active.password = Set(password_hash);
+    active.tokens_valid_after = Set(Utc::now().naive_utc().with_nanosecond(0));
+
+    match active.update(db).await {
+        Ok(_) => match generated {
+            Some(password) => Json(serde_json::json!({ "password": password })).into_response(),
+            None => StatusCode::NO_CONTENT.into_response(),
         },
-    ];
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// Issue a short-lived token that authenticates as another user (admin only).
+///
+/// For support work: the admin sees exactly what the user sees. The token
+/// carries an `impersonated_by` claim naming the admin, every request made
+/// with it is logged against them, and it expires after
+/// `impersonation_ttl_secs`. An impersonation token can't be used to mint
+/// another one.
+///
+/// # Path Parameters
+/// - `id`: ID of the user to act as
+///
+/// # Returns
+/// - `200 OK` with `{ "token", "expires_in", "impersonated_by" }`
+/// - `401 UNAUTHORIZED` if token is missing/invalid
+/// - `403 FORBIDDEN` if the caller is not an admin or is already impersonating
+/// - `404 NOT_FOUND` if the user doesn't exist
+/// - `500 INTERNAL_SERVER_ERROR` on signing or DB failure
+pub async fn impersonate_user(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    AuthUser(caller): AuthUser,
+    impersonator: Option<Extension<Impersonator>>,
+) -> impl IntoResponse {
+    if caller.role != Role::Admin.as_str() || impersonator.is_some() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    let target = match user::Entity::find_by_id(id).one(&state.db).await {
+        Ok(Some(u)) => u,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    // 🎭 Sign a token for the target that still names the admin
+    let ttl = state.config.impersonation_ttl_secs;
+    let token = match create_impersonation_jwt(
+        &target.email,
+        &caller.email,
+        Duration::seconds(ttl as i64),
+        &state.config.jwt_secret,
+    ) {
+        Ok(t) => t,
+        Err(e) => {
+            tracing::error!(error = ?e, "impersonation token signing failed");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    tracing::warn!(
+        admin = %caller.email,
+        user = %target.email,
+        expires_in = ttl,
+        "impersonation token issued"
+    );
+
+    Json(serde_json::json!({
+        "token": token,
+        "expires_in": ttl,
+        "impersonated_by": caller.email,
+    }))
+    .into_response()
+}
+
+/// List the tickets a user updated most recently (admin only).
+///
+/// For audits: "what has this user been touching?". A ticket counts when
+/// the user made its latest update, as recorded in `updated_by`; tickets
+/// they updated before someone else did are not listed. Newest first.
+///
+/// # Path Parameters
+/// - `id`: ID of the user
+///
+/// # Query Parameters
+/// - `page`, `per_page`: See [`Pagination`]
+///
+/// # Returns
+/// - `200 OK` with a JSON array of tickets, plus `X-Page` / `X-Per-Page` headers
+/// - `401 UNAUTHORIZED` if token is missing/invalid
+/// - `403 FORBIDDEN` if the caller is not an admin
+/// - `404 NOT_FOUND` if the user doesn't exist
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn get_user_ticket_activity(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    AuthUser(caller): AuthUser,
+    pagination: Pagination,
+) -> impl IntoResponse {
+    if caller.role != Role::Admin.as_str() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    let db = &state.db;
+    match user::Entity::find_by_id(id).one(db).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return DbError(e).into_response(),
+    }
 
-    // 📥 Insert users concurrently
-    let inserted = join_all(users.into_iter().map(|u| u.insert(db)))
+    match ticket::Entity::find()
+        .filter(ticket::Column::UpdatedBy.eq(id))
+        .order_by_desc(ticket::Column::UpdatedAt)
+        .order_by_desc(ticket::Column::Id)
+        .offset(pagination.offset())
+        .limit(pagination.per_page)
+        .all(db)
         .await
-        .into_iter()
-        .collect::<Result<Vec<_>, _>>()?;
+    {
+        Ok(tickets) => (pagination.headers(), Json(tickets)).into_response(),
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// Move every ticket assigned to one user onto another (admin only).
+///
+/// Meant for handing over a departing agent's work before the account is
+/// removed. All tickets move in one transaction and get a fresh
+/// `updated_at`, with the admin as `updated_by`. Reassigning a user to
+/// themselves moves nothing.
+///
+/// # Path Parameters
+/// - `from`: ID of the user whose tickets move
+/// - `into`: ID of the user who receives them
+///
+/// # Returns
+/// - `200 OK` with `{ "moved": count }`
+/// - `401 UNAUTHORIZED` if token is missing/invalid
+/// - `403 FORBIDDEN` if the caller is not an admin
+/// - `404 NOT_FOUND` if either user doesn't exist
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn reassign_tickets(
+    State(state): State<AppState>,
+    Path((from, into)): Path<(i32, i32)>,
+    AuthUser(caller): AuthUser,
+) -> impl IntoResponse {
+    if caller.role != Role::Admin.as_str() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    let txn = match state.db.begin().await {
+        Ok(txn) => txn,
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    // 🔍 Both ends must exist
+    let found = match user::Entity::find()
+        .filter(user::Column::Id.is_in([from, into]))
+        .count(&txn)
+        .await
+    {
+        Ok(found) => found,
+        Err(e) => return DbError(e).into_response(),
+    };
+    let expected = if from == into { 1 } else { 2 };
+    if found < expected {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    if from == into {
+        return Json(serde_json::json!({ "moved": 0 })).into_response();
+    }
+
+    // 📦 Move them all at once
+    let moved = match ticket::Entity::update_many()
+        .col_expr(ticket::Column::UserId, Expr::value(into))
+        .col_expr(
+            ticket::Column::UpdatedAt,
+            Expr::value(Utc::now().naive_utc()),
+        )
+        .col_expr(ticket::Column::UpdatedBy, Expr::value(caller.id))
+        .filter(ticket::Column::UserId.eq(from))
+        .exec(&txn)
+        .await
+    {
+        Ok(res) => res.rows_affected,
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    match txn.commit().await {
+        Ok(()) => Json(serde_json::json!({ "moved": moved })).into_response(),
+        Err(e) => DbError(e).into_response(),
+    }
+}
 
-    Ok(inserted)
+/// Payload for deleting every ticket that matches a filter.
+///
+/// Filters combine with AND; at least one is required.
+#[derive(Debug, Deserialize)]
+pub struct BulkDeleteTickets {
+    pub status: Option<String>,
+    /// RFC 3339; only tickets created strictly before this moment.
+    pub created_before: Option<String>,
+    pub user_id: Option<i32>,
+    /// Must be `true`, so a stray request can't wipe tickets.
+    #[serde(default)]
+    pub confirm: bool,
 }
 
-/// Seed default tags, tickets, and their relations to users and each other.
+/// Delete every ticket matching a filter, with its tag links (admin only).
 ///
-/// - Inserts a list of tags
-/// - Inserts example tickets assigned to users
-/// - Creates ticket-tag associations (many-to-many)
+/// Meant for cleaning up spam or a test batch. Matching tickets and their
+/// `ticket_tag` rows go in one transaction.
 ///
-/// Returns a tuple with counts: (tags, tickets, ticket_tag relations)
-async fn seed_tags_and_tickets(
-    db: &DatabaseConnection,
-    users: &Vec<user::Model>,
-) -> Result<(usize, usize, usize), DbErr> {
-    let now = Local::now().naive_local();
+/// # JSON Payload
+/// - `status`, `created_before`, `user_id`: Filters, see [`BulkDeleteTickets`]
+/// - `confirm`: Must be `true`
+///
+/// # Returns
+/// - `200 OK` with `{ "deleted": count }`
+/// - `400 BAD_REQUEST` with `{ "error": "confirmation_required" }` without `confirm: true`
+/// - `400 BAD_REQUEST` with `{ "error": "empty_filter" }` if no filter is given
+/// - `400 BAD_REQUEST` with `{ "error": "invalid_created_before" }` for a bad timestamp
+/// - `401 UNAUTHORIZED` if token is missing/invalid
+/// - `403 FORBIDDEN` if the caller is not an admin
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn bulk_delete_tickets(
+    State(state): State<AppState>,
+    AuthUser(caller): AuthUser,
+    Json(payload): Json<BulkDeleteTickets>,
+) -> impl IntoResponse {
+    if caller.role != Role::Admin.as_str() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
 
-    // 🏷️ Tags
-    let tags = vec![
-        tag::ActiveModel {
-            name: Set("Bug".into()),
-            created_at: Set(Some(now)),
-            updated_at: Set(Some(now)),
-            ..Default::default()
-        },
-        tag::ActiveModel {
-            name: Set("Feature".into()),
-            created_at: Set(Some(now)),
-            updated_at: Set(Some(now)),
-            ..Default::default()
-        },
-        tag::ActiveModel {
-            name: Set("Urgent".into()),
-            created_at: Set(Some(now)),
-            updated_at: Set(Some(now)),
-            ..Default::default()
-        },
-    ];
+    // 🧯 Refuse anything that isn't clearly deliberate
+    let bad_request = |error: &str| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": error })),
+        )
+            .into_response()
+    };
+    if !payload.confirm {
+        return bad_request("confirmation_required");
+    }
+    if payload.status.is_none() && payload.created_before.is_none() && payload.user_id.is_none() {
+        return bad_request("empty_filter");
+    }
+
+    // 🔎 Build the filter
+    let mut filter = Condition::all();
+    if let Some(status) = payload.status.as_deref() {
+        filter = filter.add(ticket::Column::Status.eq(status.trim()));
+    }
+    if let Some(raw) = payload.created_before.as_deref() {
+        let Some(before) = crate::utils::time::parse(raw) else {
+            return bad_request("invalid_created_before");
+        };
+        filter = filter.add(ticket::Column::CreatedAt.lt(before));
+    }
+    if let Some(user_id) = payload.user_id {
+        filter = filter.add(ticket::Column::UserId.eq(user_id));
+    }
+
+    let txn = match state.db.begin().await {
+        Ok(txn) => txn,
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    let doomed = match ticket::Entity::find().filter(filter).all(&txn).await {
+        Ok(doomed) => doomed,
+        Err(e) => return DbError(e).into_response(),
+    };
+    let ids: Vec<i32> = doomed.iter().map(|t| t.id).collect();
 
-    let saved_tags = join_all(tags.into_iter().map(|t| t.insert(db)))
+    // 🗑️ Tag links first, then the tickets themselves
+    if let Err(e) = ticket_tag::Entity::delete_many()
+        .filter(ticket_tag::Column::TicketId.is_in(ids.clone()))
+        .exec(&txn)
         .await
-        .into_iter()
-        .collect::<Result<Vec<_>, _>>()?;
-
-    // 🎫 Tickets
-    let tickets = vec![
-        ticket::ActiveModel {
-            title: Set("Fix navbar overflow bug".into()),
-            description: Set(Some("Navbar overlaps on mobile screens".into())),
-            user_id: Set(Some(users[0].id)),
-            created_at: Set(Some(now)),
-            updated_at: Set(Some(now)),
-            ..Default::default()
-        },
-        ticket::ActiveModel {
-            title: Set("Add dark mode toggle".into()),
-            description: Set(Some("Users should be able to switch themes".into())),
-            user_id: Set(Some(users[1].id)),
-            created_at: Set(Some(now)),
-            updated_at: Set(Some(now)),
-            ..Default::default()
-        },
-    ];
+    {
+        return DbError(e).into_response();
+    }
+    let deleted = match ticket::Entity::delete_many()
+        .filter(ticket::Column::Id.is_in(ids))
+        .exec(&txn)
+        .await
+    {
+        Ok(res) => res.rows_affected,
+        Err(e) => return DbError(e).into_response(),
+    };
 
-    let saved_tickets = join_all(tickets.into_iter().map(|t| t.insert(db)))
+    if let Err(e) = txn.commit().await {
+        return DbError(e).into_response();
+    }
+    for ticket in doomed {
+        events::publish(&state.events, TicketEventKind::Deleted, ticket);
+    }
+    Json(serde_json::json!({ "deleted": deleted })).into_response()
+}
+
+/// One tag in a taxonomy backup.
+///
+/// Tag IDs are left out on purpose: tags are matched by `owner_id` and
+/// name (ignoring case) on import, so a backup can be loaded into a
+/// database whose tag IDs differ. `owner_id` is `None` for global tags.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagExport {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub owner_id: Option<i32>,
+    #[serde(default, with = "crate::utils::time::rfc3339")]
+    pub created_at: Option<NaiveDateTime>,
+    #[serde(default, with = "crate::utils::time::rfc3339")]
+    pub updated_at: Option<NaiveDateTime>,
+    #[serde(default, with = "crate::utils::time::rfc3339")]
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+impl From<tag::Model> for TagExport {
+    fn from(tag: tag::Model) -> Self {
+        Self {
+            name: tag.name,
+            description: tag.description,
+            owner_id: tag.owner_id,
+            created_at: tag.created_at,
+            updated_at: tag.updated_at,
+            deleted_at: tag.deleted_at,
+        }
+    }
+}
+
+/// Export every tag, soft-deleted ones included, for backup (admin only).
+///
+/// The response body can be sent as-is to [`import_tags`].
+///
+/// # Returns
+/// - `200 OK` with `[{ "name", "description", "owner_id", "created_at", "updated_at", "deleted_at" }]`, ordered by ID
+/// - `401 UNAUTHORIZED` if token is missing/invalid
+/// - `403 FORBIDDEN` if the caller is not an admin
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn export_tags(
+    State(state): State<AppState>,
+    AuthUser(caller): AuthUser,
+) -> impl IntoResponse {
+    if caller.role != Role::Admin.as_str() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    match tag::Entity::find()
+        .order_by_asc(tag::Column::Id)
+        .all(&state.db)
         .await
-        .into_iter()
-        .collect::<Result<Vec<_>, _>>()?;
-
-    // 🔗 Ticket-Tag Relations
-    let relations = vec![
-        ticket_tag::ActiveModel {
-            ticket_id: Set(saved_tickets[0].id),
-            tag_id: Set(saved_tags[0].id),
-            ..Default::default()
-        },
-        ticket_tag::ActiveModel {
-            ticket_id: Set(saved_tickets[0].id),
-            tag_id: Set(saved_tags[2].id),
-            ..Default::default()
-        },
-        ticket_tag::ActiveModel {
-            ticket_id: Set(saved_tickets[1].id),
-            tag_id: Set(saved_tags[1].id),
-            ..Default::default()
-        },
-    ];
+    {
+        Ok(tags) => {
+            let export: Vec<TagExport> = tags.into_iter().map(TagExport::from).collect();
+            Json(export).into_response()
+        }
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// Load a tag backup produced by [`export_tags`] (admin only).
+///
+/// Tags are upserted in one transaction, matched by name ignoring case in
+/// the namespace that keeps names unique (per `owner_id` with
+/// `tag_ownership` on, across all tags otherwise): existing tags
+/// take the backup's `name`, `description` and `deleted_at`, missing ones
+/// are created with the backup's timestamps. Tags not in the backup are
+/// left alone.
+///
+/// # Returns
+/// - `200 OK` with `{ "created", "updated" }`
+/// - `400 BAD_REQUEST` with `{ "error": "invalid_tag_name", "name" }` if any name is invalid
+/// - `400 BAD_REQUEST` with `{ "error": "unknown_owner", "owner_id" }` if an owner doesn't exist here
+/// - `401 UNAUTHORIZED` if token is missing/invalid
+/// - `403 FORBIDDEN` if the caller is not an admin
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn import_tags(
+    State(state): State<AppState>,
+    AuthUser(caller): AuthUser,
+    Json(payload): Json<Vec<TagExport>>,
+) -> impl IntoResponse {
+    if caller.role != Role::Admin.as_str() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    // 🧪 Same name rules as the batch endpoint; nothing is written on failure
+    if let Some(bad) = payload
+        .iter()
+        .find(|t| t.name.trim().is_empty() || t.name.chars().count() > MAX_TAG_NAME_LEN)
+    {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": "invalid_tag_name", "name": bad.name })),
+        )
+            .into_response();
+    }
 
-    let saved_relations = join_all(relations.into_iter().map(|r| r.insert(db)))
+    // 👤 Owners must exist here; user IDs can differ between databases
+    let owners: BTreeSet<i32> = payload.iter().filter_map(|t| t.owner_id).collect();
+    let known: BTreeSet<i32> = match user::Entity::find()
+        .select_only()
+        .column(user::Column::Id)
+        .filter(user::Column::Id.is_in(owners.iter().copied()))
+        .into_tuple()
+        .all(&state.db)
         .await
+    {
+        Ok(ids) => ids.into_iter().collect(),
+        Err(e) => return DbError(e).into_response(),
+    };
+    if let Some(missing) = owners.difference(&known).next() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": "unknown_owner", "owner_id": missing })),
+        )
+            .into_response();
+    }
+
+    let txn = match state.db.begin().await {
+        Ok(txn) => txn,
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    let counts = match upsert_tags(&txn, payload, state.config.tag_ownership).await {
+        Ok(counts) => counts,
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    if let Err(e) = txn.commit().await {
+        return DbError(e).into_response();
+    }
+    state.tag_cache.invalidate().await;
+
+    let (created, updated) = counts;
+    Json(serde_json::json!({ "created": created, "updated": updated })).into_response()
+}
+
+/// Insert or update each tag by name, within its owner's namespace when
+/// `per_owner`; returns `(created, updated)`.
+async fn upsert_tags(
+    txn: &DatabaseTransaction,
+    tags: Vec<TagExport>,
+    per_owner: bool,
+) -> Result<(usize, usize), DbErr> {
+    let now = Utc::now().naive_utc();
+    let (mut created, mut updated) = (0, 0);
+
+    for incoming in tags {
+        let existing = find_named(&incoming.name, incoming.owner_id, per_owner)
+            .one(txn)
+            .await?;
+
+        match existing {
+            Some(tag) => {
+                let mut active = tag.into_active_model();
+                active.name = Set(incoming.name);
+                active.description = Set(incoming.description);
+                active.deleted_at = Set(incoming.deleted_at);
+                active.updated_at = Set(Some(now));
+                active.update(txn).await?;
+                updated += 1;
+            }
+            None => {
+                tag::ActiveModel {
+                    name: Set(incoming.name),
+                    description: Set(incoming.description),
+                    owner_id: Set(incoming.owner_id),
+                    created_at: Set(incoming.created_at.or(Some(now))),
+                    updated_at: Set(incoming.updated_at.or(Some(now))),
+                    deleted_at: Set(incoming.deleted_at),
+                    ..Default::default()
+                }
+                .insert(txn)
+                .await?;
+                created += 1;
+            }
+        }
+    }
+
+    Ok((created, updated))
+}
+
+/// Tag names shared by more than one tag of one owner, compared
+/// case-insensitively. `owner_id` is `None` for global tags.
+#[derive(Debug, Serialize)]
+pub struct DuplicateTagName {
+    pub owner_id: Option<i32>,
+    pub name: String,
+    pub ids: Vec<i32>,
+}
+
+/// Read-only data integrity summary returned by `GET /admin/integrity`.
+///
+/// # Fields
+/// - `ok`: `true` when every list below is empty.
+/// - `orphaned_ticket_tags`: `ticket_tag` IDs whose ticket or tag no longer exists.
+/// - `tickets_without_user`: Ticket IDs whose `user_id` is null or points at no user.
+/// - `duplicate_tag_names`: Names (lowercased) used by several tags of one owner, with their IDs.
+///   The tag name indexes rule these out, so any found predate them.
+#[derive(Debug, Serialize)]
+pub struct IntegrityReport {
+    pub ok: bool,
+    pub orphaned_ticket_tags: Vec<i32>,
+    pub tickets_without_user: Vec<i32>,
+    pub duplicate_tag_names: Vec<DuplicateTagName>,
+}
+
+/// Report data integrity problems without fixing them (admin only).
+///
+/// # Returns
+/// - `200 OK` with an [`IntegrityReport`]
+/// - `401 UNAUTHORIZED` if token is missing/invalid
+/// - `403 FORBIDDEN` if the caller is not an admin
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+#[tracing::instrument(skip_all)]
+pub async fn check_integrity(
+    State(state): State<AppState>,
+    AuthUser(caller): AuthUser,
+) -> impl IntoResponse {
+    if caller.role != Role::Admin.as_str() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    match timed("admin.integrity", integrity_report(&state.db)).await {
+        Ok(report) => Json(report).into_response(),
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// IDs of `ticket_tag` rows whose ticket or tag no longer exists.
+async fn orphaned_ticket_tag_ids<C: ConnectionTrait>(db: &C) -> Result<Vec<i32>, DbErr> {
+    ticket_tag::Entity::find()
+        .select_only()
+        .column(ticket_tag::Column::Id)
+        .join(JoinType::LeftJoin, ticket_tag::Relation::Ticket.def())
+        .join(JoinType::LeftJoin, ticket_tag::Relation::Tag.def())
+        .filter(
+            Condition::any()
+                .add(ticket::Column::Id.is_null())
+                .add(tag::Column::Id.is_null()),
+        )
+        .order_by_asc(ticket_tag::Column::Id)
+        .into_tuple()
+        .all(db)
+        .await
+}
+
+async fn integrity_report(db: &DatabaseConnection) -> Result<IntegrityReport, DbErr> {
+    // 🔗 Relations whose ticket or tag is gone
+    let orphaned_ticket_tags = orphaned_ticket_tag_ids(db).await?;
+
+    // 👤 Tickets nobody owns
+    let tickets_without_user: Vec<i32> = ticket::Entity::find()
+        .select_only()
+        .column(ticket::Column::Id)
+        .join(JoinType::LeftJoin, ticket::Relation::User.def())
+        .filter(user::Column::Id.is_null())
+        .order_by_asc(ticket::Column::Id)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    // 🏷️ Names one owner uses more than once, ignoring case
+    let tags: Vec<(i32, String, Option<i32>)> = tag::Entity::find()
+        .select_only()
+        .columns([tag::Column::Id, tag::Column::Name, tag::Column::OwnerId])
+        .order_by_asc(tag::Column::Id)
+        .into_tuple()
+        .all(db)
+        .await?;
+    let mut by_name: BTreeMap<(Option<i32>, String), Vec<i32>> = BTreeMap::new();
+    for (id, name, owner_id) in tags {
+        by_name
+            .entry((owner_id, name.to_lowercase()))
+            .or_default()
+            .push(id);
+    }
+    let duplicate_tag_names: Vec<DuplicateTagName> = by_name
+        .into_iter()
+        .filter(|(_, ids)| ids.len() > 1)
+        .map(|((owner_id, name), ids)| DuplicateTagName {
+            owner_id,
+            name,
+            ids,
+        })
+        .collect();
+
+    Ok(IntegrityReport {
+        ok: orphaned_ticket_tags.is_empty()
+            && tickets_without_user.is_empty()
+            && duplicate_tag_names.is_empty(),
+        orphaned_ticket_tags,
+        tickets_without_user,
+        duplicate_tag_names,
+    })
+}
+
+/// Delete `ticket_tag` rows whose ticket or tag no longer exists (admin only).
+///
+/// The only fix applied for problems reported by [`check_integrity`]. Runs in
+/// one transaction and is safe to repeat; a clean database removes nothing.
+///
+/// # Returns
+/// - `200 OK` with `{ "removed": n }`
+/// - `401 UNAUTHORIZED` if token is missing/invalid
+/// - `403 FORBIDDEN` if the caller is not an admin
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+#[tracing::instrument(skip_all)]
+pub async fn cleanup_orphans(
+    State(state): State<AppState>,
+    AuthUser(caller): AuthUser,
+) -> impl IntoResponse {
+    if caller.role != Role::Admin.as_str() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    let txn = match state.db.begin().await {
+        Ok(txn) => txn,
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    let removed = match timed("admin.cleanup_orphans", delete_orphans(&txn)).await {
+        Ok(removed) => removed,
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    if let Err(e) = txn.commit().await {
+        return DbError(e).into_response();
+    }
+
+    Json(serde_json::json!({ "removed": removed })).into_response()
+}
+
+async fn delete_orphans(txn: &DatabaseTransaction) -> Result<u64, DbErr> {
+    let ids = orphaned_ticket_tag_ids(txn).await?;
+    if ids.is_empty() {
+        return Ok(0);
+    }
+    let result = ticket_tag::Entity::delete_many()
+        .filter(ticket_tag::Column::Id.is_in(ids))
+        .exec(txn)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+/// Payload for switching maintenance mode.
+#[derive(Debug, Deserialize)]
+pub struct UpdateMaintenance {
+    pub enabled: bool,
+}
+
+/// Report whether maintenance mode is on (admin only).
+///
+/// # Returns
+/// - `200 OK` with `{ "enabled": bool }`
+/// - `401 UNAUTHORIZED` if token is missing/invalid
+/// - `403 FORBIDDEN` if the caller is not an admin
+pub async fn get_maintenance(
+    State(state): State<AppState>,
+    AuthUser(caller): AuthUser,
+) -> impl IntoResponse {
+    if caller.role != Role::Admin.as_str() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    Json(serde_json::json!({ "enabled": state.maintenance.is_enabled() })).into_response()
+}
+
+/// Switch maintenance mode on or off (admin only).
+///
+/// While on, everyone but admins gets `503` with `Retry-After`, except on
+/// health checks, admin routes, and login. The switch isn't persisted; a
+/// restart goes back to `MAINTENANCE_MODE`.
+///
+/// # JSON Payload
+/// - `enabled`: `true` to start maintenance, `false` to end it
+///
+/// # Returns
+/// - `200 OK` with `{ "enabled": bool }`
+/// - `401 UNAUTHORIZED` if token is missing/invalid
+/// - `403 FORBIDDEN` if the caller is not an admin
+pub async fn set_maintenance(
+    State(state): State<AppState>,
+    AuthUser(caller): AuthUser,
+    Json(payload): Json<UpdateMaintenance>,
+) -> impl IntoResponse {
+    if caller.role != Role::Admin.as_str() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    state.maintenance.set(payload.enabled);
+    tracing::info!(
+        admin = caller.id,
+        enabled = payload.enabled,
+        "🚧 Maintenance mode switched"
+    );
+
+    Json(serde_json::json!({ "enabled": payload.enabled })).into_response()
+}
+
+/// How many days `get_dashboard` charts ticket creation over, today included.
+pub const DASHBOARD_DAYS: i64 = 7;
+
+/// System-wide counts returned by `GET /admin/dashboard`.
+#[derive(Debug, Serialize)]
+pub struct Dashboard {
+    pub users: u64,
+    pub tickets: Vec<StatusCount>,
+    pub tags: u64,
+    pub tickets_per_day_last_7: Vec<DayCount>,
+}
+
+/// Tickets created on one day (UTC).
+#[derive(Debug, Serialize)]
+pub struct DayCount {
+    pub date: NaiveDate,
+    pub count: i64,
+}
+
+/// Aggregate counts for the admin dashboard (admin only).
+///
+/// Everything is counted in the database: users, tickets per status, live
+/// (not soft-deleted) tags, and tickets created on each of the last seven
+/// days, oldest first with `0` for quiet days.
+///
+/// # Returns
+/// - `200 OK` with `{ "users", "tickets": [{ "status", "count" }], "tags", "tickets_per_day_last_7": [{ "date", "count" }] }`
+/// - `401 UNAUTHORIZED` if token is missing/invalid
+/// - `403 FORBIDDEN` if the caller is not an admin
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+#[tracing::instrument(skip_all)]
+pub async fn get_dashboard(
+    State(state): State<AppState>,
+    AuthUser(caller): AuthUser,
+) -> impl IntoResponse {
+    if caller.role != Role::Admin.as_str() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    match timed("admin.dashboard", dashboard(&state.read_db)).await {
+        Ok(dashboard) => Json(dashboard).into_response(),
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+async fn dashboard(db: &DatabaseConnection) -> Result<Dashboard, DbErr> {
+    let users = user::Entity::find().count(db).await?;
+    let tickets = count_by_status(ticket::Entity::find(), db).await?;
+    let tags = tag::Entity::find()
+        .filter(tag::Column::DeletedAt.is_null())
+        .count(db)
+        .await?;
+
+    // 📊 Tickets created per day, truncated in the database
+    let first = Utc::now().date_naive() - Duration::days(DASHBOARD_DAYS - 1);
+    let per_day: Vec<(NaiveDateTime, i64)> = ticket::Entity::find()
+        .select_only()
+        .column_as(Expr::cust("date_trunc('day', \"created_at\")"), "day")
+        .column_as(ticket::Column::Id.count(), "count")
+        .filter(ticket::Column::CreatedAt.gte(first.and_time(NaiveTime::MIN)))
+        .group_by(Expr::cust("day"))
+        .into_tuple()
+        .all(db)
+        .await?;
+    let per_day: HashMap<NaiveDate, i64> = per_day
         .into_iter()
-        .collect::<Result<Vec<_>, _>>()?;
+        .map(|(day, count)| (day.date(), count))
+        .collect();
+
+    // 🕳️ Fill the gaps so every day shows up
+    let tickets_per_day_last_7 = (0..DASHBOARD_DAYS)
+        .map(|i| {
+            let date = first + Duration::days(i);
+            DayCount {
+                date,
+                count: per_day.get(&date).copied().unwrap_or(0),
+            }
+        })
+        .collect();
+
+    Ok(Dashboard {
+        users,
+        tickets,
+        tags,
+        tickets_per_day_last_7,
+    })
+}
+
+/// A user and how many tickets they created, returned by `GET /admin/stats/by-user`.
+#[derive(Debug, Serialize)]
+pub struct UserTicketCount {
+    pub user: UserPublic,
+    pub ticket_count: i64,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct UserTicketRow {
+    #[sea_orm(nested)]
+    user: user::Model,
+    ticket_count: i64,
+}
+
+/// Count the tickets each user created, busiest first (admin only).
+///
+/// One query left-joins `user` to `ticket` on `created_by` and groups by
+/// user, so users who created nothing come back with `0`. Ties are broken
+/// by user ID.
+///
+/// # Returns
+/// - `200 OK` with `[{ "user", "ticket_count" }]`
+/// - `401 UNAUTHORIZED` if token is missing/invalid
+/// - `403 FORBIDDEN` if the caller is not an admin
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+#[tracing::instrument(skip_all)]
+pub async fn get_ticket_counts_by_user(
+    State(state): State<AppState>,
+    AuthUser(caller): AuthUser,
+) -> impl IntoResponse {
+    if caller.role != Role::Admin.as_str() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
 
-    Ok((saved_tags.len(), saved_tickets.len(), saved_relations.len()))
-}
\ No newline at end of file
+    let created_by = ticket::Entity::belongs_to(user::Entity)
+        .from(ticket::Column::CreatedBy)
+        .to(user::Column::Id)
+        .into();
+    let rows = timed(
+        "admin.stats_by_user",
+        user::Entity::find()
+            .column_as(ticket::Column::Id.count(), "ticket_count")
+            .join_rev(JoinType::LeftJoin, created_by)
+            .group_by(user::Column::Id)
+            .order_by_desc(Expr::col("ticket_count"))
+            .order_by_asc(user::Column::Id)
+            .into_model::<UserTicketRow>()
+            .all(&state.read_db),
+    )
+    .await;
+
+    match rows {
+        Ok(rows) => Json(
+            rows.into_iter()
+                .map(|row| UserTicketCount {
+                    user: row.user.into(),
+                    ticket_count: row.ticket_count,
+                })
+                .collect::<Vec<_>>(),
+        )
+        .into_response(),
+        Err(e) => DbError(e).into_response(),
+    }
+}
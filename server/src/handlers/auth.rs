@@ -1,11 +1,37 @@
-use crate::models::user::{ActiveModel, Entity as User};
-use crate::routes::auth::{RegisterRequest, LoginRequest, LoginResponse};
-use crate::utils::auth::extract_claims;
-use crate::utils::jwt::create_jwt;
-use axum::{extract::Request, http::StatusCode, Json, response::IntoResponse};
-use bcrypt::{DEFAULT_COST, hash, verify};
-use chrono::Local;
-use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
+use crate::middleware::auth::{AUTH_COOKIE, AuthUser};
+use crate::middleware::error::DbError;
+use crate::models::user::{self, ActiveModel, Entity as User, Role, UserPublic};
+use crate::models::{login_event, password_reset};
+use crate::routes::auth::{
+    ForgotPasswordRequest, LoginMode, LoginQuery, LoginRequest, LoginResponse, RegisterQuery,
+    RegisterRequest, ResetPasswordRequest, VerifyEmailQuery,
+};
+use crate::state::AppState;
+use crate::utils::{
+    client_ip::ClientIp,
+    jwt::{TOKEN_TTL, create_jwt},
+    normalize,
+    pagination::Pagination,
+    time::to_rfc3339,
+    token,
+    validation::ValidationErrors,
+};
+use axum::{
+    Json,
+    extract::{OriginalUri, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use axum_extra::extract::cookie::{Cookie, CookieJar, SameSite};
+use bcrypt::{HashParts, hash, verify};
+use chrono::{Duration, Timelike, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, IntoActiveModel,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, SqlErr, TransactionTrait,
+    sea_query::Expr,
+};
+use serde::Serialize;
+use serde_json::json;
 
 /// Register a new user in the system.
 ///
@@ -15,58 +41,176 @@ use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
 /// - password
 /// - role
 ///
-/// Hashes the password using bcrypt, inserts the user into the database,
-/// and returns a success message or an internal server error.
+/// The email is trimmed and the name trimmed with inner whitespace
+/// collapsed. Every field is checked before anything is rejected, so all
+/// problems come back together. Hashes the password using
+/// bcrypt, inserts the user into the database, and returns a success
+/// message or an internal server error.
+///
+/// Without a `role`, the user gets `default_role` (normally `agent`). Unless
+/// `allow_self_admin_registration` is set, only a request carrying an
+/// admin's token may create an admin.
+///
+/// An email that is already registered is a conflict. With `?upsert=true`
+/// the existing user is returned instead, so provisioning scripts can run
+/// "create if absent" repeatedly; nothing about that user is changed.
+///
+/// The user starts with `email_verified = false` and a one-time verification
+/// token, redeemed at `GET /auth/verify-email`. Only the token's hash is
+/// stored; the link is mailed through `AppState::email`, and a failed send
+/// is logged without failing the registration.
 ///
 /// # Returns
 /// - `201 CREATED` on success
+/// - `200 OK` with the existing `UserPublic` when the email is taken and `upsert` is set
+/// - `403 FORBIDDEN` with `{ "error": "admin_registration_forbidden" }` for an
+///   admin registration without an admin's token
+/// - `409 CONFLICT` with `{ "error": "email_taken" }` when the email is taken
+/// - `422 UNPROCESSABLE_ENTITY` with `{ "errors": { field: message } }` for a blank or
+///   malformed email, a blank name or password, or a role other than `agent`/`admin`
 /// - `500 INTERNAL_SERVER_ERROR` on hashing or DB insert failure
-pub async fn register_user(Json(payload): Json<RegisterRequest>) -> impl IntoResponse {
+pub async fn register_user(
+    State(state): State<AppState>,
+    auth: Option<AuthUser>,
+    Query(query): Query<RegisterQuery>,
+    Json(payload): Json<RegisterRequest>,
+) -> impl IntoResponse {
+    // 🧹 Normalize, then check every field before rejecting
+    let mut errors = ValidationErrors::default();
+    let email = match normalize::email(&payload.email) {
+        Some(email) if !email.contains('@') => {
+            errors.add("email", "must be an email address");
+            None
+        }
+        email => errors.require("email", email, "must not be blank"),
+    };
+    let name = errors.require("name", normalize::name(&payload.name), "must not be blank");
+    if payload.password.is_empty() {
+        errors.add("password", "must not be empty");
+    }
+    let role = match payload.role.as_deref() {
+        Some(raw) => errors.require("role", Role::parse(raw), "must be \"agent\" or \"admin\""),
+        None => Some(state.config.default_role),
+    };
+    let (Some(email), Some(name), Some(role), true) = (email, name, role, errors.is_empty()) else {
+        return errors.into_response();
+    };
+
+    // 👑 Admin accounts come from admins, unless open admin signup is allowed
+    let caller_is_admin = auth.is_some_and(|AuthUser(caller)| caller.role == Role::Admin.as_str());
+    if role == Role::Admin && !state.config.allow_self_admin_registration && !caller_is_admin {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(json!({ "error": "admin_registration_forbidden" })),
+        )
+            .into_response();
+    }
+
+    // 👥 An existing account is either a conflict or, when upserting, the answer
+    let db = &state.db;
+    match existing_user(db, &email, query.upsert).await {
+        Ok(Some(response)) => return response,
+        Ok(None) => {}
+        Err(e) => return DbError(e).into_response(),
+    }
+
     // 🔐 Hash the user's password securely
-    let password_hash = match hash(&payload.password, DEFAULT_COST) {
+    let password_hash = match hash(&payload.password, state.config.bcrypt_cost) {
         Ok(h) => h,
         Err(e) => {
             eprintln!("❌ Password hashing failed: {:?}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json("❌ Failed to hash password."),
-            );
+            )
+                .into_response();
         }
     };
 
     // 🕰️ Current timestamp for created_at
-    let now = Local::now().naive_local();
+    let now = Utc::now().naive_utc();
+
+    // ✉️ One-time token for the verification link
+    let (verification_token, verification_hash) = token::generate();
 
     // 🧱 Build a new ActiveModel for the user
     let new_user = ActiveModel {
-        email: Set(payload.email),
-        name: Set(payload.name),
+        email: Set(email.clone()),
+        name: Set(name),
         password: Set(password_hash),
-        role: Set(payload.role),
+        role: Set(role.as_str().to_string()),
         created_at: Set(Some(now)),
+        email_verified: Set(false),
+        email_verification_token: Set(Some(verification_hash)),
         ..Default::default()
     };
 
-    // 🌐 Connect to the database and attempt insert
-    let db = crate::db::db::connect().await;
-    let res = new_user.insert(&db).await;
+    // 🌐 Attempt insert using the shared connection pool
+    let res = new_user.insert(db).await;
 
     // 📦 Handle success/failure
     match res {
-        Ok(_) => (
-            StatusCode::CREATED,
-            Json("🎉 User registered successfully!"),
-        ),
+        Ok(user) => {
+            let body = format!(
+                "Welcome to TagBlaze! Confirm your email at /auth/verify-email?token={verification_token}"
+            );
+            if let Err(e) = state
+                .email
+                .send(&user.email, "Verify your TagBlaze email", &body)
+                .await
+            {
+                tracing::warn!(email = %user.email, error = %e, "verification email failed");
+            }
+            (
+                StatusCode::CREATED,
+                Json("🎉 User registered successfully!"),
+            )
+                .into_response()
+        }
+        // 🏁 Lost a race with a concurrent registration of the same email
+        Err(e) if matches!(e.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) => {
+            match existing_user(db, &email, query.upsert).await {
+                Ok(Some(response)) => response,
+                Ok(None) | Err(_) => DbError(e).into_response(),
+            }
+        }
         Err(e) => {
             eprintln!("❌ Error inserting user: {:?}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json("❌ Could not register user."),
             )
+                .into_response()
         }
     }
 }
 
+/// The response for registering an email that already has an account, or
+/// `None` if it doesn't: the existing user when `upsert` is set, `409` otherwise.
+async fn existing_user(
+    db: &DatabaseConnection,
+    email: &str,
+    upsert: bool,
+) -> Result<Option<Response>, DbErr> {
+    let Some(existing) = User::find()
+        .filter(user::Column::Email.eq(email))
+        .one(db)
+        .await?
+    else {
+        return Ok(None);
+    };
+    if upsert {
+        return Ok(Some(Json(UserPublic::from(existing)).into_response()));
+    }
+    Ok(Some(
+        (
+            StatusCode::CONFLICT,
+            Json(json!({ "error": "email_taken" })),
+        )
+            .into_response(),
+    ))
+}
+
 /// Authenticate a user and issue a JWT token upon successful login.
 ///
 /// Accepts a `LoginRequest` with:
@@ -76,48 +220,466 @@ pub async fn register_user(Json(payload): Json<RegisterRequest>) -> impl IntoRes
 /// Validates credentials against the database using bcrypt hashing,
 /// and generates a JWT token if the credentials are correct.
 ///
+/// Every attempt is counted in `AppState::metrics`, and failures are logged
+/// as a structured `warn` event with the attempted email and client IP.
+/// Attempts on an existing account are also stored as a `login_event`,
+/// which its owner can list through [`get_my_logins`].
+///
+/// Browsers can ask for the token as an `HttpOnly; Secure; SameSite=Strict`
+/// cookie instead, with `?mode=cookie` or an `Accept` header that prefers
+/// `text/html`; `AuthUser` reads it back when no `Authorization` header is sent.
+/// `?mode=body` forces the JSON response.
+///
+/// A successful login whose stored hash was made at a different bcrypt
+/// cost than `bcrypt_cost` re-hashes the password at that cost, so
+/// changing the setting migrates accounts as their owners log in.
+///
+/// With `require_email_verification` set, users who haven't verified their
+/// email are turned away even with the right password. Users an admin has
+/// disabled are always turned away.
+///
 /// # Returns
 /// - `200 OK` with JWT token in a `LoginResponse` on success
+/// - `204 NO_CONTENT` with `Set-Cookie` on success in cookie mode
 /// - `401 UNAUTHORIZED` on failure
+/// - `403 FORBIDDEN` with `{ "error": "account_disabled" }` for disabled users
+/// - `403 FORBIDDEN` with `{ "error": "email_not_verified" }` for unverified users when verification is required
 pub async fn login_user(
+    State(state): State<AppState>,
+    client_ip: ClientIp,
+    Query(query): Query<LoginQuery>,
+    headers: HeaderMap,
     Json(payload): Json<LoginRequest>,
-) -> Result<Json<LoginResponse>, StatusCode> {
-    let db = crate::db::db::connect().await;
+) -> Result<Response, StatusCode> {
+    let db = &state.db;
 
     // 🔍 Attempt to find the user by email
     let user = User::find()
-        .filter(crate::models::user::Column::Email.eq(payload.email.clone()))
-        .one(&db)
-        .await
-        .unwrap();
+        .filter(user::Column::Email.eq(payload.email.trim()))
+        .one(db)
+        .await;
+    let user = match user {
+        Ok(user) => user,
+        Err(e) => return Ok(DbError(e).into_response()),
+    };
 
     // 🔐 Validate password
     if let Some(user) = user {
-        let valid = verify(payload.password, &user.password).unwrap();
+        let valid = verify(&payload.password, &user.password).unwrap();
+        let success = valid
+            && user.is_active
+            && (user.email_verified || !state.config.require_email_verification);
+        record_login(db, user.id, client_ip, &headers, success).await;
+        if valid && !user.is_active {
+            state.metrics.record_login_failure();
+            tracing::warn!(email = %user.email, client_ip = %client_ip, "login refused: account disabled");
+            return Ok((
+                StatusCode::FORBIDDEN,
+                Json(json!({ "error": "account_disabled" })),
+            )
+                .into_response());
+        }
+        if valid && state.config.require_email_verification && !user.email_verified {
+            state.metrics.record_login_failure();
+            tracing::warn!(email = %user.email, client_ip = %client_ip, "login refused: email not verified");
+            return Ok((
+                StatusCode::FORBIDDEN,
+                Json(json!({ "error": "email_not_verified" })),
+            )
+                .into_response());
+        }
         if valid {
+            // ♻️ Bring a hash made at an older cost up to the configured one
+            rehash_if_outdated(db, &user, &payload.password, state.config.bcrypt_cost).await;
+
             // 🎟️ Create JWT token using secret key
-            let secret = std::env::var("JWT_SECRET").unwrap();
-            let token = create_jwt(&user.email, &secret).unwrap();
+            let token = create_jwt(&user.email, &state.config.jwt_secret).unwrap();
 
-            return Ok(Json(LoginResponse { token }));
+            state.metrics.record_login_success();
+            tracing::info!(email = %user.email, client_ip = %client_ip, "login succeeded");
+
+            let mode = query
+                .mode
+                .unwrap_or_else(|| login_mode_from_accept(&headers));
+            return Ok(match mode {
+                LoginMode::Body => Json(LoginResponse { token }).into_response(),
+                LoginMode::Cookie => (
+                    CookieJar::new().add(auth_cookie(token)),
+                    StatusCode::NO_CONTENT,
+                )
+                    .into_response(),
+            });
         }
     }
 
     // 🚫 Unauthorized if no match or invalid credentials
+    state.metrics.record_login_failure();
+    tracing::warn!(email = %payload.email, client_ip = %client_ip, "login failed");
     Err(StatusCode::UNAUTHORIZED)
 }
 
+/// Re-hash `password` at `cost` if `user`'s stored hash uses another cost.
+///
+/// Only replaces the hash that was just verified, so a password changed
+/// concurrently isn't overwritten. Failures are logged; the old hash keeps
+/// working.
+async fn rehash_if_outdated(
+    db: &DatabaseConnection,
+    user: &user::Model,
+    password: &str,
+    cost: u32,
+) {
+    let current = user
+        .password
+        .parse::<HashParts>()
+        .map(|parts| parts.get_cost());
+    if current.is_ok_and(|c| c == cost) {
+        return;
+    }
+    let upgraded = match hash(password, cost) {
+        Ok(h) => h,
+        Err(e) => {
+            tracing::warn!(user_id = user.id, error = ?e, "password rehash failed");
+            return;
+        }
+    };
+    let result = User::update_many()
+        .col_expr(user::Column::Password, Expr::value(upgraded))
+        .filter(user::Column::Id.eq(user.id))
+        .filter(user::Column::Password.eq(user.password.as_str()))
+        .exec(db)
+        .await;
+    if let Err(e) = result {
+        tracing::warn!(user_id = user.id, error = %e, "rehashed password not stored");
+    }
+}
+
+/// Store a login attempt on `user_id`'s account, logging any failure.
+///
+/// The login itself goes ahead either way; losing an event isn't worth
+/// locking someone out over.
+async fn record_login(
+    db: &DatabaseConnection,
+    user_id: i32,
+    client_ip: ClientIp,
+    headers: &HeaderMap,
+    success: bool,
+) {
+    let event = login_event::ActiveModel {
+        user_id: Set(user_id),
+        ip: Set(client_ip.0.map(|ip| ip.to_string())),
+        user_agent: Set(headers
+            .get(header::USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_owned)),
+        success: Set(success),
+        created_at: Set(Utc::now().naive_utc()),
+        ..Default::default()
+    };
+    if let Err(e) = event.insert(db).await {
+        tracing::warn!(user_id, error = %e, "login event not recorded");
+    }
+}
+
+/// Browsers send `Accept: text/html,...` on navigations and form posts; API
+/// clients send `application/json` or nothing.
+fn login_mode_from_accept(headers: &HeaderMap) -> LoginMode {
+    let prefers_html = headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|accept| accept.split(',').next())
+        .is_some_and(|first| first.trim().starts_with("text/html"));
+    if prefers_html {
+        LoginMode::Cookie
+    } else {
+        LoginMode::Body
+    }
+}
+
+/// The auth cookie, living as long as the token inside it.
+fn auth_cookie(token: String) -> Cookie<'static> {
+    Cookie::build((AUTH_COOKIE, token))
+        .path("/")
+        .http_only(true)
+        .secure(true)
+        .same_site(SameSite::Strict)
+        .max_age(time::Duration::seconds(TOKEN_TTL.num_seconds()))
+        .build()
+}
+
+/// Mark the user holding a verification token as verified.
+///
+/// The token is single-use: it is cleared once redeemed.
+///
+/// # Query Parameters
+/// - `token`: The token from the verification link
+///
+/// # Returns
+/// - `200 OK` with `{ "email": ..., "email_verified": true }`
+/// - `400 BAD_REQUEST` with `{ "error": "invalid_token" }` if no user holds the token
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn verify_email(
+    State(state): State<AppState>,
+    Query(query): Query<VerifyEmailQuery>,
+) -> impl IntoResponse {
+    let db = &state.db;
+
+    // 🔍 Tokens are stored hashed, so look up the hash
+    let user = match User::find()
+        .filter(user::Column::EmailVerificationToken.eq(token::hash(&query.token)))
+        .one(db)
+        .await
+    {
+        Ok(Some(user)) => user,
+        Ok(None) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": "invalid_token" })),
+            )
+                .into_response();
+        }
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    let mut active = user.into_active_model();
+    active.email_verified = Set(true);
+    active.email_verification_token = Set(None);
+
+    match active.update(db).await {
+        Ok(user) => Json(json!({
+            "email": user.email,
+            "email_verified": user.email_verified,
+        }))
+        .into_response(),
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// Start a password reset by mailing a one-time token.
+///
+/// Always answers `200 OK` with the same message, whether or not the email
+/// belongs to an account, so the endpoint can't be used to probe for users.
+/// The token expires after `password_reset_ttl_secs` and only its hash is
+/// stored.
+///
+/// # JSON Payload
+/// - `email`: Address of the account to reset
+///
+/// # Returns
+/// - `200 OK` in every case but a DB failure
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn forgot_password(
+    State(state): State<AppState>,
+    Json(payload): Json<ForgotPasswordRequest>,
+) -> impl IntoResponse {
+    let db = &state.db;
+    let accepted = Json("📬 If that account exists, a reset link is on its way.");
+
+    let user = match User::find()
+        .filter(user::Column::Email.eq(payload.email.trim()))
+        .one(db)
+        .await
+    {
+        Ok(Some(user)) => user,
+        Ok(None) => return accepted.into_response(),
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    // 🎟️ Short-lived, single-use token; only its hash is kept
+    let (reset_token, reset_hash) = token::generate();
+    let now = Utc::now().naive_utc();
+    let ttl = Duration::seconds(state.config.password_reset_ttl_secs as i64);
+    let reset = password_reset::ActiveModel {
+        user_id: Set(user.id),
+        token_hash: Set(reset_hash),
+        expires_at: Set(now + ttl),
+        created_at: Set(now),
+        ..Default::default()
+    };
+    if let Err(e) = reset.insert(db).await {
+        return DbError(e).into_response();
+    }
+
+    let body = format!(
+        "Reset your TagBlaze password by sending this token to /auth/reset-password: {reset_token}"
+    );
+    if let Err(e) = state
+        .email
+        .send(&user.email, "Reset your TagBlaze password", &body)
+        .await
+    {
+        tracing::warn!(email = %user.email, error = %e, "password reset email failed");
+    }
+
+    accepted.into_response()
+}
+
+/// Finish a password reset with the mailed token.
+///
+/// The token is spent in the same transaction that stores the new hash, so
+/// it works at most once even under concurrent requests. Every login token
+/// issued before the reset is revoked in that transaction too.
+///
+/// # JSON Payload
+/// - `token`: The token from the reset email
+/// - `new_password`: The password to set
+///
+/// # Returns
+/// - `200 OK` once the password is changed
+/// - `400 BAD_REQUEST` with `{ "error": "invalid_token" }` for an unknown or already used token
+/// - `400 BAD_REQUEST` with `{ "error": "token_expired" }` for an expired token
+/// - `422 UNPROCESSABLE_ENTITY` with `{ "errors": { "new_password": ... } }` if the new
+///   password is empty; the token stays unused
+/// - `500 INTERNAL_SERVER_ERROR` on hashing or DB failure
+pub async fn reset_password(
+    State(state): State<AppState>,
+    Json(payload): Json<ResetPasswordRequest>,
+) -> impl IntoResponse {
+    let db = &state.db;
+    let rejected =
+        |error: &str| (StatusCode::BAD_REQUEST, Json(json!({ "error": error }))).into_response();
+
+    if payload.new_password.is_empty() {
+        let mut errors = ValidationErrors::default();
+        errors.add("new_password", "must not be empty");
+        return errors.into_response();
+    }
+
+    let reset = match password_reset::Entity::find()
+        .filter(password_reset::Column::TokenHash.eq(token::hash(&payload.token)))
+        .one(db)
+        .await
+    {
+        Ok(Some(reset)) if reset.used_at.is_none() => reset,
+        Ok(_) => return rejected("invalid_token"),
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    let now = Utc::now().naive_utc();
+    if reset.expires_at <= now {
+        return rejected("token_expired");
+    }
+
+    // 🔐 Hash the new password before touching the database
+    let password_hash = match hash(&payload.new_password, state.config.bcrypt_cost) {
+        Ok(h) => h,
+        Err(e) => {
+            tracing::error!(error = ?e, "password hashing failed");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let txn = match db.begin().await {
+        Ok(txn) => txn,
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    // 🔒 Claim the token; a concurrent reset that got there first wins
+    let claimed = password_reset::Entity::update_many()
+        .col_expr(password_reset::Column::UsedAt, Expr::value(now))
+        .filter(password_reset::Column::Id.eq(reset.id))
+        .filter(password_reset::Column::UsedAt.is_null())
+        .exec(&txn)
+        .await;
+    match claimed {
+        Ok(res) if res.rows_affected == 1 => {}
+        Ok(_) => return rejected("invalid_token"),
+        Err(e) => return DbError(e).into_response(),
+    }
+
+    // 🚪 Revoke every token issued before the reset, like an admin reset does
+    let updated = User::update_many()
+        .col_expr(user::Column::Password, Expr::value(password_hash))
+        .col_expr(
+            user::Column::TokensValidAfter,
+            Expr::value(now.with_nanosecond(0)),
+        )
+        .filter(user::Column::Id.eq(reset.user_id))
+        .exec(&txn)
+        .await;
+    if let Err(e) = updated {
+        return DbError(e).into_response();
+    }
+
+    match txn.commit().await {
+        Ok(()) => Json("🔑 Password updated.").into_response(),
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
 /// Return the identity of the currently authenticated user.
 ///
-/// This endpoint reads the JWT from the request,
-/// extracts the claims, and returns the user's email (subject).
+/// This endpoint authenticates the bearer token and returns the user's email.
 ///
 /// # Returns
 /// - `"👤 Logged in as: user@example.com"` if the token is valid
-/// - `"❌ Invalid token"` if authentication fails
-pub async fn me(req: Request) -> Json<String> {
-    match extract_claims(&req) {
-        Ok(claims) => Json(format!("👤 Logged in as: {}", claims.sub)),
-        Err(_) => Json("❌ Invalid token".into()),
+/// - `401 UNAUTHORIZED` with `token_expired`, `token_missing`, `token_invalid`, or `token_revoked` if authentication fails
+pub async fn me(AuthUser(user): AuthUser) -> Json<String> {
+    Json(format!("👤 Logged in as: {}", user.email))
+}
+
+/// One entry of the caller's login history.
+#[derive(Debug, Serialize)]
+pub struct LoginEntry {
+    pub id: i32,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub success: bool,
+    pub created_at: String,
+}
+
+impl From<login_event::Model> for LoginEntry {
+    fn from(event: login_event::Model) -> Self {
+        Self {
+            id: event.id,
+            ip: event.ip,
+            user_agent: event.user_agent,
+            success: event.success,
+            created_at: to_rfc3339(event.created_at),
+        }
+    }
+}
+
+/// List recent login attempts on the caller's account, newest first.
+///
+/// Covers both successful and failed attempts, so users can spot someone
+/// else trying their password. Attempts on unknown emails belong to no
+/// account and aren't listed anywhere.
+///
+/// # Query Parameters
+/// - `page`, `per_page`: See [`Pagination`]
+///
+/// # Returns
+/// - `200 OK` with `[{ "id", "ip", "user_agent", "success", "created_at" }]`,
+///   `X-Page`/`X-Per-Page` headers, and a `Link` header with `next`/`prev` pages
+/// - `401 UNAUTHORIZED` if JWT is invalid
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn get_my_logins(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    OriginalUri(uri): OriginalUri,
+    pagination: Pagination,
+) -> impl IntoResponse {
+    let db = &state.db;
+    let select = login_event::Entity::find().filter(login_event::Column::UserId.eq(user.id));
+
+    let total = match select.clone().count(db).await {
+        Ok(total) => total,
+        Err(e) => return DbError(e).into_response(),
+    };
+    let events = select
+        .order_by_desc(login_event::Column::CreatedAt)
+        .order_by_desc(login_event::Column::Id)
+        .offset(pagination.offset())
+        .limit(pagination.per_page)
+        .all(db)
+        .await;
+
+    match events {
+        Ok(events) => {
+            let entries: Vec<LoginEntry> = events.into_iter().map(LoginEntry::from).collect();
+            (pagination.headers_with_links(&uri, total), Json(entries)).into_response()
+        }
+        Err(e) => DbError(e).into_response(),
     }
-}
\ No newline at end of file
+}
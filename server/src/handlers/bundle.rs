@@ -0,0 +1,380 @@
+use std::collections::{BTreeSet, HashMap, hash_map::Entry};
+
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    Set,
+    sea_query::{Expr, Func},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    events::{self, TicketEventKind},
+    handlers::{
+        tag::{invalid_tag_name, owner_for, valid_tag_name},
+        ticket::{
+            OnOverflow, TicketWithTags, description_too_long, fit_description, ticket_quota_denied,
+        },
+    },
+    middleware::{auth::AuthUser, error::DbError, transaction::Tx},
+    models::{
+        tag, ticket, ticket_tag,
+        ticket_tag_event::{self, TagAction},
+        user,
+    },
+    state::AppState,
+    utils::{normalize, path::Path, time, validation::ValidationErrors},
+};
+
+/// Identifies the bundle layout, so future changes can stay readable.
+pub const BUNDLE_FORMAT: &str = "tagblaze.ticket.v1";
+
+/// A ticket and everything that happened to it, free of database IDs.
+///
+/// Tags are named rather than numbered and actors are given by email, so
+/// a bundle can be imported into another server.
+///
+/// # Fields
+/// - `format`: Always [`BUNDLE_FORMAT`].
+/// - `ticket`: The ticket's own fields, see [`BundledTicket`].
+/// - `tags`: Names of the tags attached now, sorted.
+/// - `activity`: Tag attach/detach events, oldest first, see [`BundledActivity`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TicketBundle {
+    pub format: String,
+    pub ticket: BundledTicket,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub activity: Vec<BundledActivity>,
+}
+
+/// The portable part of a ticket; who it belongs to is decided on import.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundledTicket {
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default, with = "time::rfc3339")]
+    pub created_at: Option<NaiveDateTime>,
+}
+
+/// One tag being attached to or detached from the bundled ticket.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundledActivity {
+    pub action: TagAction,
+    pub tag: String,
+    #[serde(default)]
+    pub actor_email: Option<String>,
+    #[serde(with = "time::rfc3339")]
+    pub created_at: Option<NaiveDateTime>,
+}
+
+/// Export a ticket, its tags, and its tag activity as a [`TicketBundle`].
+///
+/// Access control matches `GET /tickets/{id}`: admins may export every
+/// ticket, agents only their own.
+///
+/// # Returns
+/// - `200 OK` with the bundle
+/// - `401 UNAUTHORIZED` if JWT is invalid
+/// - `403 FORBIDDEN` if access is denied
+/// - `404 NOT_FOUND` if ticket doesn't exist
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn export_ticket_bundle(
+    State(state): State<AppState>,
+    Path(ticket_id): Path<i32>,
+    AuthUser(caller): AuthUser,
+) -> impl IntoResponse {
+    let db = &state.db;
+
+    let ticket = match ticket::Entity::find_by_id(ticket_id).one(db).await {
+        Ok(Some(ticket)) => ticket,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    // 🚫 Access control
+    if caller.role != "admin" && Some(caller.id) != ticket.user_id {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    match build_bundle(db, ticket).await {
+        Ok(bundle) => Json(bundle).into_response(),
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+async fn build_bundle<C: ConnectionTrait>(
+    db: &C,
+    ticket: ticket::Model,
+) -> Result<TicketBundle, DbErr> {
+    // 🏷️ Current tags, by name
+    let mut tags: Vec<String> = ticket_tag::Entity::find()
+        .filter(ticket_tag::Column::TicketId.eq(ticket.id))
+        .find_also_related(tag::Entity)
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|(_, tag)| tag.map(|t| t.name))
+        .collect();
+    tags.sort();
+
+    // 📜 Tag history, with tag names and actor emails resolved
+    let events = ticket_tag_event::Entity::find()
+        .filter(ticket_tag_event::Column::TicketId.eq(ticket.id))
+        .order_by_asc(ticket_tag_event::Column::CreatedAt)
+        .order_by_asc(ticket_tag_event::Column::Id)
+        .find_also_related(tag::Entity)
+        .all(db)
+        .await?;
+    let actor_ids: BTreeSet<i32> = events.iter().filter_map(|(e, _)| e.user_id).collect();
+    let actors: HashMap<i32, String> = user::Entity::find()
+        .filter(user::Column::Id.is_in(actor_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|u| (u.id, u.email))
+        .collect();
+    let activity = events
+        .into_iter()
+        .filter_map(|(event, tag)| {
+            let action = match event.action.as_str() {
+                "attached" => TagAction::Attached,
+                "detached" => TagAction::Detached,
+                _ => return None,
+            };
+            Some(BundledActivity {
+                action,
+                tag: tag?.name,
+                actor_email: event.user_id.and_then(|id| actors.get(&id).cloned()),
+                created_at: Some(event.created_at),
+            })
+        })
+        .collect();
+
+    Ok(TicketBundle {
+        format: BUNDLE_FORMAT.into(),
+        ticket: BundledTicket {
+            title: ticket.title,
+            description: ticket.description,
+            status: ticket.status,
+            created_at: ticket.created_at,
+        },
+        tags,
+        activity,
+    })
+}
+
+/// Recreate a ticket from a [`TicketBundle`], assigned to the caller.
+///
+/// Tags are matched by name, ignoring case, among the caller's own and
+/// global tags; names with no match are created as if by `POST /tags`.
+/// Activity keeps its timestamps, and actors are matched by email (left
+/// empty when unknown here). Status and `created_at` are kept as given.
+/// Everything is written in the request's [`Tx`], so a bad bundle leaves
+/// nothing behind; the tag cache is refreshed and a `Created` event
+/// published only after it commits. Unlike `POST /tickets`, no tag
+/// subscriber is emailed. The `max_tickets_per_user` quota applies as it
+/// does there.
+///
+/// # Returns
+/// - `201 CREATED` with the new ticket and its tags
+/// - `400 BAD_REQUEST` with `{ "error": "unsupported_format" }` for an unknown `format`
+/// - `400 BAD_REQUEST` with `{ "error": "invalid_tag_name", "name" }` if a tag name is blank or too long
+/// - `400 BAD_REQUEST` with `{ "error": "description_too_long", "max_len" }` if the description doesn't fit
+/// - `401 UNAUTHORIZED` if JWT is invalid
+/// - `403 FORBIDDEN` with `{ "error": "ticket_quota_exceeded", "max" }` once a non-admin is at the quota
+/// - `422 UNPROCESSABLE_ENTITY` with `{ "errors": { "title": message } }` for a blank title
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn import_ticket_bundle(
+    State(state): State<AppState>,
+    AuthUser(importer): AuthUser,
+    tx: Tx,
+    Json(mut bundle): Json<TicketBundle>,
+) -> impl IntoResponse {
+    if bundle.format != BUNDLE_FORMAT {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": "unsupported_format" })),
+        )
+            .into_response();
+    }
+
+    // 🧹 Check the fields a normal create would
+    let mut errors = ValidationErrors::default();
+    let Some(title) = errors.require(
+        "title",
+        normalize::trimmed(&bundle.ticket.title),
+        "must not be blank",
+    ) else {
+        return errors.into_response();
+    };
+    let names = bundle
+        .tags
+        .iter_mut()
+        .chain(bundle.activity.iter_mut().map(|a| &mut a.tag));
+    for name in names {
+        match valid_tag_name(name) {
+            Some(valid) => *name = valid,
+            None => return invalid_tag_name(name),
+        }
+    }
+    let max_len = state.config.max_description_len;
+    let description = match bundle.ticket.description {
+        Some(d) => match fit_description(d, max_len, OnOverflow::Reject) {
+            Some((d, _)) => Some(d),
+            None => return description_too_long(max_len),
+        },
+        None => None,
+    };
+
+    // 🎟️ The copy counts against the importer's quota like a new ticket
+    if let Some(denied) = ticket_quota_denied(&state, &*tx, &importer).await {
+        return denied;
+    }
+
+    let now = Utc::now().naive_utc();
+    let saved = match (ticket::ActiveModel {
+        title: Set(title),
+        description: Set(description),
+        status: Set(bundle.ticket.status),
+        user_id: Set(Some(importer.id)),
+        created_by: Set(Some(importer.id)),
+        updated_by: Set(Some(importer.id)),
+        created_at: Set(bundle.ticket.created_at.or(Some(now))),
+        updated_at: Set(Some(now)),
+        ..Default::default()
+    })
+    .insert(&*tx)
+    .await
+    {
+        Ok(saved) => saved,
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    match recreate_tags_and_activity(&*tx, &importer, saved.id, &bundle.tags, &bundle.activity)
+        .await
+    {
+        Ok(tags) => {
+            // 📣 Tags may be new and the ticket exists only once this commits
+            let ticket = saved.clone();
+            tx.after_commit(async move {
+                state.tag_cache.invalidate().await;
+                events::publish(&state.events, TicketEventKind::Created, ticket);
+            });
+            (
+                StatusCode::CREATED,
+                Json(TicketWithTags {
+                    ticket: saved,
+                    tags,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// Attach the bundle's tags to `ticket_id` and replay its activity, given
+/// tag names already normalized. Returns the attached tags, ordered by name.
+async fn recreate_tags_and_activity<C: ConnectionTrait>(
+    db: &C,
+    importer: &user::Model,
+    ticket_id: i32,
+    tags: &[String],
+    activity: &[BundledActivity],
+) -> Result<Vec<tag::Model>, DbErr> {
+    // 🔍 Resolve every name once, creating what's missing
+    let mut by_name: HashMap<String, tag::Model> = HashMap::new();
+    for name in tags.iter().chain(activity.iter().map(|a| &a.tag)) {
+        if let Entry::Vacant(slot) = by_name.entry(name.to_lowercase()) {
+            slot.insert(resolve_tag(db, importer, name).await?);
+        }
+    }
+
+    let mut attached: Vec<tag::Model> = Vec::new();
+    for name in tags {
+        let tag = &by_name[&name.to_lowercase()];
+        if attached.iter().any(|t| t.id == tag.id) {
+            continue;
+        }
+        ticket_tag::ActiveModel {
+            ticket_id: Set(ticket_id),
+            tag_id: Set(tag.id),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+        attached.push(tag.clone());
+    }
+    attached.sort_by(|a, b| a.name.cmp(&b.name));
+
+    // 👥 Actors by email, where they exist here
+    let emails: BTreeSet<&str> = activity
+        .iter()
+        .filter_map(|a| a.actor_email.as_deref())
+        .collect();
+    let actors: HashMap<String, i32> = user::Entity::find()
+        .filter(user::Column::Email.is_in(emails))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|u| (u.email, u.id))
+        .collect();
+
+    let now = Utc::now().naive_utc();
+    for entry in activity {
+        ticket_tag_event::ActiveModel {
+            ticket_id: Set(ticket_id),
+            tag_id: Set(by_name[&entry.tag.to_lowercase()].id),
+            user_id: Set(entry
+                .actor_email
+                .as_ref()
+                .and_then(|email| actors.get(email).copied())),
+            action: Set(entry.action.as_str().into()),
+            created_at: Set(entry.created_at.unwrap_or(now)),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+    }
+
+    Ok(attached)
+}
+
+/// The tag `importer` would mean by `name`: their own first, then a global
+/// one, else a new tag they own (global when the importer is an admin).
+async fn resolve_tag<C: ConnectionTrait>(
+    db: &C,
+    importer: &user::Model,
+    name: &str,
+) -> Result<tag::Model, DbErr> {
+    let mut candidates = tag::Entity::find()
+        .filter(Expr::expr(Func::lower(Expr::col(tag::Column::Name))).eq(name.to_lowercase()))
+        .filter(
+            tag::Column::OwnerId
+                .eq(importer.id)
+                .or(tag::Column::OwnerId.is_null()),
+        )
+        .all(db)
+        .await?;
+    candidates.sort_by_key(|t| t.owner_id.is_none());
+    if let Some(tag) = candidates.into_iter().next() {
+        return Ok(tag);
+    }
+
+    let now = Utc::now().naive_utc();
+    tag::ActiveModel {
+        name: Set(name.to_string()),
+        owner_id: Set(owner_for(importer)),
+        created_at: Set(Some(now)),
+        updated_at: Set(Some(now)),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+}
@@ -0,0 +1,47 @@
+use axum::{
+    Json,
+    http::{Method, StatusCode, Uri},
+    response::{IntoResponse, Response},
+};
+use serde_json::json;
+
+/// Fallback for paths that match no route.
+///
+/// # Returns
+/// - `404 NOT_FOUND` with `{ "error": "not_found", "path": "<requested>" }`
+pub async fn not_found(uri: Uri) -> impl IntoResponse {
+    (
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "not_found",
+            "path": uri.path()
+        })),
+    )
+        .into_response()
+}
+
+/// Fallback for known paths hit with an unsupported method.
+///
+/// Axum appends the `Allow` header listing the path's methods to this
+/// response; the handler only supplies a JSON body matching other errors.
+/// A plain `OPTIONS` request (CORS preflights are answered earlier by the
+/// CORS layer) gets an empty `204` instead, so the `Allow` header serves as
+/// the answer.
+///
+/// # Returns
+/// - `204 NO_CONTENT` for `OPTIONS`
+/// - `405 METHOD_NOT_ALLOWED` with `{ "error": "method_not_allowed", "method", "path" }`
+pub async fn method_not_allowed(method: Method, uri: Uri) -> Response {
+    if method == Method::OPTIONS {
+        return StatusCode::NO_CONTENT.into_response();
+    }
+    (
+        StatusCode::METHOD_NOT_ALLOWED,
+        Json(json!({
+            "error": "method_not_allowed",
+            "method": method.as_str(),
+            "path": uri.path()
+        })),
+    )
+        .into_response()
+}
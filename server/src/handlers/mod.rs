@@ -1,5 +1,7 @@
 pub mod admin;
 pub mod auth;
+pub mod bundle;
+pub mod fallback;
 pub mod relations;
 pub mod tag;
-pub mod ticket;
\ No newline at end of file
+pub mod ticket;
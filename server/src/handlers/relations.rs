@@ -1,18 +1,34 @@
-use axum::{Json, extract::Path, http::StatusCode, response::IntoResponse};
-use axum_extra::extract::TypedHeader;
-use headers::{Authorization, authorization::Bearer};
-use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use std::collections::{BTreeMap, HashMap};
+
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait,
+    QueryFilter, QueryOrder, Set, SqlErr, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 use crate::{
-    db::db::connect,
-    models::{tag, ticket_tag, ticket_tag::Entity as TicketTagEntity},
-    utils::jwt::extract_claims,
+    middleware::{auth::AuthUser, error::DbError},
+    models::{
+        tag, ticket, ticket_tag,
+        ticket_tag::Entity as TicketTagEntity,
+        ticket_tag_event::{self, TagAction},
+        user,
+    },
+    notifications::notify_tag_subscribers,
+    state::AppState,
+    utils::{path::Path, time},
 };
 
 /// Attach a tag to a ticket (create a relation).
 ///
-/// Requires a valid JWT bearer token for authentication.
+/// Requires a valid JWT bearer token for authentication. The caller is
+/// recorded as the actor in the ticket's tag history.
+///
+/// A new attach emails the tag's subscribers, see
+/// [`notify_tag_subscribers`]; re-attaching doesn't.
 ///
 /// # Path Params
 /// - `ticket_id`: ID of the ticket
@@ -22,19 +38,17 @@ use crate::{
 /// - `Authorization: Bearer <token>`
 ///
 /// # Returns
-/// - `201 CREATED` on success
-/// - `409 CONFLICT` if the relation already exists
+/// - `201 CREATED` with the ticket's full tag list after attaching
+/// - `200 OK` with the same list if the tag was already attached; concurrent
+///   attaches of one pair race on the unique index and the loser lands here
+/// - `409 CONFLICT` if the relation can't be created (e.g. unknown ticket or tag)
 /// - `401 UNAUTHORIZED` if token is invalid
 pub async fn attach_tag(
+    State(state): State<AppState>,
     Path((ticket_id, tag_id)): Path<(i32, i32)>,
-    TypedHeader(Authorization(bearer)): TypedHeader<Authorization<Bearer>>,
+    AuthUser(user): AuthUser,
 ) -> impl IntoResponse {
-    // 🛡️ Authenticate the request via JWT
-    if extract_claims(bearer.token()).is_err() {
-        return StatusCode::UNAUTHORIZED;
-    }
-
-    let db = connect().await;
+    let db = &state.db;
 
     // 🔗 Create new tag-ticket relation
     let link = ticket_tag::ActiveModel {
@@ -43,40 +57,113 @@ pub async fn attach_tag(
         ..Default::default()
     };
 
-    // 💾 Try to insert relation into DB
-    match link.insert(&db).await {
-        Ok(_) => StatusCode::CREATED,
-        Err(_) => StatusCode::CONFLICT,
+    // 💾 Insert the relation and its history entry together
+    let txn = match db.begin().await {
+        Ok(txn) => txn,
+        Err(e) => return DbError(e).into_response(),
+    };
+    let status = match link.insert(&txn).await {
+        Ok(_) => {
+            if let Err(e) =
+                record_tag_event(&txn, ticket_id, tag_id, user.id, TagAction::Attached).await
+            {
+                return DbError(e).into_response();
+            }
+            if let Err(e) = txn.commit().await {
+                return DbError(e).into_response();
+            }
+            notify_subscribers(&state, ticket_id, tag_id, user.id).await;
+            StatusCode::CREATED
+        }
+        // 🏁 Already attached, maybe by a racing request: the end state is right
+        Err(e) if matches!(e.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) => {
+            StatusCode::OK
+        }
+        Err(_) => return StatusCode::CONFLICT.into_response(),
+    };
+
+    // 📋 Return the ticket's tags so the client needn't refetch
+    match tags_for_ticket(db, ticket_id).await {
+        Ok(tags) => (status, Json(tags)).into_response(),
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// Tell subscribers about a freshly attached tag, logging any failure.
+async fn notify_subscribers(state: &AppState, ticket_id: i32, tag_id: i32, actor_id: i32) {
+    let result = match ticket::Entity::find_by_id(ticket_id).one(&state.db).await {
+        Ok(Some(ticket)) => {
+            notify_tag_subscribers(state, &state.db, &ticket, &[tag_id], actor_id).await
+        }
+        Ok(None) => Ok(0),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        tracing::warn!(ticket_id, tag_id, error = %e, "tag subscribers not notified");
+    }
+}
+
+/// Append an entry to a ticket's tag history.
+pub(crate) async fn record_tag_event<C: ConnectionTrait>(
+    db: &C,
+    ticket_id: i32,
+    tag_id: i32,
+    actor_id: i32,
+    action: TagAction,
+) -> Result<(), DbErr> {
+    ticket_tag_event::ActiveModel {
+        ticket_id: Set(ticket_id),
+        tag_id: Set(tag_id),
+        user_id: Set(Some(actor_id)),
+        action: Set(action.as_str().into()),
+        created_at: Set(Utc::now().naive_utc()),
+        ..Default::default()
     }
+    .insert(db)
+    .await
+    .map(|_| ())
+}
+
+/// Tags attached to a ticket, ordered by name.
+///
+/// Soft-deleted tags are included; their `deleted_at` is set.
+async fn tags_for_ticket(
+    db: &DatabaseConnection,
+    ticket_id: i32,
+) -> Result<Vec<tag::Model>, DbErr> {
+    let pairs = ticket_tag::Entity::find()
+        .filter(ticket_tag::Column::TicketId.eq(ticket_id))
+        .find_also_related(tag::Entity)
+        .order_by_asc(tag::Column::Name)
+        .all(db)
+        .await?;
+
+    // Extract only the tag part of the (ticket_tag, tag) pair
+    Ok(pairs
+        .into_iter()
+        .filter_map(|(_, maybe_tag)| maybe_tag)
+        .collect())
 }
 
 /// Fetch all tags associated with a given ticket.
 ///
+/// Soft-deleted tags still resolve here, marked by a non-null `deleted_at`.
+///
 /// # Path Params
 /// - `ticket_id`: ID of the ticket to fetch tags for
 ///
 /// # Returns
 /// - `200 OK` with a JSON array of tag objects
 /// - `500 INTERNAL_SERVER_ERROR` on failure
-pub async fn get_tags_for_ticket(Path(ticket_id): Path<i32>) -> impl IntoResponse {
-    let db: DatabaseConnection = connect().await;
+pub async fn get_tags_for_ticket(
+    State(state): State<AppState>,
+    Path(ticket_id): Path<i32>,
+) -> impl IntoResponse {
+    let db = &state.db;
 
     // 🔍 Find all ticket_tag entries related to this ticket, with tag data joined
-    match ticket_tag::Entity::find()
-        .filter(ticket_tag::Column::TicketId.eq(ticket_id))
-        .find_also_related(tag::Entity)
-        .all(&db)
-        .await
-    {
-        Ok(pairs) => {
-            // Extract only the tag part of the (ticket_tag, tag) pair
-            let tags: Vec<_> = pairs
-                .into_iter()
-                .filter_map(|(_, maybe_tag)| maybe_tag)
-                .collect();
-
-            Json(tags).into_response()
-        }
+    match tags_for_ticket(db, ticket_id).await {
+        Ok(tags) => Json(tags).into_response(),
         Err(e) => {
             eprintln!("❌ Failed to fetch tags for ticket {}: {:?}", ticket_id, e);
             (
@@ -86,31 +173,287 @@ pub async fn get_tags_for_ticket(Path(ticket_id): Path<i32>) -> impl IntoRespons
                     "details": e.to_string(),
                     "ticket_id": ticket_id
                 })),
-            ).into_response()
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Body of `POST /relations/tags/batch`.
+#[derive(Debug, Deserialize)]
+pub struct BatchTicketTags {
+    pub ticket_ids: Vec<i32>,
+}
+
+/// Fetch the tags of several tickets at once.
+///
+/// The batch form of [`get_tags_for_ticket`] for list views: one joined
+/// query filtered on `ticket_id IN (...)` replaces a request per ticket.
+/// Every requested ID appears in the result, with `[]` for tickets that
+/// have no tags or don't exist. Tags are ordered by name, and soft-deleted
+/// ones are included with `deleted_at` set.
+///
+/// # Request Body
+/// - `ticket_ids`: Tickets to fetch tags for
+///
+/// # Returns
+/// - `200 OK` with `{ "<ticket_id>": [tag, ...] }`
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn get_tags_for_tickets(
+    State(state): State<AppState>,
+    Json(payload): Json<BatchTicketTags>,
+) -> impl IntoResponse {
+    let db = &state.db;
+
+    // 🔗 One query for every (ticket_tag, tag) pair across the batch
+    let pairs = match ticket_tag::Entity::find()
+        .filter(ticket_tag::Column::TicketId.is_in(payload.ticket_ids.clone()))
+        .find_also_related(tag::Entity)
+        .order_by_asc(tag::Column::Name)
+        .all(db)
+        .await
+    {
+        Ok(pairs) => pairs,
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    let mut tags_by_ticket: BTreeMap<i32, Vec<tag::Model>> = payload
+        .ticket_ids
+        .into_iter()
+        .map(|id| (id, Vec::new()))
+        .collect();
+    for (link, tag) in pairs {
+        if let Some(tag) = tag {
+            tags_by_ticket.entry(link.ticket_id).or_default().push(tag);
         }
     }
+    Json(tags_by_ticket).into_response()
 }
 
 /// Detach a tag from a ticket (delete the relation).
 ///
+/// Requires a valid JWT bearer token; the caller is recorded as the actor
+/// in the ticket's tag history. Detaching a tag the ticket doesn't carry
+/// changes nothing and records nothing.
+///
 /// # Path Params
 /// - `ticket_id`: ID of the ticket
 /// - `tag_id`: ID of the tag to detach
 ///
 /// # Returns
-/// - `204 NO_CONTENT` on success
+/// - `200 OK` with the ticket's remaining tags
+/// - `401 UNAUTHORIZED` if token is invalid
 /// - `500 INTERNAL_SERVER_ERROR` on failure
-pub async fn detach_tag(Path((ticket_id, tag_id)): Path<(i32, i32)>) -> impl IntoResponse {
-    let db = connect().await;
+pub async fn detach_tag(
+    State(state): State<AppState>,
+    Path((ticket_id, tag_id)): Path<(i32, i32)>,
+    AuthUser(user): AuthUser,
+) -> impl IntoResponse {
+    let db = &state.db;
+
+    let txn = match db.begin().await {
+        Ok(txn) => txn,
+        Err(e) => return DbError(e).into_response(),
+    };
 
     // 🗑️ Delete the specific ticket-tag relation
-    match TicketTagEntity::delete_many()
+    let deleted = match TicketTagEntity::delete_many()
         .filter(ticket_tag::Column::TicketId.eq(ticket_id))
         .filter(ticket_tag::Column::TagId.eq(tag_id))
-        .exec(&db)
+        .exec(&txn)
         .await
     {
-        Ok(_) => StatusCode::NO_CONTENT,
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        Ok(res) => res.rows_affected,
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    // 📜 Only a relation that existed leaves a trace
+    if deleted > 0
+        && let Err(e) =
+            record_tag_event(&txn, ticket_id, tag_id, user.id, TagAction::Detached).await
+    {
+        return DbError(e).into_response();
+    }
+    if let Err(e) = txn.commit().await {
+        return DbError(e).into_response();
+    }
+
+    match tags_for_ticket(db, ticket_id).await {
+        Ok(tags) => Json(tags).into_response(),
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// One entry in a ticket's tag history.
+#[derive(Debug, Serialize)]
+pub struct TagHistoryEntry {
+    pub action: String,
+    pub tag_id: i32,
+    pub tag_name: Option<String>,
+    pub actor_id: Option<i32>,
+    pub actor_email: Option<String>,
+    #[serde(serialize_with = "time::rfc3339::serialize")]
+    pub created_at: Option<NaiveDateTime>,
+}
+
+/// List the tags attached to and detached from a ticket, oldest first.
+///
+/// Access control matches `GET /tickets/{id}`: admins see every ticket,
+/// agents only their own.
+///
+/// # Path Params
+/// - `id`: ID of the ticket
+///
+/// # Returns
+/// - `200 OK` with a JSON array of [`TagHistoryEntry`]
+/// - `403 FORBIDDEN` if access is denied
+/// - `404 NOT_FOUND` if ticket doesn't exist
+/// - `401 UNAUTHORIZED` if JWT is invalid
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn get_tag_history(
+    State(state): State<AppState>,
+    Path(ticket_id): Path<i32>,
+    AuthUser(caller): AuthUser,
+) -> impl IntoResponse {
+    let db = &state.db;
+
+    let ticket = match ticket::Entity::find_by_id(ticket_id).one(db).await {
+        Ok(Some(ticket)) => ticket,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    // 🚫 Access control
+    if caller.role != "admin" && Some(caller.id) != ticket.user_id {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    match load_tag_history(db, ticket_id).await {
+        Ok(history) => Json(history).into_response(),
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// A ticket's tag events, oldest first, with tag names and actor emails resolved.
+async fn load_tag_history(
+    db: &DatabaseConnection,
+    ticket_id: i32,
+) -> Result<Vec<TagHistoryEntry>, DbErr> {
+    let events = ticket_tag_event::Entity::find()
+        .filter(ticket_tag_event::Column::TicketId.eq(ticket_id))
+        .order_by_asc(ticket_tag_event::Column::CreatedAt)
+        .order_by_asc(ticket_tag_event::Column::Id)
+        .find_also_related(tag::Entity)
+        .all(db)
+        .await?;
+
+    // 👥 Resolve actors in one query
+    let actor_ids: Vec<i32> = events.iter().filter_map(|(e, _)| e.user_id).collect();
+    let actors = emails_by_id(db, actor_ids).await?;
+
+    Ok(events
+        .into_iter()
+        .map(|(event, tag)| TagHistoryEntry {
+            actor_email: event.user_id.and_then(|id| actors.get(&id).cloned()),
+            action: event.action,
+            tag_id: event.tag_id,
+            tag_name: tag.map(|t| t.name),
+            actor_id: event.user_id,
+            created_at: Some(event.created_at),
+        })
+        .collect())
+}
+
+async fn emails_by_id(
+    db: &DatabaseConnection,
+    ids: Vec<i32>,
+) -> Result<HashMap<i32, String>, DbErr> {
+    Ok(user::Entity::find()
+        .filter(user::Column::Id.is_in(ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|u| (u.id, u.email))
+        .collect())
+}
+
+/// One entry in a ticket's timeline, discriminated by `type`.
+///
+/// - `created`: The ticket was opened.
+/// - `tag`: A tag was attached or detached, see [`TagHistoryEntry`].
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TimelineEntry {
+    Created {
+        actor_id: Option<i32>,
+        actor_email: Option<String>,
+        #[serde(serialize_with = "time::rfc3339::serialize")]
+        created_at: Option<NaiveDateTime>,
+    },
+    Tag(TagHistoryEntry),
+}
+
+impl TimelineEntry {
+    fn at(&self) -> Option<NaiveDateTime> {
+        match self {
+            TimelineEntry::Created { created_at, .. } => *created_at,
+            TimelineEntry::Tag(entry) => entry.created_at,
+        }
+    }
+}
+
+/// Everything that happened to a ticket, merged into one list, oldest first.
+///
+/// Merges the ticket's creation with its tag history so clients don't have
+/// to fetch and interleave them. Entries at the same moment keep source
+/// order (creation first). Access control matches `GET /tickets/{id}`.
+///
+/// # Path Params
+/// - `id`: ID of the ticket
+///
+/// # Returns
+/// - `200 OK` with a JSON array of [`TimelineEntry`]
+/// - `403 FORBIDDEN` if access is denied
+/// - `404 NOT_FOUND` if ticket doesn't exist
+/// - `401 UNAUTHORIZED` if JWT is invalid
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn get_timeline(
+    State(state): State<AppState>,
+    Path(ticket_id): Path<i32>,
+    AuthUser(caller): AuthUser,
+) -> impl IntoResponse {
+    let db = &state.db;
+
+    let ticket = match ticket::Entity::find_by_id(ticket_id).one(db).await {
+        Ok(Some(ticket)) => ticket,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    // 🚫 Access control
+    if caller.role != "admin" && Some(caller.id) != ticket.user_id {
+        return StatusCode::FORBIDDEN.into_response();
     }
-}
\ No newline at end of file
+
+    // 📚 Gather every source
+    let history = match load_tag_history(db, ticket_id).await {
+        Ok(history) => history,
+        Err(e) => return DbError(e).into_response(),
+    };
+    let creator = match emails_by_id(db, ticket.created_by.into_iter().collect()).await {
+        Ok(emails) => ticket.created_by.and_then(|id| emails.get(&id).cloned()),
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    let mut timeline = vec![TimelineEntry::Created {
+        actor_id: ticket.created_by,
+        actor_email: creator,
+        created_at: ticket.created_at,
+    }];
+    timeline.extend(history.into_iter().map(TimelineEntry::Tag));
+
+    // 🕰️ Stable sort keeps source order for ties
+    timeline.sort_by_key(TimelineEntry::at);
+
+    Json(timeline).into_response()
+}
@@ -1,15 +1,36 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
 use axum::{
-    extract::{Json, Path},
-    http::StatusCode,
-    response::IntoResponse,
+    extract::{Json, OriginalUri, Query, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseTransaction, DbErr,
+    EntityTrait, FromQueryResult, IntoActiveModel, JoinType, ModelTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, RelationTrait, Select, Set, SqlErr, TransactionTrait,
+    sea_query::{Expr, Func},
 };
-use axum_extra::extract::TypedHeader;
-use chrono::Local;
-use headers::{Authorization, authorization::Bearer};
-use sea_orm::{ActiveModelTrait, EntityTrait, IntoActiveModel, ModelTrait, Set};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
 
-use crate::{db::db::connect, models::tag, utils::jwt::extract_claims};
+use crate::{
+    db::instrument::timed,
+    handlers::ticket::count_by_status,
+    middleware::{auth::AuthUser, error::DbError},
+    models::{
+        tag, tag_subscription, ticket, ticket_tag,
+        ticket_tag_event::{self, TagAction},
+        user::{self, Role},
+    },
+    state::AppState,
+    utils::{
+        date_range::CreatedRange, normalize, pagination::Pagination, path::Path,
+        validation::ValidationErrors,
+    },
+};
 
 /// Payload for creating a new tag.
 #[derive(Deserialize)]
@@ -21,58 +42,412 @@ pub struct CreateTag {
 #[derive(Deserialize)]
 pub struct UpdateTag {
     pub name: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Payload for creating many tags at once.
+#[derive(Deserialize)]
+pub struct BatchCreateTags {
+    pub names: Vec<String>,
+}
+
+/// Payload for checking which tag IDs exist.
+#[derive(Deserialize)]
+pub struct ValidateTagIds {
+    pub ids: Vec<i32>,
+}
+
+/// Query parameters accepted by `get_tags`.
+#[derive(Debug, Default, Deserialize)]
+pub struct TagListQuery {
+    #[serde(default)]
+    pub include_deleted: bool,
+}
+
+/// Longest tag name accepted.
+pub const MAX_TAG_NAME_LEN: usize = 64;
+
+/// Normalize a tag name (see [`normalize::name`]), or `None` if it ends up
+/// empty or longer than [`MAX_TAG_NAME_LEN`].
+pub fn valid_tag_name(raw: &str) -> Option<String> {
+    normalize::name(raw).filter(|name| name.chars().count() <= MAX_TAG_NAME_LEN)
+}
+
+pub(crate) fn invalid_tag_name(raw: &str) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({ "error": "invalid_tag_name", "name": raw })),
+    )
+        .into_response()
+}
+
+/// Owner recorded for a tag created by `user`: agents own what they create,
+/// admins create global tags.
+pub(crate) fn owner_for(user: &user::Model) -> Option<i32> {
+    (user.role != Role::Admin.as_str()).then_some(user.id)
+}
+
+/// Whether `caller` may change or delete `tag` under `tag_ownership`.
+///
+/// Returns the status to reject with, or `None` to allow. With ownership
+/// off, anyone may. With it on, admins may change any tag, agents only
+/// their own; global tags are admin-only.
+fn tag_write_denied(
+    state: &AppState,
+    caller: Option<&user::Model>,
+    tag: &tag::Model,
+) -> Option<StatusCode> {
+    if !state.config.tag_ownership {
+        return None;
+    }
+    match caller {
+        None => Some(StatusCode::UNAUTHORIZED),
+        Some(user) if user.role == Role::Admin.as_str() => None,
+        Some(user) if tag.owner_id == Some(user.id) => None,
+        Some(_) => Some(StatusCode::FORBIDDEN),
+    }
+}
+
+/// Tags sharing a name namespace with `owner_id`.
+///
+/// With `per_owner` (`tag_ownership` on), that agent's own tags, or every
+/// global tag when `owner_id` is `None`. Otherwise tags are shared, so
+/// every tag.
+fn same_owner(owner_id: Option<i32>, per_owner: bool) -> Condition {
+    match owner_id {
+        _ if !per_owner => Condition::all(),
+        Some(id) => Condition::all().add(tag::Column::OwnerId.eq(id)),
+        None => Condition::all().add(tag::Column::OwnerId.is_null()),
+    }
+}
+
+/// Tags named `name`, ignoring case, within `owner_id`'s namespace (see
+/// [`same_owner`]).
+pub(crate) fn find_named(
+    name: &str,
+    owner_id: Option<i32>,
+    per_owner: bool,
+) -> Select<tag::Entity> {
+    tag::Entity::find()
+        .filter(same_owner(owner_id, per_owner))
+        .filter(Expr::expr(Func::lower(Expr::col(tag::Column::Name))).eq(name.to_lowercase()))
+}
+
+/// Whether a tag other than `except` already uses `name`, ignoring case,
+/// within `owner_id`'s namespace.
+///
+/// With `per_owner`, names are unique per owner, so two agents may each
+/// have a "Bug"; global tags are unique among themselves. Backed by the
+/// `tag_owner_id_lower_name` and `tag_global_lower_name` indexes, which
+/// catch racing writes. Without it, names are unique across all tags.
+async fn name_taken<C: ConnectionTrait>(
+    db: &C,
+    name: &str,
+    owner_id: Option<i32>,
+    per_owner: bool,
+    except: Option<i32>,
+) -> Result<bool, DbErr> {
+    let mut select = find_named(name, owner_id, per_owner);
+    if let Some(id) = except {
+        select = select.filter(tag::Column::Id.ne(id));
+    }
+    Ok(select.count(db).await? > 0)
+}
+
+/// Whether `e` is a write losing the race for a name to another tag.
+fn is_name_clash(e: &DbErr) -> bool {
+    matches!(e.sql_err(), Some(SqlErr::UniqueConstraintViolation(_)))
+}
+
+/// Payload for merging one tag into another.
+#[derive(Deserialize)]
+pub struct MergeTags {
+    pub from_id: i32,
+    pub into_id: i32,
 }
 
 /// Create a new tag.
 ///
-/// Requires a valid bearer token. Accepts a JSON payload with the tag name.
-/// Timestamps for `created_at` and `updated_at` are automatically set.
+/// Requires a valid bearer token. Accepts a JSON payload with the tag name,
+/// which is trimmed with inner whitespace collapsed. Timestamps for
+/// `created_at` and `updated_at` are automatically set. Tags created by an
+/// agent are owned by them; tags created by an admin are global.
+///
+/// Names are unique ignoring case. With `tag_ownership` on they are unique
+/// per owner: two agents may each own a "Bug", and a global "Bug" doesn't
+/// stop either, but no owner gets two. With it off tags are shared, so no
+/// two tags share a name.
 ///
 /// # Returns
 /// - `200 OK` with the created tag
+/// - `400 BAD_REQUEST` with `{ "error": "invalid_tag_name", "name" }` if the name is blank or longer than [`MAX_TAG_NAME_LEN`]
 /// - `401 UNAUTHORIZED` if token is missing/invalid
+/// - `409 CONFLICT` if the name is already taken
 /// - `500 INTERNAL_SERVER_ERROR` on DB failure
 pub async fn create_tag(
-    TypedHeader(Authorization(bearer)): TypedHeader<Authorization<Bearer>>,
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
     Json(payload): Json<CreateTag>,
 ) -> impl IntoResponse {
-    // 🛡️ Validate JWT token
-    let _claims = match extract_claims(bearer.token()) {
-        Ok(c) => c,
-        Err(_) => return StatusCode::UNAUTHORIZED.into_response(),
+    let db = &state.db;
+    let now = Utc::now().naive_utc();
+
+    let Some(name) = valid_tag_name(&payload.name) else {
+        return invalid_tag_name(&payload.name);
     };
 
-    let db = connect().await;
-    let now = Local::now().naive_local();
+    // ⚔️ Each name is used once per namespace
+    let owner_id = owner_for(&user);
+    match name_taken(db, &name, owner_id, state.config.tag_ownership, None).await {
+        Ok(true) => return StatusCode::CONFLICT.into_response(),
+        Ok(false) => {}
+        Err(e) => return DbError(e).into_response(),
+    }
 
     // 🧱 Construct new tag ActiveModel
     let new_tag = tag::ActiveModel {
-        name: Set(payload.name),
+        name: Set(name),
+        owner_id: Set(owner_id),
         created_at: Set(Some(now)),
         updated_at: Set(Some(now)),
         ..Default::default()
     };
 
     // 💾 Insert into DB
-    match new_tag.insert(&db).await {
-        Ok(saved_tag) => axum::Json::<tag::Model>(saved_tag).into_response(),
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    match new_tag.insert(db).await {
+        Ok(saved_tag) => {
+            state.tag_cache.invalidate().await;
+            axum::Json::<tag::Model>(saved_tag).into_response()
+        }
+        Err(e) if is_name_clash(&e) => StatusCode::CONFLICT.into_response(),
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// Create many tags in one transaction.
+///
+/// Names are trimmed with inner whitespace collapsed. Names the caller's
+/// namespace already has, compared case-insensitively as in [`create_tag`]
+/// (including repeats within the batch), are skipped rather than failing. If any name is empty or longer than
+/// [`MAX_TAG_NAME_LEN`], nothing is created. Ownership follows
+/// [`create_tag`].
+///
+/// # JSON Payload
+/// - `names`: Tag names to create
+///
+/// # Returns
+/// - `200 OK` with `{ "created": [tag], "skipped": [name] }`
+/// - `400 BAD_REQUEST` with `{ "error": "invalid_tag_name", "name" }` if any name is invalid
+/// - `401 UNAUTHORIZED` if token is missing/invalid
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn batch_create_tags(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Json(payload): Json<BatchCreateTags>,
+) -> impl IntoResponse {
+    // 🧪 Validate everything up front so a bad name fails the whole batch
+    let mut names = Vec::with_capacity(payload.names.len());
+    for raw in &payload.names {
+        match valid_tag_name(raw) {
+            Some(name) => names.push(name),
+            None => return invalid_tag_name(raw),
+        }
+    }
+
+    let txn = match state.db.begin().await {
+        Ok(txn) => txn,
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    let per_owner = state.config.tag_ownership;
+    match insert_new_tags(&txn, names, owner_for(&user), per_owner).await {
+        Ok((created, skipped)) => match txn.commit().await {
+            Ok(_) => {
+                state.tag_cache.invalidate().await;
+                Json(json!({ "created": created, "skipped": skipped })).into_response()
+            }
+            Err(e) => DbError(e).into_response(),
+        },
+        Err(e) => DbError(e).into_response(),
     }
 }
 
+/// Insert the names not yet taken (case-insensitively) in `owner_id`'s
+/// namespace; returns `(created, skipped)`.
+async fn insert_new_tags(
+    txn: &DatabaseTransaction,
+    names: Vec<String>,
+    owner_id: Option<i32>,
+    per_owner: bool,
+) -> Result<(Vec<tag::Model>, Vec<String>), DbErr> {
+    let lowered: Vec<String> = names.iter().map(|n| n.to_lowercase()).collect();
+    let existing: Vec<String> = tag::Entity::find()
+        .select_only()
+        .column(tag::Column::Name)
+        .filter(same_owner(owner_id, per_owner))
+        .filter(Expr::expr(Func::lower(Expr::col(tag::Column::Name))).is_in(lowered))
+        .into_tuple()
+        .all(txn)
+        .await?;
+
+    let mut taken: HashSet<String> = existing.iter().map(|n| n.to_lowercase()).collect();
+    let now = Utc::now().naive_utc();
+    let mut created = Vec::new();
+    let mut skipped = Vec::new();
+
+    for name in names {
+        if !taken.insert(name.to_lowercase()) {
+            skipped.push(name);
+            continue;
+        }
+        let tag = tag::ActiveModel {
+            name: Set(name),
+            owner_id: Set(owner_id),
+            created_at: Set(Some(now)),
+            updated_at: Set(Some(now)),
+            ..Default::default()
+        }
+        .insert(txn)
+        .await?;
+        created.push(tag);
+    }
+
+    Ok((created, skipped))
+}
+
+/// Check which of a batch of tag IDs exist, in one query.
+///
+/// Lets a client vet `tag_ids` before creating a ticket instead of
+/// finding out from a `400 unknown_tag`. Soft-deleted tags count as
+/// valid, since tickets may still carry them. Both lists keep the request
+/// order with repeats dropped.
+///
+/// # JSON Payload
+/// - `ids`: Tag IDs to check
+///
+/// # Returns
+/// - `200 OK` with `{ "valid": [id], "invalid": [id] }`
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn validate_tag_ids(
+    State(state): State<AppState>,
+    Json(payload): Json<ValidateTagIds>,
+) -> impl IntoResponse {
+    let mut seen = HashSet::new();
+    let ids: Vec<i32> = payload
+        .ids
+        .into_iter()
+        .filter(|id| seen.insert(*id))
+        .collect();
+
+    let found: Vec<i32> = match tag::Entity::find()
+        .select_only()
+        .column(tag::Column::Id)
+        .filter(tag::Column::Id.is_in(ids.iter().copied()))
+        .into_tuple()
+        .all(&state.db)
+        .await
+    {
+        Ok(found) => found,
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    let found: HashSet<i32> = found.into_iter().collect();
+    let (valid, invalid): (Vec<i32>, Vec<i32>) = ids.into_iter().partition(|id| found.contains(id));
+    Json(json!({ "valid": valid, "invalid": invalid })).into_response()
+}
+
 /// Fetch all tags.
 ///
-/// Public route that returns a list of all tags in the database.
+/// Public route that returns a page of tags, ordered by ID.
+/// Soft-deleted tags are hidden unless `include_deleted=true`. Reads come
+/// from [`TagCache`](crate::cache::TagCache), which every tag write invalidates,
+/// filled from [`AppState::read_db`].
+///
+/// # Query Parameters
+/// - `page`, `per_page`: See [`Pagination`]
+/// - `include_deleted`: Also list soft-deleted tags
+/// - `created_after`, `created_before`: Inclusive RFC 3339 bounds, see [`CreatedRange`]
+///
+/// # Headers
+/// - `If-None-Match`: An `ETag` from an earlier response; answered with `304`
+///   while the matching tags are unchanged (see [`tag_list_etag`])
 ///
 /// # Returns
-/// - `200 OK` with array of tags
+/// - `200 OK` with array of tags, an `ETag`, `X-Page`/`X-Per-Page` headers, and
+///   a `Link` header with `next`/`prev` pages (see [`Pagination::headers_with_links`])
+/// - `304 NOT_MODIFIED` with the `ETag` and no body if `If-None-Match` matches
+/// - `400 BAD_REQUEST` if the created range is invalid or inverted
 /// - `500 INTERNAL_SERVER_ERROR` on DB failure
-pub async fn get_tags() -> impl IntoResponse {
-    let db = connect().await;
-    match tag::Entity::find().all(&db).await {
-        Ok(tags) => axum::Json::<Vec<tag::Model>>(tags).into_response(),
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+pub async fn get_tags(
+    State(state): State<AppState>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+    Query(query): Query<TagListQuery>,
+    pagination: Pagination,
+    created: CreatedRange,
+) -> impl IntoResponse {
+    // 🗃️ Served from the in-memory snapshot; tag writes invalidate it
+    let tags = match state.tag_cache.load(&state.read_db).await {
+        Ok(tags) => tags,
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    let matching: Vec<&tag::Model> = tags
+        .iter()
+        .filter(|t| query.include_deleted || t.deleted_at.is_none())
+        .filter(|t| created.contains(t.created_at))
+        .collect();
+
+    // 🔁 Unchanged since the client's copy: skip the body
+    let etag = tag_list_etag(&matching);
+    if if_none_match(&headers, &etag) {
+        return (StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response();
     }
+
+    let page: Vec<tag::Model> = matching
+        .iter()
+        .skip(pagination.offset() as usize)
+        .take(pagination.per_page as usize)
+        .map(|t| (*t).clone())
+        .collect();
+
+    (
+        [(header::ETAG, etag)],
+        pagination.headers_with_links(&uri, matching.len() as u64),
+        axum::Json::<Vec<tag::Model>>(page),
+    )
+        .into_response()
+}
+
+/// Strong `ETag` for a tag listing, hashed from each tag's ID, `updated_at`,
+/// and `deleted_at`.
+///
+/// Edits bump `updated_at` and soft deletes set `deleted_at`, so any change
+/// to the listed tags changes the tag. It covers the whole filtered set
+/// rather than one page, which also keeps the `Link` header honest.
+pub fn tag_list_etag(tags: &[&tag::Model]) -> HeaderValue {
+    let mut hasher = Sha256::new();
+    for tag in tags {
+        hasher.update(format!(
+            "{}|{:?}|{:?};",
+            tag.id, tag.updated_at, tag.deleted_at
+        ));
+    }
+    let digest = hasher.finalize();
+    let etag = format!("\"{}\"", hex::encode(&digest[..16]));
+    HeaderValue::from_str(&etag).expect("hex is a valid header value")
+}
+
+/// Whether `If-None-Match` lists `etag` (weakly compared) or is `*`.
+fn if_none_match(headers: &HeaderMap, etag: &HeaderValue) -> bool {
+    let etag = etag.to_str().unwrap_or_default();
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(|candidate| candidate.trim().trim_start_matches("W/"))
+        .any(|candidate| candidate == "*" || candidate == etag)
 }
 
 /// Fetch a single tag by its ID.
@@ -84,82 +459,738 @@ pub async fn get_tags() -> impl IntoResponse {
 /// - `200 OK` with tag object
 /// - `404 NOT_FOUND` if tag doesn't exist
 /// - `500 INTERNAL_SERVER_ERROR` on DB failure
-pub async fn get_tag_by_id(Path(id): Path<i32>) -> impl IntoResponse {
-    let db = connect().await;
-    match tag::Entity::find_by_id(id).one(&db).await {
+pub async fn get_tag_by_id(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> impl IntoResponse {
+    let db = &state.db;
+    match tag::Entity::find_by_id(id).one(db).await {
         Ok(Some(tag)) => axum::Json::<tag::Model>(tag).into_response(),
         Ok(None) => StatusCode::NOT_FOUND.into_response(),
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// Count the tickets carrying a tag.
+///
+/// Runs a single `COUNT` over `ticket_tag` instead of loading the tickets,
+/// which is all a badge in the UI needs.
+///
+/// # Path Parameters
+/// - `id`: ID of the tag
+///
+/// # Returns
+/// - `200 OK` with `{ "count": N }`
+/// - `404 NOT_FOUND` if tag doesn't exist
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+#[tracing::instrument(skip_all, fields(tag_id = id))]
+pub async fn count_tickets_for_tag(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> impl IntoResponse {
+    let db = &state.read_db;
+
+    match tag::Entity::find_by_id(id).one(db).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return DbError(e).into_response(),
+    }
+
+    let count = timed(
+        "tags.count_tickets",
+        ticket_tag::Entity::find()
+            .filter(ticket_tag::Column::TagId.eq(id))
+            .count(db),
+    )
+    .await;
+
+    match count {
+        Ok(count) => Json(json!({ "count": count })).into_response(),
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// A tag with a breakdown of the tickets carrying it.
+#[derive(Debug, Serialize)]
+pub struct TagSummary {
+    #[serde(flatten)]
+    pub tag: tag::Model,
+    pub total_tickets: i64,
+    /// Ticket count per status; tickets without one are counted under `"none"`.
+    pub by_status: BTreeMap<String, i64>,
+}
+
+/// Summarize a tag for a dashboard: the tag plus its tickets by status.
+///
+/// Counts come from one grouped query over `ticket` joined to `ticket_tag`.
+///
+/// # Path Parameters
+/// - `id`: ID of the tag
+///
+/// # Returns
+/// - `200 OK` with the tag's fields plus `{ "total_tickets", "by_status": { status: count } }`
+/// - `404 NOT_FOUND` if tag doesn't exist
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+#[tracing::instrument(skip_all, fields(tag_id = id))]
+pub async fn get_tag_summary(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> impl IntoResponse {
+    let db = &state.read_db;
+
+    let tag = match tag::Entity::find_by_id(id).one(db).await {
+        Ok(Some(tag)) => tag,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    // 📊 Tickets carrying the tag, grouped by status
+    let tagged = ticket::Entity::find()
+        .join(JoinType::InnerJoin, ticket::Relation::TicketTag.def())
+        .filter(ticket_tag::Column::TagId.eq(id));
+    let counts = match timed("tags.summary", count_by_status(tagged, db)).await {
+        Ok(counts) => counts,
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    let by_status: BTreeMap<String, i64> = counts
+        .into_iter()
+        .map(|c| (c.status.unwrap_or_else(|| "none".into()), c.count))
+        .collect();
+    Json(TagSummary {
+        tag,
+        total_tickets: by_status.values().sum(),
+        by_status,
+    })
+    .into_response()
+}
+
+/// Query parameters accepted by `get_tag_trend`.
+#[derive(Debug, Default, Deserialize)]
+pub struct TrendQuery {
+    /// `day` or `week` (default `week`).
+    pub bucket: Option<String>,
+    /// How many weeks back to look, `1..=52` (default `12`).
+    pub weeks: Option<i64>,
+}
+
+/// Longest window `get_tag_trend` will look back over, in weeks.
+pub const MAX_TREND_WEEKS: i64 = 52;
+
+/// One bucket of a tag's usage trend.
+#[derive(Debug, Serialize)]
+pub struct TrendPoint {
+    /// First day of the bucket (Monday for weekly buckets).
+    pub start: NaiveDate,
+    pub count: i64,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct BucketCount {
+    bucket: NaiveDateTime,
+    count: i64,
+}
+
+/// How often a tag was attached to tickets, per day or week.
+///
+/// Counts `attached` entries in the tag history, grouped in the database
+/// with `date_trunc`, over the last `weeks` weeks up to and including the
+/// current bucket. Every bucket in the window is listed, oldest first, with
+/// `0` where nothing happened. Weeks start on Monday (UTC).
+///
+/// # Path Parameters
+/// - `id`: ID of the tag
+///
+/// # Query Parameters
+/// - `bucket`: `day` or `week` (default `week`)
+/// - `weeks`: Window length in weeks, `1..=52` (default `12`)
+///
+/// # Returns
+/// - `200 OK` with `{ "tag_id", "bucket", "buckets": [{ "start", "count" }] }`
+/// - `404 NOT_FOUND` if tag doesn't exist
+/// - `422 UNPROCESSABLE_ENTITY` if `bucket` or `weeks` is out of range
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+#[tracing::instrument(skip_all, fields(tag_id = id))]
+pub async fn get_tag_trend(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Query(query): Query<TrendQuery>,
+) -> impl IntoResponse {
+    let db = &state.read_db;
+
+    let mut errors = ValidationErrors::default();
+    let (unit, step) = match query.bucket.as_deref().unwrap_or("week") {
+        "day" => ("day", Duration::days(1)),
+        "week" => ("week", Duration::weeks(1)),
+        _ => {
+            errors.add("bucket", "must be day or week");
+            ("week", Duration::weeks(1))
+        }
+    };
+    let weeks = query.weeks.unwrap_or(12);
+    if !(1..=MAX_TREND_WEEKS).contains(&weeks) {
+        errors.add("weeks", format!("must be between 1 and {MAX_TREND_WEEKS}"));
+    }
+    if !errors.is_empty() {
+        return errors.into_response();
+    }
+
+    match tag::Entity::find_by_id(id).one(db).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return DbError(e).into_response(),
+    }
+
+    // 🗓️ Window: the current bucket and enough before it to cover `weeks`
+    let today = Utc::now().date_naive();
+    let current = match unit {
+        "day" => today,
+        _ => today - Duration::days(today.weekday().num_days_from_monday().into()),
+    };
+    let buckets = Duration::weeks(weeks).num_days() / step.num_days();
+    let first = current - step * (buckets - 1) as i32;
+
+    // 📊 Attach events per bucket, truncated in the database
+    let counted = ticket_tag_event::Entity::find()
+        .select_only()
+        .column_as(
+            Expr::cust(format!("date_trunc('{unit}', \"created_at\")")),
+            "bucket",
+        )
+        .column_as(ticket_tag_event::Column::Id.count(), "count")
+        .filter(ticket_tag_event::Column::TagId.eq(id))
+        .filter(ticket_tag_event::Column::Action.eq(TagAction::Attached.as_str()))
+        .filter(ticket_tag_event::Column::CreatedAt.gte(first.and_time(NaiveTime::MIN)))
+        .group_by(Expr::cust("bucket"))
+        .into_model::<BucketCount>()
+        .all(db);
+    let counts: HashMap<NaiveDate, i64> = match timed("tags.trend", counted).await {
+        Ok(rows) => rows
+            .into_iter()
+            .map(|r| (r.bucket.date(), r.count))
+            .collect(),
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    // 🕳️ Fill the gaps so every bucket shows up
+    let points: Vec<TrendPoint> = (0..buckets)
+        .map(|i| {
+            let start = first + step * i as i32;
+            TrendPoint {
+                start,
+                count: counts.get(&start).copied().unwrap_or(0),
+            }
+        })
+        .collect();
+
+    Json(json!({ "tag_id": id, "bucket": unit, "buckets": points })).into_response()
+}
+
+/// A tag the caller has used, with how many of their tickets carry it.
+#[derive(Debug, Serialize)]
+pub struct UsedTag {
+    #[serde(flatten)]
+    pub tag: tag::Model,
+    pub uses: i64,
+}
+
+/// List the tags on the caller's tickets, most used first.
+///
+/// Feeds a personalized tag picker. Uses are counted over the tickets
+/// assigned to the caller, admins included; ties are broken by name.
+/// Soft-deleted tags are left out since they can't be attached anyway.
+///
+/// # Returns
+/// - `200 OK` with `[{ ...tag, "uses" }]`
+/// - `401 UNAUTHORIZED` if JWT is invalid
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+#[tracing::instrument(skip_all, fields(user_id = user.id))]
+pub async fn get_my_tags(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+) -> impl IntoResponse {
+    let db = &state.read_db;
+
+    // 📊 Uses per tag across the caller's tickets
+    let counts: Vec<(i32, i64)> = match timed(
+        "tags.mine",
+        ticket_tag::Entity::find()
+            .select_only()
+            .column(ticket_tag::Column::TagId)
+            .column_as(ticket_tag::Column::Id.count(), "uses")
+            .join(JoinType::InnerJoin, ticket_tag::Relation::Ticket.def())
+            .filter(ticket::Column::UserId.eq(Some(user.id)))
+            .group_by(ticket_tag::Column::TagId)
+            .into_tuple()
+            .all(db),
+    )
+    .await
+    {
+        Ok(counts) => counts,
+        Err(e) => return DbError(e).into_response(),
+    };
+    let uses: HashMap<i32, i64> = counts.into_iter().collect();
+
+    let tags = match tag::Entity::find()
+        .filter(tag::Column::Id.is_in(uses.keys().copied()))
+        .filter(tag::Column::DeletedAt.is_null())
+        .all(db)
+        .await
+    {
+        Ok(tags) => tags,
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    let mut used: Vec<UsedTag> = tags
+        .into_iter()
+        .map(|tag| UsedTag {
+            uses: uses[&tag.id],
+            tag,
+        })
+        .collect();
+    used.sort_by(|a, b| {
+        b.uses
+            .cmp(&a.uses)
+            .then_with(|| a.tag.name.cmp(&b.tag.name))
+    });
+    Json(used).into_response()
+}
+
+/// A tag on the management screen, with whether any ticket carries it.
+#[derive(Debug, Serialize, FromQueryResult)]
+pub struct ManagedTag {
+    #[serde(flatten)]
+    #[sea_orm(nested)]
+    pub tag: tag::Model,
+    #[sea_orm(skip)]
+    pub in_use: bool,
+    pub ticket_count: i64,
+}
+
+/// List every tag alphabetically with its usage, for pruning (admin only).
+///
+/// One query left-joins `tag` to `ticket_tag` and groups by tag, so unused
+/// tags come back with `"in_use": false` and `"ticket_count": 0`.
+/// Soft-deleted tags are listed too, marked by a non-null `deleted_at`.
+///
+/// # Returns
+/// - `200 OK` with `[{ ...tag, "in_use", "ticket_count" }]`, ordered by name
+/// - `401 UNAUTHORIZED` if token is missing/invalid
+/// - `403 FORBIDDEN` if the caller is not an admin
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+#[tracing::instrument(skip_all)]
+pub async fn get_tag_management(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+) -> impl IntoResponse {
+    if user.role != Role::Admin.as_str() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    let rows = timed(
+        "tags.manage",
+        tag::Entity::find()
+            .column_as(ticket_tag::Column::Id.count(), "ticket_count")
+            .join(JoinType::LeftJoin, tag::Relation::TicketTag.def())
+            .group_by(tag::Column::Id)
+            .order_by_asc(tag::Column::Name)
+            .order_by_asc(tag::Column::Id)
+            .into_model::<ManagedTag>()
+            .all(&state.read_db),
+    )
+    .await;
+
+    match rows {
+        Ok(rows) => {
+            let tags: Vec<ManagedTag> = rows
+                .into_iter()
+                .map(|row| ManagedTag {
+                    in_use: row.ticket_count > 0,
+                    ..row
+                })
+                .collect();
+            Json(tags).into_response()
+        }
+        Err(e) => DbError(e).into_response(),
     }
 }
 
 /// Update an existing tag by its ID.
 ///
-/// Accepts a partial update payload. Only the tag name is currently updatable.
+/// Accepts a partial update payload (served for both `PUT` and `PATCH`).
+/// Every provided field is applied in one write, and `updated_at` is bumped
+/// once if any of them differs from the stored value; a payload that
+/// changes nothing returns the tag untouched without writing. The
+/// `X-Modified` header says which of the two happened. Renaming a tag to a
+/// name already taken, ignoring case, is rejected (per owner with
+/// `tag_ownership` on, as in [`create_tag`]); changing only the case
+/// of its own name is fine. With `tag_ownership` on, the caller must own the
+/// tag or be an admin.
 ///
 /// # Path Parameters
 /// - `id`: ID of the tag to update
 ///
 /// # JSON Payload
-/// - `name` (optional): New name for the tag
+/// - `name` (optional): New name for the tag, normalized like on create
+/// - `description` (optional): New description for the tag
 ///
 /// # Returns
-/// - `200 OK` with updated tag
+/// - `200 OK` with updated tag and `X-Modified: true`, or the stored tag and
+///   `X-Modified: false` if nothing differed
 /// - `400 BAD_REQUEST` if no updatable fields are provided
+/// - `400 BAD_REQUEST` with `{ "error": "invalid_tag_name", "name" }` if the new name is blank or longer than [`MAX_TAG_NAME_LEN`]
+/// - `401 UNAUTHORIZED` / `403 FORBIDDEN` if ownership rules deny the change
 /// - `404 NOT_FOUND` if tag doesn't exist
+/// - `409 CONFLICT` if another tag in the same namespace already has the requested name
 /// - `500 INTERNAL_SERVER_ERROR` on DB failure
 pub async fn update_tag_by_id(
+    State(state): State<AppState>,
     Path(id): Path<i32>,
+    auth: Option<AuthUser>,
     Json(payload): Json<UpdateTag>,
 ) -> impl IntoResponse {
-    let db = connect().await;
+    let db = &state.db;
+
+    // 🚫 No updates provided
+    if payload.name.is_none() && payload.description.is_none() {
+        return StatusCode::BAD_REQUEST.into_response();
+    }
+
+    // 🧪 Same name rules as create
+    let new_name = match payload.name.as_deref() {
+        Some(raw) => match valid_tag_name(raw) {
+            Some(name) => Some(name),
+            None => return invalid_tag_name(raw),
+        },
+        None => None,
+    };
 
     // 🔍 Fetch the existing tag
-    match tag::Entity::find_by_id(id).one(&db).await {
-        Ok(Some(existing)) => {
-            let mut active = existing.into_active_model();
-
-            // 📝 Apply update if field is provided
-            if let Some(new_name) = payload.name.clone() {
-                active.name = Set(new_name);
-                active.updated_at = Set(Some(Local::now().naive_local()));
-            } else {
-                return StatusCode::BAD_REQUEST.into_response(); // 🚫 No updates provided
-            }
+    let existing = match tag::Entity::find_by_id(id).one(db).await {
+        Ok(Some(existing)) => existing,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return DbError(e).into_response(),
+    };
 
-            // 💾 Save updated tag
-            match active.update(&db).await {
-                Ok(_) => match tag::Entity::find_by_id(id).one(&db).await {
-                    Ok(Some(updated_tag)) => Json(updated_tag).into_response(),
-                    _ => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
-                },
-                Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
-            }
+    // 🛡️ Ownership rules, when enabled
+    if let Some(status) = tag_write_denied(&state, auth.as_ref().map(|a| &a.0), &existing) {
+        return status.into_response();
+    }
+
+    // ⚔️ Refuse to take a name another tag in the namespace already uses
+    if let Some(new_name) = new_name.as_deref()
+        && new_name != existing.name
+    {
+        let per_owner = state.config.tag_ownership;
+        match name_taken(db, new_name, existing.owner_id, per_owner, Some(id)).await {
+            Ok(true) => return StatusCode::CONFLICT.into_response(),
+            Ok(false) => {}
+            Err(e) => return DbError(e).into_response(),
         }
-        Ok(None) => StatusCode::NOT_FOUND.into_response(),
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+
+    // 📝 Apply every provided field that actually differs
+    let mut changed = false;
+    let mut active = existing.clone().into_active_model();
+    if let Some(new_name) = new_name
+        && new_name != existing.name
+    {
+        active.name = Set(new_name);
+        changed = true;
+    }
+    if let Some(new_description) = payload.description
+        && existing.description.as_ref() != Some(&new_description)
+    {
+        active.description = Set(Some(new_description));
+        changed = true;
+    }
+
+    // 💤 Nothing differs, so nothing is written
+    if !changed {
+        return ([("x-modified", "false")], Json(existing)).into_response();
+    }
+    active.updated_at = Set(Some(Utc::now().naive_utc()));
+
+    // 💾 Save updated tag
+    match active.update(db).await {
+        Ok(updated_tag) => {
+            state.tag_cache.invalidate().await;
+            ([("x-modified", "true")], Json(updated_tag)).into_response()
+        }
+        Err(e) if is_name_clash(&e) => StatusCode::CONFLICT.into_response(),
+        Err(e) => DbError(e).into_response(),
     }
 }
 
-/// Delete a tag by its ID.
+/// Soft-delete a tag by its ID.
+///
+/// Sets `deleted_at` so the tag drops out of `GET /tags` while tickets
+/// that carry it keep resolving it. Deleting an already deleted tag keeps
+/// the original timestamp. With `tag_ownership` on, the caller must own the
+/// tag or be an admin.
 ///
 /// # Path Parameters
 /// - `id`: ID of the tag to delete
 ///
 /// # Returns
 /// - `204 NO_CONTENT` on success
+/// - `401 UNAUTHORIZED` / `403 FORBIDDEN` if ownership rules deny the delete
 /// - `404 NOT_FOUND` if tag doesn't exist
 /// - `500 INTERNAL_SERVER_ERROR` on DB failure
-pub async fn delete_tag_by_id(Path(id): Path<i32>) -> impl IntoResponse {
-    let db = connect().await;
+pub async fn delete_tag_by_id(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    auth: Option<AuthUser>,
+) -> impl IntoResponse {
+    let db = &state.db;
 
-    // 🔍 Fetch and delete tag if it exists
-    match tag::Entity::find_by_id(id).one(&db).await {
-        Ok(Some(tag)) => match tag.delete(&db).await {
-            Ok(_) => StatusCode::NO_CONTENT,
-            Err(_) => StatusCode::INTERNAL_SERVER_ERROR,
-        },
-        Ok(None) => StatusCode::NOT_FOUND,
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    // 🔍 Fetch and mark tag as deleted if it exists
+    let tag = match tag::Entity::find_by_id(id).one(db).await {
+        Ok(Some(tag)) => tag,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    // 🛡️ Ownership rules, when enabled
+    if let Some(status) = tag_write_denied(&state, auth.as_ref().map(|a| &a.0), &tag) {
+        return status.into_response();
+    }
+    if tag.deleted_at.is_some() {
+        return StatusCode::NO_CONTENT.into_response();
+    }
+
+    let mut active = tag.into_active_model();
+    active.deleted_at = Set(Some(Utc::now().naive_utc()));
+    match active.update(db).await {
+        Ok(_) => {
+            state.tag_cache.invalidate().await;
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// Restore a soft-deleted tag (admin only).
+///
+/// Restoring a tag that isn't deleted is a no-op.
+///
+/// # Returns
+/// - `200 OK` with the restored tag
+/// - `401 UNAUTHORIZED` if token is missing/invalid
+/// - `403 FORBIDDEN` if the caller is not an admin
+/// - `404 NOT_FOUND` if tag doesn't exist
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn restore_tag(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    AuthUser(user): AuthUser,
+) -> impl IntoResponse {
+    let db = &state.db;
+
+    if user.role != "admin" {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    let tag = match tag::Entity::find_by_id(id).one(db).await {
+        Ok(Some(t)) => t,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    if tag.deleted_at.is_none() {
+        return Json(tag).into_response();
+    }
+
+    let mut active = tag.into_active_model();
+    active.deleted_at = Set(None);
+    active.updated_at = Set(Some(Utc::now().naive_utc()));
+
+    match active.update(db).await {
+        Ok(tag) => {
+            state.tag_cache.invalidate().await;
+            Json(tag).into_response()
+        }
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// Subscribe the caller to a tag.
+///
+/// Subscribers are emailed when the tag is attached to a ticket they can
+/// see. Subscribing twice is a no-op.
+///
+/// # Returns
+/// - `201 CREATED` with the new subscription
+/// - `200 OK` with the existing subscription
+/// - `401 UNAUTHORIZED` if token is missing/invalid
+/// - `404 NOT_FOUND` if tag doesn't exist or is deleted
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn subscribe_to_tag(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    AuthUser(user): AuthUser,
+) -> impl IntoResponse {
+    let db = &state.db;
+
+    match tag::Entity::find_by_id(id).one(db).await {
+        Ok(Some(tag)) if tag.deleted_at.is_none() => {}
+        Ok(_) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return DbError(e).into_response(),
     }
-}
\ No newline at end of file
+
+    // 🔍 Already subscribed?
+    let existing = tag_subscription::Entity::find()
+        .filter(tag_subscription::Column::UserId.eq(user.id))
+        .filter(tag_subscription::Column::TagId.eq(id))
+        .one(db)
+        .await;
+    match existing {
+        Ok(Some(subscription)) => return (StatusCode::OK, Json(subscription)).into_response(),
+        Ok(None) => {}
+        Err(e) => return DbError(e).into_response(),
+    }
+
+    let subscription = tag_subscription::ActiveModel {
+        user_id: Set(user.id),
+        tag_id: Set(id),
+        created_at: Set(Utc::now().naive_utc()),
+        ..Default::default()
+    };
+    match subscription.insert(db).await {
+        Ok(subscription) => (StatusCode::CREATED, Json(subscription)).into_response(),
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// Unsubscribe the caller from a tag.
+///
+/// Unsubscribing from a tag one doesn't follow is a no-op.
+///
+/// # Returns
+/// - `204 NO_CONTENT` on success
+/// - `401 UNAUTHORIZED` if token is missing/invalid
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn unsubscribe_from_tag(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    AuthUser(user): AuthUser,
+) -> impl IntoResponse {
+    let deleted = tag_subscription::Entity::delete_many()
+        .filter(tag_subscription::Column::UserId.eq(user.id))
+        .filter(tag_subscription::Column::TagId.eq(id))
+        .exec(&state.db)
+        .await;
+    match deleted {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// Merge a duplicate tag into another one (admin only).
+///
+/// Every `ticket_tag` row pointing at `from_id` is repointed to `into_id`.
+/// Tickets that already carry both tags keep a single relation. The `from`
+/// tag is deleted afterwards. All steps run in one transaction.
+///
+/// # JSON Payload
+/// - `from_id`: Tag to merge away (deleted on success)
+/// - `into_id`: Tag that survives the merge
+///
+/// # Returns
+/// - `200 OK` with a merge summary
+/// - `400 BAD_REQUEST` if `from_id == into_id`
+/// - `401 UNAUTHORIZED` if token is missing/invalid
+/// - `403 FORBIDDEN` if the caller is not an admin
+/// - `404 NOT_FOUND` if either tag doesn't exist
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn merge_tags(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Json(payload): Json<MergeTags>,
+) -> impl IntoResponse {
+    let db = &state.db;
+
+    if user.role != "admin" {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    // 🚫 Merging a tag into itself is meaningless
+    if payload.from_id == payload.into_id {
+        return StatusCode::BAD_REQUEST.into_response();
+    }
+
+    // 🔍 Both tags must exist
+    let from_tag = match tag::Entity::find_by_id(payload.from_id).one(db).await {
+        Ok(Some(t)) => t,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return DbError(e).into_response(),
+    };
+    match tag::Entity::find_by_id(payload.into_id).one(db).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return DbError(e).into_response(),
+    }
+
+    let txn = match db.begin().await {
+        Ok(txn) => txn,
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    // 🔀 Repoint relations and drop the source tag; dropping `txn` on error rolls back
+    let (moved, deduped) = match merge_relations(&txn, from_tag, payload.into_id).await {
+        Ok(counts) => counts,
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    if let Err(e) = txn.commit().await {
+        return DbError(e).into_response();
+    }
+    state.tag_cache.invalidate().await;
+
+    Json(json!({
+        "merged": true,
+        "from_id": payload.from_id,
+        "into_id": payload.into_id,
+        "relations_moved": moved,
+        "duplicates_removed": deduped
+    }))
+    .into_response()
+}
+
+/// Repoint `from_tag`'s relations to `into_id` and delete `from_tag`.
+///
+/// Returns `(relations_moved, duplicates_removed)`.
+async fn merge_relations(
+    txn: &DatabaseTransaction,
+    from_tag: tag::Model,
+    into_id: i32,
+) -> Result<(u64, u64), DbErr> {
+    // 🎯 Tickets that already carry the surviving tag
+    let already_tagged: Vec<i32> = ticket_tag::Entity::find()
+        .select_only()
+        .column(ticket_tag::Column::TicketId)
+        .filter(ticket_tag::Column::TagId.eq(into_id))
+        .into_tuple()
+        .all(txn)
+        .await?;
+
+    // 🧹 Drop the relations that would become duplicates
+    let deduped = ticket_tag::Entity::delete_many()
+        .filter(ticket_tag::Column::TagId.eq(from_tag.id))
+        .filter(ticket_tag::Column::TicketId.is_in(already_tagged))
+        .exec(txn)
+        .await?
+        .rows_affected;
+
+    // 🔗 Move the rest over
+    let moved = ticket_tag::Entity::update_many()
+        .col_expr(ticket_tag::Column::TagId, Expr::value(into_id))
+        .filter(ticket_tag::Column::TagId.eq(from_tag.id))
+        .exec(txn)
+        .await?
+        .rows_affected;
+
+    from_tag.delete(txn).await?;
+
+    Ok((moved, deduped))
+}
@@ -1,18 +1,46 @@
+use std::{
+    collections::{HashMap, HashSet},
+    convert::Infallible,
+};
+
 use axum::{
-    extract::{Json, Path},
-    http::StatusCode,
-    response::IntoResponse,
+    extract::{Json, OriginalUri, Query, State},
+    http::{StatusCode, header},
+    response::{
+        IntoResponse, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+};
+use chrono::{DateTime, Duration, Utc};
+use futures::stream;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DbErr,
+    EntityTrait, FromQueryResult, IntoActiveModel, JoinType, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, RelationTrait, Select, Set, SqlErr,
+    sea_query::{self, Expr, IntoCondition, SelectStatement},
 };
-use axum_extra::extract::TypedHeader;
-use chrono::Local;
-use headers::{Authorization, authorization::Bearer};
-use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter, Set};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+use tokio::sync::broadcast::error::RecvError;
 
 use crate::{
-    db::db::connect,
-    models::{ticket, user},
-    utils::jwt::extract_claims,
+    db::instrument::timed,
+    events::{self, TicketEventKind},
+    handlers::relations::record_tag_event,
+    middleware::{auth::AuthUser, error::DbError, transaction::Tx},
+    models::{tag, ticket, ticket_tag, ticket_tag_event::TagAction, user},
+    notifications::notify_tag_subscribers,
+    state::AppState,
+    utils::{
+        bulk::{BulkItem, BulkResults},
+        cursor::Cursor,
+        date_range::CreatedRange,
+        normalize,
+        pagination::Pagination,
+        path::Path,
+        time,
+        validation::ValidationErrors,
+    },
 };
 
 /// Payload for creating a new ticket.
@@ -21,6 +49,187 @@ pub struct CreateTicket {
     pub title: String,
     pub description: Option<String>,
     pub status: Option<String>,
+    #[serde(default)]
+    pub tag_ids: Vec<i32>,
+}
+
+/// What to do with a description longer than `max_description_len`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnOverflow {
+    /// Refuse the write with `400 BAD_REQUEST`.
+    #[default]
+    Reject,
+    /// Keep the first `max_description_len` characters.
+    Truncate,
+}
+
+/// Query parameters accepted by `create_ticket` and `update_ticket_by_id`.
+#[derive(Debug, Default, Deserialize)]
+pub struct WriteTicketQuery {
+    #[serde(default)]
+    pub on_overflow: OnOverflow,
+}
+
+/// A written ticket, flagged when its description was cut to fit.
+#[derive(Debug, Serialize)]
+pub struct SavedTicket {
+    #[serde(flatten)]
+    pub ticket: ticket::Model,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub description_truncated: bool,
+}
+
+/// Apply the description length limit.
+///
+/// Returns the description to store and whether it was truncated, or `None`
+/// when it is too long and `mode` is [`OnOverflow::Reject`].
+pub(crate) fn fit_description(
+    description: String,
+    max_len: usize,
+    mode: OnOverflow,
+) -> Option<(String, bool)> {
+    if description.chars().count() <= max_len {
+        return Some((description, false));
+    }
+    match mode {
+        OnOverflow::Reject => None,
+        OnOverflow::Truncate => Some((description.chars().take(max_len).collect(), true)),
+    }
+}
+
+pub(crate) fn description_too_long(max_len: usize) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(serde_json::json!({ "error": "description_too_long", "max_len": max_len })),
+    )
+        .into_response()
+}
+
+/// Whether `user` may be assigned another new ticket under
+/// `max_tickets_per_user`.
+///
+/// Returns the response to reject with, or `None` to allow. Admins have
+/// no quota.
+pub(crate) async fn ticket_quota_denied<C: ConnectionTrait>(
+    state: &AppState,
+    db: &C,
+    user: &user::Model,
+) -> Option<Response> {
+    let max = state.config.max_tickets_per_user?;
+    if user.role == "admin" {
+        return None;
+    }
+    let held = ticket::Entity::find()
+        .filter(ticket::Column::UserId.eq(Some(user.id)))
+        .count(db)
+        .await;
+    match held {
+        Ok(held) if held >= max => Some(
+            (
+                StatusCode::FORBIDDEN,
+                Json(serde_json::json!({ "error": "ticket_quota_exceeded", "max": max })),
+            )
+                .into_response(),
+        ),
+        Ok(_) => None,
+        Err(e) => Some(DbError(e).into_response()),
+    }
+}
+
+/// Which subset of tickets `get_tickets` should return.
+///
+/// - `assigned_to_me`: Tickets whose `user_id` is the caller.
+/// - `created_by_me`: Tickets whose `created_by` is the caller, even if
+///   they have since been reassigned to someone else.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TicketScope {
+    AssignedToMe,
+    CreatedByMe,
+}
+
+/// Ticket columns a client may request through `?fields=`.
+pub const TICKET_FIELDS: &[(&str, ticket::Column)] = &[
+    ("id", ticket::Column::Id),
+    ("title", ticket::Column::Title),
+    ("description", ticket::Column::Description),
+    ("status", ticket::Column::Status),
+    ("user_id", ticket::Column::UserId),
+    ("created_by", ticket::Column::CreatedBy),
+    ("created_at", ticket::Column::CreatedAt),
+    ("updated_at", ticket::Column::UpdatedAt),
+];
+
+/// Query parameters accepted by `get_tickets`.
+///
+/// - `include`: Comma-separated related data to embed; only `tags` is supported.
+/// - `fields`: Comma-separated columns to return, from [`TICKET_FIELDS`].
+/// - `untagged`: Only tickets without any tags.
+/// - `unassigned`: Only tickets with no assignee (admins only).
+/// - `cursor`, `limit`: Keyset pagination; either one switches it on, and an
+///   empty `cursor` starts from the beginning.
+#[derive(Debug, Default, Deserialize)]
+pub struct TicketListQuery {
+    pub scope: Option<TicketScope>,
+    pub include: Option<String>,
+    pub fields: Option<String>,
+    #[serde(default)]
+    pub untagged: bool,
+    #[serde(default)]
+    pub unassigned: bool,
+    pub cursor: Option<String>,
+    pub limit: Option<u64>,
+}
+
+impl TicketListQuery {
+    /// Whether the caller asked for cursor rather than page pagination.
+    pub fn uses_cursor(&self) -> bool {
+        self.cursor.is_some() || self.limit.is_some()
+    }
+
+    /// The columns named by `fields`, in request order without repeats, or
+    /// `None` when every column is wanted.
+    ///
+    /// Fails on an unknown or empty field list.
+    pub fn fields(&self) -> Result<Option<Vec<ticket::Column>>, String> {
+        let Some(raw) = self.fields.as_deref() else {
+            return Ok(None);
+        };
+
+        let mut selected: Vec<&(&str, ticket::Column)> = Vec::new();
+        for name in raw.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            let field = TICKET_FIELDS
+                .iter()
+                .find(|(field, _)| *field == name)
+                .ok_or_else(|| format!("unknown field '{name}'"))?;
+            if !selected.iter().any(|(seen, _)| *seen == name) {
+                selected.push(field);
+            }
+        }
+
+        if selected.is_empty() {
+            return Err("fields must name at least one column".into());
+        }
+        Ok(Some(
+            selected.into_iter().map(|(_, column)| *column).collect(),
+        ))
+    }
+
+    /// Whether `include` asks for each ticket's tags.
+    pub fn includes_tags(&self) -> bool {
+        self.include
+            .as_deref()
+            .is_some_and(|include| include.split(',').any(|part| part.trim() == "tags"))
+    }
+}
+
+/// A ticket with its tags embedded, returned for `?include=tags`.
+#[derive(Debug, Serialize)]
+pub struct TicketWithTags {
+    #[serde(flatten)]
+    pub ticket: ticket::Model,
+    pub tags: Vec<tag::Model>,
 }
 
 /// Create a new ticket assigned to the authenticated user.
@@ -29,55 +238,137 @@ pub struct CreateTicket {
 /// - `Authorization: Bearer <token>`
 ///
 /// # Request Body
-/// - `title`: Title of the ticket (required)
+/// - `title`: Title of the ticket (required, trimmed)
 /// - `description`: Optional description
-/// - `status`: Optional status (defaults to `"open"`)
+/// - `status`: Optional status (defaults to `"open"`, trimmed)
+/// - `tag_ids`: Optional tags to attach; repeats are ignored
+///
+/// # Query Parameters
+/// - `on_overflow=reject|truncate`: Handling of a description longer than
+///   `max_description_len` (default `reject`)
+///
+/// The ticket, its `ticket_tag` rows and an `attached` tag history entry
+/// per tag are written in the request's [`Tx`], so a failed relation insert
+/// leaves no ticket behind.
+///
+/// Once that commits, subscribers of the attached tags are emailed (see
+/// [`notify_tag_subscribers`]) and a `Created` event is published; a
+/// rolled-back create announces nothing.
+///
+/// With `max_tickets_per_user` set, a non-admin already assigned that many
+/// tickets can't create another until some are deleted or reassigned.
 ///
 /// # Returns
-/// - `200 OK` with the created ticket
+/// - `200 OK` with the created ticket, plus `"description_truncated": true` if it was cut
+/// - `400 BAD_REQUEST` with `{ "error": "description_too_long", "max_len" }` when rejecting an overflow
+/// - `400 BAD_REQUEST` with `{ "error": "unknown_tag" }` if a tag ID doesn't exist
 /// - `401 UNAUTHORIZED` if JWT is invalid
+/// - `403 FORBIDDEN` with `{ "error": "ticket_quota_exceeded", "max" }` once a non-admin is at the quota
+/// - `422 UNPROCESSABLE_ENTITY` with `{ "errors": { field: message } }` for a blank title or status
 /// - `500 INTERNAL_SERVER_ERROR` on DB failure
 pub async fn create_ticket(
-    TypedHeader(Authorization(bearer)): TypedHeader<Authorization<Bearer>>,
-    Json(payload): Json<CreateTicket>,
+    State(state): State<AppState>,
+    AuthUser(user_record): AuthUser,
+    tx: Tx,
+    Query(query): Query<WriteTicketQuery>,
+    Json(mut payload): Json<CreateTicket>,
 ) -> impl IntoResponse {
-    let claims = match extract_claims(bearer.token()) {
-        Ok(c) => c,
-        Err(_) => return StatusCode::UNAUTHORIZED.into_response(),
+    // 🧹 Check every field before rejecting, so all problems are reported
+    let mut errors = ValidationErrors::default();
+    let title = errors.require(
+        "title",
+        normalize::trimmed(&payload.title),
+        "must not be blank",
+    );
+    let status = match payload.status.as_deref() {
+        Some(raw) => errors.require("status", normalize::trimmed(raw), "must not be blank"),
+        None => Some("open".to_string()),
+    };
+    let (Some(title), Some(status)) = (title, status) else {
+        return errors.into_response();
     };
 
-    let db = connect().await;
-
-    // 🎯 Get user from JWT claim
-    let user_record = match user::Entity::find()
-        .filter(user::Column::Email.eq(claims.sub.clone()))
-        .one(&db)
-        .await
-        .unwrap()
-    {
-        Some(u) => u,
-        None => return StatusCode::UNAUTHORIZED.into_response(),
+    // ✂️ Enforce the description length limit
+    let max_len = state.config.max_description_len;
+    let (description, description_truncated) = match payload.description {
+        Some(d) => match fit_description(d, max_len, query.on_overflow) {
+            Some((d, truncated)) => (Some(d), truncated),
+            None => return description_too_long(max_len),
+        },
+        None => (None, false),
     };
 
+    // 🎟️ Non-admins may hold only so many tickets
+    if let Some(denied) = ticket_quota_denied(&state, &*tx, &user_record).await {
+        return denied;
+    }
+
     // 🕒 Timestamp now
-    let now = Local::now().naive_local();
+    let now = Utc::now().naive_utc();
 
     // 📦 Build ticket model
     let new_ticket = ticket::ActiveModel {
-        title: Set(payload.title),
-        description: Set(payload.description),
-        status: Set(Some(payload.status.unwrap_or("open".into()))),
+        title: Set(title),
+        description: Set(description),
+        status: Set(Some(status)),
         user_id: Set(Some(user_record.id)),
+        created_by: Set(Some(user_record.id)),
         created_at: Set(Some(now)),
         updated_at: Set(Some(now)),
         ..Default::default()
     };
 
-    // 💾 Insert into DB
-    match new_ticket.insert(&db).await {
-        Ok(saved_ticket) => axum::Json(saved_ticket).into_response(),
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    // 🧮 A tag listed twice is attached once
+    let mut seen = HashSet::new();
+    payload.tag_ids.retain(|id| seen.insert(*id));
+
+    // 💾 Insert ticket, relations and their history in the request's transaction; an error rolls all back
+    let saved_ticket = match new_ticket.insert(&*tx).await {
+        Ok(saved) => saved,
+        Err(e) => return DbError(e).into_response(),
+    };
+    for &tag_id in &payload.tag_ids {
+        let relation = ticket_tag::ActiveModel {
+            ticket_id: Set(saved_ticket.id),
+            tag_id: Set(tag_id),
+            ..Default::default()
+        };
+        match relation.insert(&*tx).await {
+            Ok(_) => {}
+            Err(e) if matches!(e.sql_err(), Some(SqlErr::ForeignKeyConstraintViolation(_))) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({ "error": "unknown_tag" })),
+                )
+                    .into_response();
+            }
+            Err(e) => return DbError(e).into_response(),
+        }
+        let attached = TagAction::Attached;
+        if let Err(e) =
+            record_tag_event(&*tx, saved_ticket.id, tag_id, user_record.id, attached).await
+        {
+            return DbError(e).into_response();
+        }
     }
+
+    // 📧 Once committed, let the tags' subscribers and stream listeners know
+    let ticket = saved_ticket.clone();
+    let tag_ids = payload.tag_ids;
+    let actor_id = user_record.id;
+    tx.after_commit(async move {
+        if let Err(e) = notify_tag_subscribers(&state, &state.db, &ticket, &tag_ids, actor_id).await
+        {
+            tracing::warn!(ticket_id = ticket.id, error = %e, "tag subscribers not notified");
+        }
+        events::publish(&state.events, TicketEventKind::Created, ticket);
+    });
+
+    axum::Json(SavedTicket {
+        ticket: saved_ticket,
+        description_truncated,
+    })
+    .into_response()
 }
 
 /// Get all tickets available to the authenticated user.
@@ -85,43 +376,425 @@ pub async fn create_ticket(
 /// - Admins receive **all** tickets.
 /// - Regular users receive only **their own** tickets.
 ///
+/// Queries go to [`AppState::read_db`], the replica when one is configured.
+///
+/// # Query Parameters
+/// - `scope=assigned_to_me`: Only tickets assigned to the caller
+/// - `scope=created_by_me`: Only tickets created by the caller
+/// - `include=tags`: Embed each ticket's tags as `"tags": [...]`
+/// - `fields=id,title,...`: Return only these columns (see [`TICKET_FIELDS`]);
+///   can't be combined with `include=tags`
+/// - `untagged=true`: Only tickets with no `ticket_tag` rows
+/// - `unassigned=true`: Only tickets with no `user_id`, for triage (admins only)
+/// - `page`, `per_page`: See [`Pagination`]; tickets are ordered by ID
+/// - `cursor`, `limit`: Keyset pagination ordered by `(created_at, id)`, see
+///   [`Cursor`]; stable while tickets are added between page loads. Tickets
+///   without `created_at` are left out, and it can't be combined with `fields`
+/// - `created_after`, `created_before`: Inclusive RFC 3339 bounds, see [`CreatedRange`]
+///
 /// # Returns
-/// - `200 OK` with ticket list
+/// - `200 OK` with ticket list, `X-Page`/`X-Per-Page` headers, and a `Link`
+///   header with `next`/`prev` pages (see [`Pagination::headers_with_links`])
+/// - `200 OK` with `{ "tickets": [...], "next_cursor" }` in cursor mode;
+///   `next_cursor` is `null` on the last page
+/// - `400 BAD_REQUEST` if the created range is invalid or inverted
+/// - `400 BAD_REQUEST` with `{ "error": "invalid_cursor" }` for a cursor this server didn't issue
+/// - `400 BAD_REQUEST` with `{ "error": "invalid_fields", "details" }` for a bad `fields` list
 /// - `401 UNAUTHORIZED` if JWT is invalid
+/// - `403 FORBIDDEN` if a non-admin asks for `unassigned=true`
 /// - `500 INTERNAL_SERVER_ERROR` on DB failure
+#[tracing::instrument(skip_all)]
 pub async fn get_tickets(
-    TypedHeader(Authorization(bearer)): TypedHeader<Authorization<Bearer>>,
+    State(state): State<AppState>,
+    OriginalUri(uri): OriginalUri,
+    AuthUser(user): AuthUser,
+    Query(query): Query<TicketListQuery>,
+    pagination: Pagination,
+    created: CreatedRange,
 ) -> impl IntoResponse {
-    let db = connect().await;
+    let db = &state.read_db;
 
-    let claims = match extract_claims(bearer.token()) {
-        Ok(c) => c,
-        Err(_) => return StatusCode::UNAUTHORIZED.into_response(),
+    // 🔎 Validate the projection before touching the database
+    let fields = match query.fields() {
+        Ok(_) if query.fields.is_some() && query.includes_tags() => {
+            return invalid_fields("fields cannot be combined with include=tags".into());
+        }
+        Ok(_) if query.fields.is_some() && query.uses_cursor() => {
+            return invalid_fields("fields cannot be combined with cursor pagination".into());
+        }
+        Ok(fields) => fields,
+        Err(details) => return invalid_fields(details),
     };
 
-    let user = match user::Entity::find()
-        .filter(user::Column::Email.eq(claims.sub.clone()))
-        .one(&db)
-        .await
-        .unwrap()
+    // 🚫 Agents only ever see their own tickets, so unassigned ones are admin territory
+    if query.unassigned && user.role != "admin" {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    // 🧠 An explicit scope wins; otherwise admins get all tickets, others only their own
+    let select = match query.scope {
+        Some(TicketScope::AssignedToMe) => {
+            ticket::Entity::find().filter(ticket::Column::UserId.eq(Some(user.id)))
+        }
+        Some(TicketScope::CreatedByMe) => {
+            ticket::Entity::find().filter(ticket::Column::CreatedBy.eq(Some(user.id)))
+        }
+        None => visible_tickets(&user),
+    };
+
+    // 🏷️ Untriaged: no ticket_tag row points at the ticket
+    let select = if query.untagged {
+        select.filter(Expr::exists(has_any_tag()).not())
+    } else {
+        select
+    };
+
+    // 📭 Awaiting triage: nobody is assigned
+    let select = if query.unassigned {
+        select.filter(ticket::Column::UserId.is_null())
+    } else {
+        select
+    };
+
+    let select = created.apply(select, ticket::Column::CreatedAt);
+    if query.uses_cursor() {
+        return cursor_page(&state, select, &query).await;
+    }
+
+    // 🔗 The total drives the `Link` header's next/prev pages
+    let total = match timed("tickets.count", select.clone().count(db)).await {
+        Ok(total) => total,
+        Err(e) => return DbError(e).into_response(),
+    };
+    let headers = pagination.headers_with_links(&uri, total);
+
+    let select = select
+        .order_by_asc(ticket::Column::Id)
+        .offset(pagination.offset())
+        .limit(pagination.per_page);
+
+    // ✂️ Sparse fieldsets: select only the requested columns into JSON objects
+    if let Some(fields) = fields {
+        let projected = fields
+            .into_iter()
+            .fold(select.select_only(), |select, column| select.column(column));
+        return match timed("tickets.list_fields", projected.into_json().all(db)).await {
+            Ok(mut rows) => {
+                rows.iter_mut().for_each(format_timestamps);
+                (headers, Json(rows)).into_response()
+            }
+            Err(e) => DbError(e).into_response(),
+        };
+    }
+
+    let list = match timed("tickets.list", select.all(db)).await {
+        Ok(list) => list,
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    if !query.includes_tags() {
+        return (headers, Json(list)).into_response();
+    }
+
+    match timed("tickets.list_tags", with_tags(db, list)).await {
+        Ok(list) => (headers, Json(list)).into_response(),
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// One page of `get_tickets` in cursor mode: the tickets after `query.cursor`
+/// in `(created_at, id)` order, plus the cursor for the page after them.
+async fn cursor_page(
+    state: &AppState,
+    select: Select<ticket::Entity>,
+    query: &TicketListQuery,
+) -> Response {
+    let db = &state.read_db;
+
+    // 🧭 Resume after the last ticket the client saw
+    let after = match query.cursor.as_deref().filter(|raw| !raw.trim().is_empty()) {
+        Some(raw) => match Cursor::decode(raw) {
+            Some(cursor) => Some(cursor),
+            None => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({ "error": "invalid_cursor" })),
+                )
+                    .into_response();
+            }
+        },
+        None => None,
+    };
+    let limit = Pagination::resolve(None, query.limit, &state.config).per_page;
+
+    let mut select = select.filter(ticket::Column::CreatedAt.is_not_null());
+    if let Some(after) = after {
+        select = select.filter(
+            Condition::any()
+                .add(ticket::Column::CreatedAt.gt(after.created_at))
+                .add(
+                    Condition::all()
+                        .add(ticket::Column::CreatedAt.eq(after.created_at))
+                        .add(ticket::Column::Id.gt(after.id)),
+                ),
+        );
+    }
+
+    // 📏 Fetch one extra row to learn whether another page follows
+    let mut list = match timed(
+        "tickets.list_cursor",
+        select
+            .order_by_asc(ticket::Column::CreatedAt)
+            .order_by_asc(ticket::Column::Id)
+            .limit(limit + 1)
+            .all(db),
+    )
+    .await
     {
-        Some(u) => u,
-        None => return StatusCode::UNAUTHORIZED.into_response(),
+        Ok(list) => list,
+        Err(e) => return DbError(e).into_response(),
+    };
+    let next_cursor = if list.len() as u64 > limit {
+        list.truncate(limit as usize);
+        list.last().and_then(|last| {
+            last.created_at.map(|created_at| {
+                Cursor {
+                    created_at,
+                    id: last.id,
+                }
+                .encode()
+            })
+        })
+    } else {
+        None
     };
 
-    // 🧠 Admins get all tickets, others get only their own
-    let tickets = if user.role == "admin" {
-        ticket::Entity::find().all(&db).await
+    if !query.includes_tags() {
+        return Json(serde_json::json!({ "tickets": list, "next_cursor": next_cursor }))
+            .into_response();
+    }
+    match timed("tickets.list_tags", with_tags(db, list)).await {
+        Ok(list) => {
+            Json(serde_json::json!({ "tickets": list, "next_cursor": next_cursor })).into_response()
+        }
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// `into_json` bypasses the model's serde attributes, so bring projected
+/// timestamps in line with [`time::to_rfc3339`].
+fn format_timestamps(row: &mut serde_json::Value) {
+    for key in ["created_at", "updated_at"] {
+        if let Some(value) = row.get_mut(key)
+            && let Some(at) = value.as_str().and_then(time::parse)
+        {
+            *value = time::to_rfc3339(at).into();
+        }
+    }
+}
+
+fn invalid_fields(details: String) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(serde_json::json!({ "error": "invalid_fields", "details": details })),
+    )
+        .into_response()
+}
+
+/// Attach tags to a page of tickets with a single join query instead of one per ticket.
+async fn with_tags(
+    db: &DatabaseConnection,
+    tickets: Vec<ticket::Model>,
+) -> Result<Vec<TicketWithTags>, DbErr> {
+    let ids: Vec<i32> = tickets.iter().map(|t| t.id).collect();
+
+    // 🔗 One query for every (ticket_tag, tag) pair on this page
+    let pairs = ticket_tag::Entity::find()
+        .filter(ticket_tag::Column::TicketId.is_in(ids))
+        .find_also_related(tag::Entity)
+        .order_by_asc(tag::Column::Name)
+        .all(db)
+        .await?;
+
+    let mut tags_by_ticket: HashMap<i32, Vec<tag::Model>> = HashMap::new();
+    for (link, tag) in pairs {
+        if let Some(tag) = tag {
+            tags_by_ticket.entry(link.ticket_id).or_default().push(tag);
+        }
+    }
+
+    Ok(tickets
+        .into_iter()
+        .map(|ticket| TicketWithTags {
+            tags: tags_by_ticket.remove(&ticket.id).unwrap_or_default(),
+            ticket,
+        })
+        .collect())
+}
+
+/// `SELECT 1 FROM ticket_tag WHERE ticket_tag.ticket_id = ticket.id`, for `EXISTS` checks.
+fn has_any_tag() -> SelectStatement {
+    sea_query::Query::select()
+        .expr(Expr::val(1))
+        .from(ticket_tag::Entity)
+        .and_where(
+            Expr::col((ticket_tag::Entity, ticket_tag::Column::TicketId))
+                .equals((ticket::Entity, ticket::Column::Id)),
+        )
+        .to_owned()
+}
+
+/// Tickets a user may see: everything for admins, their own for everyone else.
+fn visible_tickets(user: &user::Model) -> Select<ticket::Entity> {
+    let select = ticket::Entity::find();
+    if user.role == "admin" {
+        select
     } else {
-        ticket::Entity::find()
-            .filter(ticket::Column::UserId.eq(Some(user.id)))
-            .all(&db)
-            .await
+        select.filter(ticket::Column::UserId.eq(Some(user.id)))
+    }
+}
+
+/// A ticket status and how many visible tickets carry it.
+#[derive(Debug, Serialize)]
+pub struct StatusCount {
+    pub status: Option<String>,
+    pub count: i64,
+}
+
+/// List the distinct ticket statuses in use, with counts.
+///
+/// Uses a single `SELECT status, COUNT(*) ... GROUP BY status` over the
+/// caller's visible tickets: global for admins, their own for agents.
+/// Tickets without a status are reported with `"status": null`.
+///
+/// # Returns
+/// - `200 OK` with `[{ "status", "count" }]`, ordered by status
+/// - `401 UNAUTHORIZED` if JWT is invalid
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+#[tracing::instrument(skip_all)]
+pub async fn get_ticket_statuses(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+) -> impl IntoResponse {
+    match timed(
+        "tickets.statuses",
+        count_by_status(visible_tickets(&user), &state.read_db),
+    )
+    .await
+    {
+        Ok(counts) => Json(counts).into_response(),
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// The caller's personal ticket counts, returned by `GET /auth/me/stats`.
+#[derive(Debug, Serialize)]
+pub struct MyTicketStats {
+    pub total: i64,
+    pub by_status: Vec<StatusCount>,
+}
+
+/// Count the tickets assigned to the caller, grouped by status.
+///
+/// Always scoped to the caller's `user_id`, admins included; the global
+/// view is [`get_ticket_statuses`].
+///
+/// # Returns
+/// - `200 OK` with `{ "total", "by_status": [{ "status", "count" }] }`
+/// - `401 UNAUTHORIZED` if JWT is invalid
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+#[tracing::instrument(skip_all)]
+pub async fn get_my_ticket_stats(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+) -> impl IntoResponse {
+    let mine = ticket::Entity::find().filter(ticket::Column::UserId.eq(Some(user.id)));
+
+    match timed("tickets.my_stats", count_by_status(mine, &state.read_db)).await {
+        Ok(by_status) => Json(MyTicketStats {
+            total: by_status.iter().map(|c| c.count).sum(),
+            by_status,
+        })
+        .into_response(),
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// `SELECT status, COUNT(*) ... GROUP BY status` over `select`, ordered by status.
+pub(crate) async fn count_by_status(
+    select: Select<ticket::Entity>,
+    db: &DatabaseConnection,
+) -> Result<Vec<StatusCount>, DbErr> {
+    let rows: Vec<(Option<String>, i64)> = select
+        .select_only()
+        .column(ticket::Column::Status)
+        .column_as(ticket::Column::Id.count(), "count")
+        .group_by(ticket::Column::Status)
+        .order_by_asc(ticket::Column::Status)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(status, count)| StatusCount { status, count })
+        .collect())
+}
+
+/// How far back `get_ticket_feed` looks when `since` is omitted.
+pub const FEED_DEFAULT_WINDOW: Duration = Duration::hours(24);
+
+/// Query parameters accepted by `get_ticket_feed`.
+#[derive(Debug, Default, Deserialize)]
+pub struct FeedQuery {
+    pub since: Option<String>,
+}
+
+/// List tickets updated after `since`, most recently updated first.
+///
+/// Meant for delta-sync clients: remember the newest `updated_at` seen and
+/// pass it as `since` next time. Visibility matches [`get_tickets`].
+///
+/// # Query Parameters
+/// - `since`: RFC 3339 timestamp, exclusive (default: 24 hours ago)
+/// - `page`, `per_page`: See [`Pagination`]
+///
+/// # Returns
+/// - `200 OK` with ticket list and `X-Page`/`X-Per-Page` headers
+/// - `400 BAD_REQUEST` with `{ "error": "invalid_since" }` if `since` isn't RFC 3339
+/// - `401 UNAUTHORIZED` if JWT is invalid
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+#[tracing::instrument(skip_all)]
+pub async fn get_ticket_feed(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Query(query): Query<FeedQuery>,
+    pagination: Pagination,
+) -> impl IntoResponse {
+    let since = match query.since.as_deref() {
+        Some(raw) => match DateTime::parse_from_rfc3339(raw.trim()) {
+            Ok(since) => since.naive_utc(),
+            Err(_) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({ "error": "invalid_since" })),
+                )
+                    .into_response();
+            }
+        },
+        None => Utc::now().naive_utc() - FEED_DEFAULT_WINDOW,
     };
 
-    match tickets {
-        Ok(list) => Json(list).into_response(),
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    let select = visible_tickets(&user)
+        .filter(ticket::Column::UpdatedAt.gt(since))
+        .order_by_desc(ticket::Column::UpdatedAt)
+        .order_by_desc(ticket::Column::Id)
+        .offset(pagination.offset())
+        .limit(pagination.per_page);
+
+    match timed("tickets.feed", select.all(&state.read_db)).await {
+        Ok(list) => (pagination.headers(), Json(list)).into_response(),
+        Err(e) => DbError(e).into_response(),
     }
 }
 
@@ -136,33 +809,110 @@ pub async fn get_tickets(
 /// - `404 NOT_FOUND` if ticket doesn't exist
 /// - `401 UNAUTHORIZED` if JWT is invalid
 pub async fn get_ticket_by_id(
+    State(state): State<AppState>,
     Path(ticket_id): Path<i32>,
-    TypedHeader(Authorization(bearer)): TypedHeader<Authorization<Bearer>>,
+    AuthUser(user): AuthUser,
 ) -> impl IntoResponse {
-    let db = connect().await;
+    let db = &state.db;
 
-    let claims = match extract_claims(bearer.token()) {
-        Ok(c) => c,
-        Err(_) => return StatusCode::UNAUTHORIZED.into_response(),
+    let ticket = match ticket::Entity::find_by_id(ticket_id).one(db).await {
+        Ok(Some(t)) => t,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return DbError(e).into_response(),
     };
 
-    let ticket = match ticket::Entity::find_by_id(ticket_id)
-        .one(&db)
-        .await
-        .unwrap()
-    {
-        Some(t) => t,
-        None => return StatusCode::NOT_FOUND.into_response(),
+    // 🚫 Access control
+    if user.role != "admin" && Some(user.id) != ticket.user_id {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    Json(ticket).into_response()
+}
+
+/// Download a ticket, with its tags, as a JSON file attachment.
+///
+/// Access control matches [`get_ticket_by_id`].
+///
+/// # Returns
+/// - `200 OK` with `Content-Disposition: attachment; filename="ticket-{id}.json"`
+/// - `403 FORBIDDEN` if access is denied
+/// - `404 NOT_FOUND` if ticket doesn't exist
+/// - `401 UNAUTHORIZED` if JWT is invalid
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn download_ticket(
+    State(state): State<AppState>,
+    Path(ticket_id): Path<i32>,
+    AuthUser(user): AuthUser,
+) -> impl IntoResponse {
+    let db = &state.db;
+
+    let ticket = match ticket::Entity::find_by_id(ticket_id).one(db).await {
+        Ok(Some(t)) => t,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return DbError(e).into_response(),
     };
 
-    let user = match user::Entity::find()
-        .filter(user::Column::Email.eq(claims.sub.clone()))
-        .one(&db)
-        .await
-        .unwrap()
-    {
-        Some(u) => u,
-        None => return StatusCode::UNAUTHORIZED.into_response(),
+    // 🚫 Access control
+    if user.role != "admin" && Some(user.id) != ticket.user_id {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    let export = match with_tags(db, vec![ticket]).await {
+        Ok(mut list) => list.remove(0),
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    // 📎 Pretty-printed so the saved file is readable
+    let body = match serde_json::to_vec_pretty(&export) {
+        Ok(body) => body,
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+
+    (
+        [
+            (header::CONTENT_TYPE, "application/json".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"ticket-{ticket_id}.json\""),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+/// A tag annotated with whether it is attached to a particular ticket.
+#[derive(Debug, Serialize, FromQueryResult)]
+pub struct AvailableTag {
+    pub id: i32,
+    pub name: String,
+    pub attached: bool,
+}
+
+/// List every tag, flagging the ones already attached to a ticket.
+///
+/// Powers a tag picker's checkbox list in one call: a single left join
+/// against `ticket_tag` for this ticket decides `attached`. Soft-deleted
+/// tags are left out since they can't be attached. Access control matches
+/// [`get_ticket_by_id`].
+///
+/// # Returns
+/// - `200 OK` with `[{ "id", "name", "attached" }]`, ordered by name
+/// - `403 FORBIDDEN` if access is denied
+/// - `404 NOT_FOUND` if ticket doesn't exist
+/// - `401 UNAUTHORIZED` if JWT is invalid
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn get_available_tags(
+    State(state): State<AppState>,
+    Path(ticket_id): Path<i32>,
+    AuthUser(user): AuthUser,
+) -> impl IntoResponse {
+    let db = &state.db;
+
+    let ticket = match ticket::Entity::find_by_id(ticket_id).one(db).await {
+        Ok(Some(t)) => t,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return DbError(e).into_response(),
     };
 
     // 🚫 Access control
@@ -170,7 +920,35 @@ pub async fn get_ticket_by_id(
         return StatusCode::FORBIDDEN.into_response();
     }
 
-    Json(ticket).into_response()
+    // 🔗 Left join only this ticket's relations; a match means "attached"
+    let tags = tag::Entity::find()
+        .select_only()
+        .column(tag::Column::Id)
+        .column(tag::Column::Name)
+        .column_as(
+            Expr::col((ticket_tag::Entity, ticket_tag::Column::Id)).is_not_null(),
+            "attached",
+        )
+        .join(
+            JoinType::LeftJoin,
+            tag::Relation::TicketTag
+                .def()
+                .on_condition(move |_, right| {
+                    Expr::col((right, ticket_tag::Column::TicketId))
+                        .eq(ticket_id)
+                        .into_condition()
+                }),
+        )
+        .filter(tag::Column::DeletedAt.is_null())
+        .order_by_asc(tag::Column::Name)
+        .into_model::<AvailableTag>()
+        .all(db)
+        .await;
+
+    match tags {
+        Ok(tags) => Json(tags).into_response(),
+        Err(e) => DbError(e).into_response(),
+    }
 }
 
 /// Delete a ticket by ID (with access control).
@@ -184,33 +962,16 @@ pub async fn get_ticket_by_id(
 /// - `404 NOT_FOUND` if ticket doesn't exist
 /// - `401 UNAUTHORIZED` if JWT is invalid
 pub async fn delete_ticket_by_id(
+    State(state): State<AppState>,
     Path(ticket_id): Path<i32>,
-    TypedHeader(Authorization(bearer)): TypedHeader<Authorization<Bearer>>,
+    AuthUser(user): AuthUser,
 ) -> impl IntoResponse {
-    let db = connect().await;
-
-    let claims = match extract_claims(bearer.token()) {
-        Ok(c) => c,
-        Err(_) => return StatusCode::UNAUTHORIZED.into_response(),
-    };
+    let db = &state.db;
 
-    let user = match user::Entity::find()
-        .filter(user::Column::Email.eq(claims.sub.clone()))
-        .one(&db)
-        .await
-        .unwrap()
-    {
-        Some(u) => u,
-        None => return StatusCode::UNAUTHORIZED.into_response(),
-    };
-
-    let ticket = match ticket::Entity::find_by_id(ticket_id)
-        .one(&db)
-        .await
-        .unwrap()
-    {
-        Some(t) => t,
-        None => return StatusCode::NOT_FOUND.into_response(),
+    let ticket = match ticket::Entity::find_by_id(ticket_id).one(db).await {
+        Ok(Some(t)) => t,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return DbError(e).into_response(),
     };
 
     // 🛡️ Only allow deletion if owner or admin
@@ -218,9 +979,12 @@ pub async fn delete_ticket_by_id(
         return StatusCode::FORBIDDEN.into_response();
     }
 
-    match ticket.into_active_model().delete(&db).await {
-        Ok(_) => StatusCode::NO_CONTENT.into_response(),
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    match ticket.clone().into_active_model().delete(db).await {
+        Ok(_) => {
+            events::publish(&state.events, TicketEventKind::Deleted, ticket);
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Err(e) => DbError(e).into_response(),
     }
 }
 
@@ -234,44 +998,41 @@ pub struct UpdateTicket {
 
 /// Update a ticket by ID (with access control).
 ///
+/// The caller is recorded in `updated_by`, which feeds
+/// `GET /admin/users/{id}/activity/tickets`.
+///
 /// # Request Body
 /// - Optional fields to update: `title`, `description`, `status`
 ///
+/// # Query Parameters
+/// - `on_overflow=reject|truncate`: See [`create_ticket`]
+///
 /// # Returns
-/// - `200 OK` with updated ticket
+/// - `200 OK` with updated ticket, plus `"description_truncated": true` if it was cut
+/// - `400 BAD_REQUEST` if no updatable fields are provided
+/// - `400 BAD_REQUEST` with `{ "error": "description_too_long", "max_len" }` when rejecting an overflow
 /// - `403 FORBIDDEN` if access denied
 /// - `404 NOT_FOUND` if ticket doesn't exist
 /// - `401 UNAUTHORIZED` if JWT is invalid
 /// - `500 INTERNAL_SERVER_ERROR` on update failure
 pub async fn update_ticket_by_id(
+    State(state): State<AppState>,
     Path(ticket_id): Path<i32>,
-    TypedHeader(Authorization(bearer)): TypedHeader<Authorization<Bearer>>,
+    AuthUser(user): AuthUser,
+    Query(query): Query<WriteTicketQuery>,
     Json(payload): Json<UpdateTicket>,
 ) -> impl IntoResponse {
-    let db = connect().await;
+    let db = &state.db;
 
-    let claims = match extract_claims(bearer.token()) {
-        Ok(c) => c,
-        Err(_) => return StatusCode::UNAUTHORIZED.into_response(),
-    };
-
-    let user = match user::Entity::find()
-        .filter(user::Column::Email.eq(claims.sub.clone()))
-        .one(&db)
-        .await
-        .unwrap()
-    {
-        Some(u) => u,
-        None => return StatusCode::UNAUTHORIZED.into_response(),
-    };
+    // 🚫 No updates provided
+    if payload.title.is_none() && payload.description.is_none() && payload.status.is_none() {
+        return StatusCode::BAD_REQUEST.into_response();
+    }
 
-    let ticket = match ticket::Entity::find_by_id(ticket_id)
-        .one(&db)
-        .await
-        .unwrap()
-    {
-        Some(t) => t,
-        None => return StatusCode::NOT_FOUND.into_response(),
+    let ticket = match ticket::Entity::find_by_id(ticket_id).one(db).await {
+        Ok(Some(t)) => t,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return DbError(e).into_response(),
     };
 
     // 🔐 Enforce ownership or admin access
@@ -281,20 +1042,210 @@ pub async fn update_ticket_by_id(
 
     // 🛠️ Apply patch
     let mut active_ticket: ticket::ActiveModel = ticket.into_active_model();
+    let mut description_truncated = false;
     if let Some(t) = payload.title {
         active_ticket.title = Set(t);
     }
     if let Some(d) = payload.description {
-        active_ticket.description = Set(Some(d));
+        let max_len = state.config.max_description_len;
+        match fit_description(d, max_len, query.on_overflow) {
+            Some((d, truncated)) => {
+                active_ticket.description = Set(Some(d));
+                description_truncated = truncated;
+            }
+            None => return description_too_long(max_len),
+        }
     }
     if let Some(s) = payload.status {
         active_ticket.status = Set(Some(s));
     }
 
-    active_ticket.updated_at = Set(Some(Local::now().naive_local()));
+    active_ticket.updated_at = Set(Some(Utc::now().naive_utc()));
+    active_ticket.updated_by = Set(Some(user.id));
+
+    match active_ticket.update(db).await {
+        Ok(updated) => {
+            events::publish(&state.events, TicketEventKind::Updated, updated.clone());
+            axum::Json(SavedTicket {
+                ticket: updated,
+                description_truncated,
+            })
+            .into_response()
+        }
+        Err(e) => DbError(e).into_response(),
+    }
+}
+
+/// Payload for setting the status of many tickets at once.
+#[derive(Deserialize)]
+pub struct BulkStatusUpdate {
+    pub ids: Vec<i32>,
+    pub status: String,
+}
+
+/// Set the status of many tickets at once.
+///
+/// Each ticket is checked and updated on its own, with the same access
+/// rules as [`update_ticket_by_id`], so one bad ID doesn't block the rest.
+/// A ticket whose update fails in the database is reported as such, while
+/// the ones before and after it keep their new status.
+///
+/// # Request Body
+/// - `ids`: Tickets to update
+/// - `status`: New status for all of them
+///
+/// # Returns
+/// - `200 OK` with a [`BulkItem`] per ID when all succeeded
+/// - `207 MULTI_STATUS` with the same array when some failed; failed items
+///   carry `403`/`forbidden`, `404`/`not_found` or `500`/`db_error`
+/// - `422 UNPROCESSABLE_ENTITY` if `status` is blank
+/// - `401 UNAUTHORIZED` if JWT is invalid
+/// - `500 INTERNAL_SERVER_ERROR` if the tickets can't be loaded
+pub async fn bulk_update_status(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Json(payload): Json<BulkStatusUpdate>,
+) -> impl IntoResponse {
+    let db = &state.db;
+
+    let mut errors = ValidationErrors::default();
+    let Some(status) = errors.require(
+        "status",
+        normalize::trimmed(&payload.status),
+        "must not be blank",
+    ) else {
+        return errors.into_response();
+    };
+
+    // 🔍 Load every requested ticket up front
+    let found: HashMap<i32, ticket::Model> = match ticket::Entity::find()
+        .filter(ticket::Column::Id.is_in(payload.ids.clone()))
+        .all(db)
+        .await
+    {
+        Ok(tickets) => tickets.into_iter().map(|t| (t.id, t)).collect(),
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    // 🔁 Apply item by item, recording each outcome
+    let mut results = Vec::with_capacity(payload.ids.len());
+    for id in payload.ids {
+        let Some(ticket) = found.get(&id) else {
+            results.push(BulkItem::failed(id, StatusCode::NOT_FOUND, "not_found"));
+            continue;
+        };
+        if user.role != "admin" && ticket.user_id != Some(user.id) {
+            results.push(BulkItem::failed(id, StatusCode::FORBIDDEN, "forbidden"));
+            continue;
+        }
 
-    match active_ticket.update(&db).await {
-        Ok(updated) => axum::Json(updated).into_response(),
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        let mut active = ticket.clone().into_active_model();
+        active.status = Set(Some(status.clone()));
+        active.updated_at = Set(Some(Utc::now().naive_utc()));
+        active.updated_by = Set(Some(user.id));
+        match active.update(db).await {
+            Ok(updated) => {
+                events::publish(&state.events, TicketEventKind::Updated, updated);
+                results.push(BulkItem::ok(id));
+            }
+            Err(e) => {
+                // Earlier items are already saved, so report this one and go on
+                tracing::error!(ticket_id = id, error = ?e, "bulk status update failed");
+                results.push(BulkItem::failed(
+                    id,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "db_error",
+                ));
+            }
+        }
     }
-}
\ No newline at end of file
+
+    BulkResults(results).into_response()
+}
+
+/// Payload for fetching tickets in a caller-chosen order.
+#[derive(Deserialize)]
+pub struct OrderedTickets {
+    pub ids: Vec<i32>,
+}
+
+/// Fetch tickets in exactly the order their IDs are given.
+///
+/// For boards that keep their own card order: the tickets come back in
+/// `ids` order rather than database order. IDs that don't exist or that the
+/// caller can't see (same rules as [`get_tickets`]) are left out, and the
+/// rest keep their relative positions. A repeated ID appears once, at its
+/// first position.
+///
+/// # Request Body
+/// - `ids`: Ticket IDs in the wanted order
+///
+/// # Returns
+/// - `200 OK` with the ticket list
+/// - `401 UNAUTHORIZED` if JWT is invalid
+/// - `500 INTERNAL_SERVER_ERROR` on DB failure
+pub async fn get_tickets_in_order(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Json(payload): Json<OrderedTickets>,
+) -> impl IntoResponse {
+    // 🔍 Fetch whatever the caller may see, in any order
+    let mut found: HashMap<i32, ticket::Model> = match visible_tickets(&user)
+        .filter(ticket::Column::Id.is_in(payload.ids.clone()))
+        .all(&state.db)
+        .await
+    {
+        Ok(tickets) => tickets.into_iter().map(|t| (t.id, t)).collect(),
+        Err(e) => return DbError(e).into_response(),
+    };
+
+    // 🧭 Lay them out in the requested order; taking each out drops repeats
+    let ordered: Vec<ticket::Model> = payload
+        .ids
+        .iter()
+        .filter_map(|id| found.remove(id))
+        .collect();
+
+    Json(ordered).into_response()
+}
+
+/// Stream ticket changes to the caller as server-sent events.
+///
+/// Authentication happens once, when the stream is opened. Afterwards the
+/// caller receives `ticket_created`, `ticket_updated` and `ticket_deleted`
+/// events for every ticket they could see through `get_tickets`.
+///
+/// # Headers
+/// - `Authorization: Bearer <token>`
+///
+/// # Returns
+/// - `200 OK` with a `text/event-stream` body
+/// - `401 UNAUTHORIZED` if JWT is invalid
+pub async fn ticket_stream(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+) -> impl IntoResponse {
+    let user_id = user.id;
+    let is_admin = user.role == "admin";
+
+    // 📡 Forward visible events until the channel closes
+    let stream = stream::unfold(state.events.subscribe(), move |mut rx| async move {
+        loop {
+            match rx.recv().await {
+                Ok(event) if event.visible_to(user_id, is_admin) => {
+                    let sse = Event::default()
+                        .event(event.kind.as_str())
+                        .json_data(&event.ticket)
+                        .unwrap_or_else(|_| Event::default().event(event.kind.as_str()));
+                    return Some((Ok::<_, Infallible>(sse), rx));
+                }
+                Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
@@ -0,0 +1,78 @@
+//! In-memory tracking for work handed off to background tasks.
+//!
+//! Long admin operations (such as `POST /admin/dev/reset-db?async=true`)
+//! return `202 ACCEPTED` with a job ID and run on a spawned task; clients
+//! poll `GET /admin/dev/jobs/{id}` until the job is `done` or `failed`.
+//! Jobs live only as long as the process.
+
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use serde::Serialize;
+use serde_json::Value;
+use tokio::sync::RwLock;
+
+/// Where a job is in its lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Pending,
+    Running,
+    Done,
+    Failed,
+}
+
+/// A background job and, once finished, its result summary.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Job {
+    pub id: u64,
+    pub status: JobStatus,
+    pub result: Option<Value>,
+}
+
+/// Every job started since the process came up, keyed by ID.
+#[derive(Debug)]
+pub struct JobRegistry {
+    next_id: AtomicU64,
+    jobs: RwLock<HashMap<u64, Job>>,
+}
+
+impl Default for JobRegistry {
+    fn default() -> Self {
+        Self {
+            next_id: AtomicU64::new(1),
+            jobs: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl JobRegistry {
+    /// Register a new `pending` job and return its ID.
+    pub async fn create(&self) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let job = Job {
+            id,
+            status: JobStatus::Pending,
+            result: None,
+        };
+        self.jobs.write().await.insert(id, job);
+        id
+    }
+
+    /// Move a job to `status`, recording `result` if given.
+    pub async fn update(&self, id: u64, status: JobStatus, result: Option<Value>) {
+        if let Some(job) = self.jobs.write().await.get_mut(&id) {
+            job.status = status;
+            if result.is_some() {
+                job.result = result;
+            }
+        }
+    }
+
+    /// A snapshot of the job, or `None` if no job has that ID.
+    pub async fn get(&self, id: u64) -> Option<Job> {
+        self.jobs.read().await.get(&id).cloned()
+    }
+}
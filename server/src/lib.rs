@@ -5,14 +5,30 @@
 //!
 //! # Modules
 //! - `routes`: Defines API endpoints and routing logic.
+//! - `cache`: In-memory snapshot of the tag list.
 //! - `db`: Handles database connections and queries.
+//! - `email`: Pluggable outgoing mail (`EmailSender`).
+//! - `events`: Broadcasts ticket changes to live subscribers.
 //! - `handlers`: Contains request handler implementations.
+//! - `jobs`: Status of work running on background tasks.
+//! - `metrics`: In-process counters such as login failures.
+//! - `middleware`: Tower layers applied to the whole router (e.g. CORS).
 //! - `config`: Manages application configuration and environment variables.
 //! - `models`: Defines data structures and ORM models.
+//! - `notifications`: Emails tag subscribers when their tags are attached.
+//! - `state`: Shared application state (`AppState`) passed to handlers.
 //! - `utils`: Provides utility functions used throughout the server.
+pub mod cache;
 pub mod config;
 pub mod db;
+pub mod email;
+pub mod events;
 pub mod handlers;
+pub mod jobs;
+pub mod metrics;
+pub mod middleware;
 pub mod models;
+pub mod notifications;
 pub mod routes;
+pub mod state;
 pub mod utils;
@@ -1,42 +1,95 @@
 use std::net::SocketAddr;
-use tracing_subscriber;
 
-/// Handles application-wide configuration settings
-mod config;
+use sea_orm_migration::MigratorTrait;
+use tagblaze::{
+    config::{self, Config},
+    db, routes,
+    state::AppState,
+    utils,
+};
 
-/// Manages database connections, migrations, and queries
-mod db;
+/// Entry point for the TagBlaze application.
+///
+/// This function loads the environment file, sets up logging, loads configuration, opens the shared
+/// database pool, configures the application router, binds the server to a
+/// local address, and starts the Axum HTTP server.
+#[tokio::main]
+async fn main() {
+    // Load `.env.{APP_ENV}` (or `.env`) once, before anything reads the environment
+    let env_file = config::load_env_file();
 
-/// Contains all HTTP request handlers
-mod handlers;
+    // Initialize tracing from RUST_LOG/LOG_LEVEL and LOG_FORMAT
+    utils::logging::init();
+    if let Some(path) = env_file {
+        tracing::info!(path = %path.display(), "Loaded environment file");
+    }
 
-/// Defines shared data models used across the application
-mod models;
+    // Resolve configuration and open the shared connection pool once
+    let config = Config::from_env();
+    config.log_effective();
+    let db = db::db::connect(&config).await;
 
-/// Configures all application routes and middleware
-mod routes;
+    // Optionally bring the schema up to date before serving
+    if config.run_migrations {
+        match db::migration::Migrator::up(&db, None).await {
+            Ok(()) => tracing::info!("🧱 Database migrations applied"),
+            Err(e) => panic!("❌ Failed to run migrations: {e}"),
+        }
+    }
 
-/// Utility functions and helpers used across multiple modules
-mod utils;
-
-/// Entry point for the TagBlaze application.
-/// 
-/// This function sets up logging, configures the application router,
-/// binds the server to a local address, and starts the Axum HTTP server.
-#[tokio::main]
-async fn main() {
-    // Initialize tracing subscriber for structured logging
-    tracing_subscriber::fmt::init();
+    // Optionally populate an empty database with demo data (never touches existing rows)
+    if config.seed_on_empty {
+        match db::seed::seed_if_empty(&db).await {
+            Ok(true) => tracing::info!("🌱 Empty database seeded with sample data"),
+            Ok(false) => tracing::info!("Database already has users; skipping seed"),
+            Err(e) => tracing::error!(error = %e, "Seeding on startup failed"),
+        }
+    }
+    let read_db = db::db::connect_read(&config).await;
+    let mut state = with_email_sender(AppState::new(db, config));
+    if let Some(read_db) = read_db {
+        tracing::info!("📖 Serving reads from DATABASE_READ_URL");
+        state = state.with_read_db(read_db);
+    }
 
     // Construct the full application router from all defined routes
-    let app = routes::create_router();
+    let app = routes::create_router(state);
 
     // Define the local address for the server to bind to
     let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
+    tracing::info!(%addr, "Binding HTTP listener");
     println!("🚀 TagBlaze running at http://{}", addr);
 
     // Start the Axum server with the configured router
-    axum::serve(tokio::net::TcpListener::bind(addr).await.unwrap(), app)
-        .await
-        .unwrap();
+    // (connect info exposes the client address to handlers, e.g. for login auditing)
+    axum::serve(
+        tokio::net::TcpListener::bind(addr).await.unwrap(),
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .unwrap();
+}
+
+/// Send mail over SMTP when built with the `smtp` feature and `SMTP_URL` is set.
+#[cfg(feature = "smtp")]
+fn with_email_sender(state: AppState) -> AppState {
+    let Some(url) = state.config.smtp_url.clone() else {
+        return state;
+    };
+    match tagblaze::email::SmtpEmailSender::new(&url, &state.config.email_from) {
+        Ok(sender) => state.with_email_sender(std::sync::Arc::new(sender)),
+        Err(e) => {
+            tracing::error!(error = %e, "SMTP setup failed; logging mail instead");
+            state
+        }
+    }
+}
+
+/// Without the `smtp` feature, mail is only logged.
+#[cfg(not(feature = "smtp"))]
+fn with_email_sender(state: AppState) -> AppState {
+    if state.config.smtp_url.is_some() {
+        tracing::warn!("SMTP_URL is set but the server was built without the `smtp` feature");
+    }
+    state
 }
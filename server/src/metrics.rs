@@ -0,0 +1,35 @@
+//! In-process counters for security-relevant events.
+//!
+//! The counters live in [`AppState`](crate::state::AppState) so handlers can
+//! bump them and an exporter can read them without global state.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Monotonic counters, named after the metric they back.
+///
+/// # Fields
+/// - `login_failures_total`: Rejected login attempts (unknown email or bad password).
+/// - `login_successes_total`: Logins that issued a token.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    pub login_failures_total: AtomicU64,
+    pub login_successes_total: AtomicU64,
+}
+
+impl Metrics {
+    pub fn record_login_failure(&self) {
+        self.login_failures_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_login_success(&self) {
+        self.login_successes_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn login_failures(&self) -> u64 {
+        self.login_failures_total.load(Ordering::Relaxed)
+    }
+
+    pub fn login_successes(&self) -> u64 {
+        self.login_successes_total.load(Ordering::Relaxed)
+    }
+}
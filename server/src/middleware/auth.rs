@@ -1 +1,148 @@
-// TODO
\ No newline at end of file
+use axum::{
+    extract::{FromRequestParts, OptionalFromRequestParts},
+    http::{HeaderMap, StatusCode, header, request::Parts},
+    response::{IntoResponse, Response},
+};
+use axum_extra::extract::CookieJar;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+use crate::{
+    middleware::error::DbError,
+    models::user,
+    state::AppState,
+    utils::jwt::{JwtError, bearer_token, extract_claims},
+};
+
+/// Cookie that carries the JWT for browser clients logged in with `?mode=cookie`.
+pub const AUTH_COOKIE: &str = "tagblaze_token";
+
+/// The authenticated caller, resolved from `Authorization: Bearer <token>`,
+/// or from the [`AUTH_COOKIE`] cookie when no `Authorization` header is sent.
+/// When `jwt_header` is configured (e.g. `X-Auth-Token`), a bare token in that
+/// header is tried first.
+///
+/// Decodes the JWT, loads the user named by its `sub`, and rejects tokens
+/// issued before the user's `tokens_valid_after` (set by an admin
+/// force-logout).
+///
+/// Tokens minted by an admin impersonating the user carry `impersonated_by`;
+/// every request made with one is logged against that admin, and the admin's
+/// email is left in the request extensions as [`Impersonator`].
+///
+/// # Rejections
+/// - `401 UNAUTHORIZED` with `token_missing`, `token_invalid`, `token_expired`, or `token_revoked`
+/// - `401 UNAUTHORIZED` if the user no longer exists
+/// - `503 SERVICE_UNAVAILABLE` if no database connection is free (see [`DbError`])
+/// - `500 INTERNAL_SERVER_ERROR` if the user lookup fails
+///
+/// # Example
+/// ```rust,ignore
+/// pub async fn handler(AuthUser(user): AuthUser) -> impl IntoResponse {
+///     format!("hello {}", user.email)
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AuthUser(pub user::Model);
+
+/// Email of the admin behind an impersonation token, set on the request by
+/// [`AuthUser`] when the token carries an `impersonated_by` claim.
+#[derive(Debug, Clone)]
+pub struct Impersonator(pub String);
+
+impl FromRequestParts<AppState> for AuthUser {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Response> {
+        // 🪪 Expect the configured header, a "Bearer <token>" header, or the auth cookie
+        let jar = CookieJar::from_headers(&parts.headers);
+        let token = token_from(&parts.headers, &jar, state.config.jwt_header.as_deref())
+            .map_err(IntoResponse::into_response)?;
+
+        // 🔓 Verify signature and expiry
+        let claims =
+            extract_claims(token, &state.config.jwt_secret).map_err(IntoResponse::into_response)?;
+
+        // 👤 Resolve the user behind the token
+        let user = user::Entity::find()
+            .filter(user::Column::Email.eq(claims.sub.clone()))
+            .one(&state.db)
+            .await
+            .map_err(|e| DbError(e).into_response())?
+            .ok_or_else(|| StatusCode::UNAUTHORIZED.into_response())?;
+
+        // 🚪 Tokens issued before a force-logout are dead
+        if let Some(valid_after) = user.tokens_valid_after
+            && (claims.iat as i64) < valid_after.and_utc().timestamp()
+        {
+            return Err(JwtError::Revoked.into_response());
+        }
+
+        // 🎭 Attribute impersonated requests to the admin behind them
+        if let Some(admin) = claims.impersonated_by {
+            tracing::info!(
+                user = %user.email,
+                impersonated_by = %admin,
+                method = %parts.method,
+                path = %parts.uri.path(),
+                "request made under impersonation"
+            );
+            parts.extensions.insert(Impersonator(admin));
+        }
+
+        Ok(AuthUser(user))
+    }
+}
+
+/// `Option<AuthUser>` is `None` when the request carries no credentials at
+/// all, for routes that only need a caller under some configurations.
+/// Credentials that are present but bad are still rejected.
+impl OptionalFromRequestParts<AppState> for AuthUser {
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Option<Self>, Response> {
+        let has_cookie = CookieJar::from_headers(&parts.headers)
+            .get(AUTH_COOKIE)
+            .is_some();
+        let has_custom = state
+            .config
+            .jwt_header
+            .as_deref()
+            .is_some_and(|name| parts.headers.contains_key(name));
+        if !parts.headers.contains_key(header::AUTHORIZATION) && !has_cookie && !has_custom {
+            return Ok(None);
+        }
+        <Self as FromRequestParts<AppState>>::from_request_parts(parts, state)
+            .await
+            .map(Some)
+    }
+}
+
+/// Pick the raw JWT out of a request.
+///
+/// A configured `custom` header carries the bare token and wins when
+/// present; otherwise `Authorization: Bearer` is used, then the auth cookie.
+fn token_from<'a>(
+    headers: &'a HeaderMap,
+    jar: &'a CookieJar,
+    custom: Option<&str>,
+) -> Result<&'a str, JwtError> {
+    if let Some(value) = custom.and_then(|name| headers.get(name)) {
+        return match value.to_str().map_err(|_| JwtError::Invalid)?.trim() {
+            "" => Err(JwtError::Missing),
+            token => Ok(token),
+        };
+    }
+    match headers.get(header::AUTHORIZATION) {
+        Some(value) => value
+            .to_str()
+            .map_err(|_| JwtError::Invalid)
+            .and_then(bearer_token),
+        None => jar
+            .get(AUTH_COOKIE)
+            .map(|cookie| cookie.value())
+            .ok_or(JwtError::Invalid),
+    }
+}
@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use axum::{
+    Router,
+    extract::{Request, State},
+    http::{HeaderName, HeaderValue, Method, header},
+    middleware::Next,
+    response::Response,
+};
+use tower::ServiceExt;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+
+use crate::config::Config;
+
+/// Methods the API exposes to cross-origin callers.
+const ALLOWED_METHODS: [Method; 6] = [
+    Method::GET,
+    Method::POST,
+    Method::PUT,
+    Method::PATCH,
+    Method::DELETE,
+    Method::OPTIONS,
+];
+
+/// Build the CORS layer from configuration.
+///
+/// - With no configured origins, any origin is allowed and credentials are
+///   never allowed (browsers reject `*` combined with credentials anyway).
+/// - With explicit origins, only those are allowed, and credentials are
+///   allowed when `cors_allow_credentials` is set.
+/// - A configured `jwt_header` is allowed alongside `Authorization`, and
+///   `If-None-Match` so browsers can revalidate `GET /tags`.
+/// - `cors_max_age_secs` sets `Access-Control-Max-Age` on preflight responses.
+/// - Every response, allowed origin or not, carries `Vary: origin` along
+///   with the preflight request headers (the `CorsLayer` default), so shared
+///   caches never hand one origin's answer to another.
+pub fn cors_layer(config: &Config) -> CorsLayer {
+    let origins: Vec<HeaderValue> = config
+        .cors_allowed_origins
+        .iter()
+        .filter_map(|origin| HeaderValue::from_str(origin).ok())
+        .collect();
+
+    let mut allowed_headers = vec![
+        header::AUTHORIZATION,
+        header::CONTENT_TYPE,
+        header::IF_NONE_MATCH,
+    ];
+    if let Some(name) = config.jwt_header.as_deref()
+        && let Ok(name) = HeaderName::from_bytes(name.as_bytes())
+    {
+        allowed_headers.push(name);
+    }
+
+    let mut layer = CorsLayer::new()
+        .allow_methods(ALLOWED_METHODS)
+        .allow_headers(allowed_headers);
+
+    if origins.is_empty() {
+        if config.cors_allow_credentials {
+            tracing::warn!(
+                "CORS_ALLOW_CREDENTIALS ignored: it requires explicit CORS_ALLOWED_ORIGINS"
+            );
+        }
+        layer = layer.allow_origin(Any);
+    } else {
+        layer = layer
+            .allow_origin(AllowOrigin::list(origins))
+            .allow_credentials(config.cors_allow_credentials);
+    }
+
+    if let Some(secs) = config.cors_max_age_secs {
+        layer = layer.max_age(Duration::from_secs(secs));
+    }
+
+    layer
+}
+
+/// Send plain `OPTIONS` requests past the CORS layer, straight to `router`.
+///
+/// The CORS layer answers every `OPTIONS` as a preflight. Only requests
+/// with `Access-Control-Request-Method` are preflights; the rest reach the
+/// router, whose method fallback replies `204` with an `Allow` header.
+/// Install outside the CORS layer, with the router it wraps as state.
+pub async fn plain_options(State(router): State<Router>, request: Request, next: Next) -> Response {
+    let preflight = request
+        .headers()
+        .contains_key(header::ACCESS_CONTROL_REQUEST_METHOD);
+    if request.method() != Method::OPTIONS || preflight {
+        return next.run(request).await;
+    }
+    let Ok(response) = router.oneshot(request).await;
+    response
+}
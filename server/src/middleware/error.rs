@@ -1 +1,60 @@
-// TODO
\ No newline at end of file
+use axum::{
+    Json,
+    http::{HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use sea_orm::{ConnAcquireErr, DbErr};
+use serde_json::json;
+
+/// Seconds a client is asked to wait after a `503` caused by an exhausted pool.
+pub const RETRY_AFTER_SECS: u64 = 5;
+
+/// A database failure as an HTTP response.
+///
+/// When no pooled connection became free within `db_acquire_timeout_secs`,
+/// this renders `503 SERVICE_UNAVAILABLE` with `Retry-After` and
+/// `{ "error": "db_unavailable" }` so clients back off instead of hanging.
+/// Every other error is a plain `500 INTERNAL_SERVER_ERROR`.
+///
+/// # Example
+/// ```rust,ignore
+/// match tag::Entity::find_by_id(id).one(db).await {
+///     Ok(Some(tag)) => Json(tag).into_response(),
+///     Ok(None) => StatusCode::NOT_FOUND.into_response(),
+///     Err(e) => DbError(e).into_response(),
+/// }
+/// ```
+#[derive(Debug)]
+pub struct DbError(pub DbErr);
+
+impl DbError {
+    /// Whether the pool gave up waiting for a free connection.
+    pub fn is_pool_timeout(&self) -> bool {
+        matches!(self.0, DbErr::ConnectionAcquire(ConnAcquireErr::Timeout))
+    }
+}
+
+impl From<DbErr> for DbError {
+    fn from(err: DbErr) -> Self {
+        Self(err)
+    }
+}
+
+impl IntoResponse for DbError {
+    fn into_response(self) -> Response {
+        if !self.is_pool_timeout() {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+
+        tracing::warn!("database pool exhausted; returning 503");
+        let mut response = (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({ "error": "db_unavailable" })),
+        )
+            .into_response();
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(RETRY_AFTER_SECS));
+        response
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use axum::{
+    Json,
+    extract::{FromRequestParts, Request, State},
+    http::{HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde_json::json;
+
+use crate::{middleware::auth::AuthUser, models::user::Role, state::AppState};
+
+/// Whether the server is in maintenance mode, switchable at runtime.
+///
+/// Starts from `maintenance_mode` and is flipped by
+/// `PUT /admin/maintenance`; the setting lasts until the process restarts.
+#[derive(Debug, Default)]
+pub struct Maintenance(AtomicBool);
+
+impl Maintenance {
+    pub fn new(enabled: bool) -> Self {
+        Self(AtomicBool::new(enabled))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub fn set(&self, enabled: bool) {
+        self.0.store(enabled, Ordering::Relaxed);
+    }
+}
+
+/// Paths that keep working during maintenance: health checks for the load
+/// balancer, the admin API to switch maintenance off, and login so an
+/// admin can get a token to do so.
+fn exempt(path: &str) -> bool {
+    let under = |prefix: &str| {
+        path.strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    };
+    under("/health") || under("/admin") || path == "/auth/login"
+}
+
+/// Answer `503 SERVICE_UNAVAILABLE` with `Retry-After` while in maintenance.
+///
+/// Exempt paths (see above) and requests authenticated as an admin pass
+/// through. The body is `{ "error": "maintenance" }`, and `Retry-After`
+/// carries `maintenance_retry_after_secs`.
+pub async fn maintenance_mode(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !state.maintenance.is_enabled() || exempt(request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    // 🛡️ Admins keep full access so they can check the system before reopening
+    let (mut parts, body) = request.into_parts();
+    let is_admin = AuthUser::from_request_parts(&mut parts, &state)
+        .await
+        .is_ok_and(|AuthUser(user)| user.role == Role::Admin.as_str());
+    if is_admin {
+        return next.run(Request::from_parts(parts, body)).await;
+    }
+
+    let mut response = (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(json!({ "error": "maintenance" })),
+    )
+        .into_response();
+    response.headers_mut().insert(
+        header::RETRY_AFTER,
+        HeaderValue::from(state.config.maintenance_retry_after_secs),
+    );
+    response
+}
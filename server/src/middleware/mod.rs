@@ -1,2 +1,8 @@
 pub mod auth;
-pub mod error;
\ No newline at end of file
+pub mod cors;
+pub mod error;
+pub mod maintenance;
+pub mod rate_limit;
+pub mod read_only;
+pub mod security_headers;
+pub mod transaction;
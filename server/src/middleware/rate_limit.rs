@@ -0,0 +1,91 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{Request, State},
+    http::HeaderValue,
+    middleware::Next,
+    response::Response,
+};
+
+use crate::{state::AppState, utils::client_ip::ClientIp};
+
+/// Budget left for one client in the current window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitStatus {
+    pub limit: u64,
+    pub remaining: u64,
+    pub reset_secs: u64,
+}
+
+/// Fixed-window request counter keyed by client.
+///
+/// This is a *soft* limiter: it only reports the budget, it never rejects.
+/// A `limit` of `0` disables it.
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: u64,
+    window: Duration,
+    windows: Mutex<HashMap<String, (Instant, u64)>>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: u64, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Count one request for `key` and return the remaining budget,
+    /// or `None` when limiting is disabled.
+    pub fn hit(&self, key: &str) -> Option<RateLimitStatus> {
+        if self.limit == 0 {
+            return None;
+        }
+
+        let now = Instant::now();
+        let mut windows = self.windows.lock().unwrap();
+
+        // 🧹 Forget clients whose window has already closed
+        windows.retain(|_, (started, _)| now.duration_since(*started) < self.window);
+
+        let (started, count) = windows.entry(key.to_string()).or_insert((now, 0));
+        *count += 1;
+
+        let elapsed = now.duration_since(*started);
+        Some(RateLimitStatus {
+            limit: self.limit,
+            remaining: self.limit.saturating_sub(*count),
+            reset_secs: self.window.saturating_sub(elapsed).as_secs(),
+        })
+    }
+}
+
+/// Attach `X-RateLimit-Limit`, `X-RateLimit-Remaining`, and `X-RateLimit-Reset`
+/// to responses, keyed by the client's IP address (see [`ClientIp`], which
+/// only honours forwarding headers from trusted proxies).
+pub async fn rate_limit_headers(
+    State(state): State<AppState>,
+    client_ip: ClientIp,
+    request: Request,
+    next: Next,
+) -> Response {
+    let key = client_ip.to_string();
+
+    let status = state.rate_limiter.hit(&key);
+    let mut response = next.run(request).await;
+
+    if let Some(status) = status {
+        let headers = response.headers_mut();
+        headers.insert("x-ratelimit-limit", HeaderValue::from(status.limit));
+        headers.insert("x-ratelimit-remaining", HeaderValue::from(status.remaining));
+        headers.insert("x-ratelimit-reset", HeaderValue::from(status.reset_secs));
+    }
+
+    response
+}
@@ -0,0 +1,45 @@
+use axum::{
+    Json,
+    extract::{Request, State},
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde_json::json;
+
+use crate::state::AppState;
+
+/// Writes that still go through in read-only mode, besides the admin API
+/// (kept open so a demo can still be reset).
+const ALLOWED: &[(Method, &str)] = &[(Method::POST, "/auth/login")];
+
+fn allowed(method: &Method, path: &str) -> bool {
+    path == "/admin"
+        || path.starts_with("/admin/")
+        || ALLOWED.iter().any(|(m, p)| m == method && *p == path)
+}
+
+/// Refuse writes with `403 FORBIDDEN` when `read_only` is set.
+///
+/// `POST`, `PUT`, `PATCH`, and `DELETE` are refused with
+/// `{ "error": "read_only_mode" }` unless allow-listed above; every other
+/// method passes through.
+pub async fn read_only_mode(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let writes = matches!(
+        *request.method(),
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    );
+    if !state.config.read_only || !writes || allowed(request.method(), request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    (
+        StatusCode::FORBIDDEN,
+        Json(json!({ "error": "read_only_mode" })),
+    )
+        .into_response()
+}
@@ -0,0 +1,52 @@
+use axum::{
+    extract::{Request, State},
+    http::{HeaderName, HeaderValue, header},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::state::AppState;
+
+/// `Content-Security-Policy` used when `CONTENT_SECURITY_POLICY` is unset.
+///
+/// The API only serves JSON, so nothing may be loaded or framed.
+pub const DEFAULT_CSP: &str = "default-src 'none'; frame-ancestors 'none'";
+
+/// Headers that are the same on every response.
+const FIXED_HEADERS: [(HeaderName, &str); 3] = [
+    (header::X_CONTENT_TYPE_OPTIONS, "nosniff"),
+    (header::X_FRAME_OPTIONS, "DENY"),
+    (header::REFERRER_POLICY, "no-referrer"),
+];
+
+/// Attach standard security headers to every response.
+///
+/// Sets `X-Content-Type-Options: nosniff`, `X-Frame-Options: DENY`,
+/// `Referrer-Policy: no-referrer`, and `Content-Security-Policy` from
+/// `content_security_policy` (omitted when that is empty). A header the
+/// handler already set is left alone.
+pub async fn security_headers(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+
+    for (name, value) in FIXED_HEADERS {
+        headers
+            .entry(name)
+            .or_insert(HeaderValue::from_static(value));
+    }
+
+    let csp = state.config.content_security_policy.trim();
+    if !csp.is_empty()
+        && let Ok(value) = HeaderValue::from_str(csp)
+    {
+        headers
+            .entry(header::CONTENT_SECURITY_POLICY)
+            .or_insert(value);
+    }
+
+    response
+}
@@ -0,0 +1,148 @@
+use std::{
+    fmt,
+    future::Future,
+    ops::Deref,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    extract::{FromRequestParts, Request},
+    http::{StatusCode, request::Parts},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use futures::future::BoxFuture;
+use sea_orm::{DatabaseTransaction, TransactionTrait};
+
+use crate::{middleware::error::DbError, state::AppState};
+
+/// Side effects waiting for the request's transaction to commit.
+type AfterCommit = Arc<Mutex<Vec<BoxFuture<'static, ()>>>>;
+
+/// Where [`Tx`] leaves the request's transaction, and anything queued with
+/// [`Tx::after_commit`], for [`request_transaction`] to finish once the
+/// handler has returned.
+#[derive(Clone, Default)]
+struct TxSlot {
+    txn: Arc<Mutex<Option<Arc<DatabaseTransaction>>>>,
+    after_commit: AfterCommit,
+}
+
+/// A database transaction scoped to the current request.
+///
+/// Taking `Tx` in a handler opens the transaction; every write made through
+/// it is committed by [`request_transaction`] if the handler answers `2xx`
+/// and rolled back otherwise, so a multi-step write can simply bail out
+/// with an error response. Requests whose handler never asks for a `Tx`
+/// don't open one. Extracting it twice in one request yields the same
+/// transaction.
+///
+/// # Example
+/// ```rust,ignore
+/// pub async fn create(tx: Tx, Json(body): Json<NewThing>) -> Response {
+///     let thing = match body.into_active_model().insert(&*tx).await {
+///         Ok(thing) => thing,
+///         Err(e) => return DbError(e).into_response(), // rolled back
+///     };
+///     Json(thing).into_response() // committed
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Tx {
+    txn: Arc<DatabaseTransaction>,
+    after_commit: AfterCommit,
+}
+
+impl Tx {
+    /// Run `effect` once the transaction has committed.
+    ///
+    /// For side effects that must not escape a rollback, such as emails or
+    /// published events about the rows just written. Queued effects run in
+    /// order, after the commit and before the response goes out; they are
+    /// dropped if the request rolls back or the commit fails.
+    pub fn after_commit(&self, effect: impl Future<Output = ()> + Send + 'static) {
+        self.after_commit.lock().unwrap().push(Box::pin(effect));
+    }
+}
+
+impl fmt::Debug for Tx {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tx").finish_non_exhaustive()
+    }
+}
+
+impl Deref for Tx {
+    type Target = DatabaseTransaction;
+
+    fn deref(&self) -> &DatabaseTransaction {
+        &self.txn
+    }
+}
+
+impl FromRequestParts<AppState> for Tx {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Response> {
+        let Some(slot) = parts.extensions.get::<TxSlot>().cloned() else {
+            tracing::error!("Tx extracted on a route without request_transaction");
+            return Err(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+        };
+        let tx = |txn: &Arc<DatabaseTransaction>| Tx {
+            txn: Arc::clone(txn),
+            after_commit: Arc::clone(&slot.after_commit),
+        };
+
+        if let Some(txn) = slot.txn.lock().unwrap().as_ref() {
+            return Ok(tx(txn));
+        }
+
+        let txn = Arc::new(
+            state
+                .db
+                .begin()
+                .await
+                .map_err(|e| DbError(e).into_response())?,
+        );
+        *slot.txn.lock().unwrap() = Some(Arc::clone(&txn));
+        Ok(tx(&txn))
+    }
+}
+
+/// Commit or roll back the transaction a handler opened through [`Tx`].
+///
+/// A `2xx` response commits; anything else rolls back. If the commit
+/// itself fails the client gets the database error instead of the
+/// handler's response, since nothing it reported was saved. Effects queued
+/// with [`Tx::after_commit`] run only once the commit has succeeded.
+pub async fn request_transaction(mut request: Request, next: Next) -> Response {
+    let slot = TxSlot::default();
+    request.extensions_mut().insert(slot.clone());
+
+    let response = next.run(request).await;
+
+    let Some(txn) = slot.txn.lock().unwrap().take() else {
+        return response;
+    };
+    let Ok(txn) = Arc::try_unwrap(txn) else {
+        // A clone outlived the handler; dropping the last one rolls back
+        tracing::error!("request transaction still in use after the handler returned");
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+
+    if !response.status().is_success() {
+        if let Err(e) = txn.rollback().await {
+            tracing::warn!(error = %e, "request transaction rollback failed");
+        }
+        return response;
+    }
+    if let Err(e) = txn.commit().await {
+        return DbError(e).into_response();
+    }
+
+    // 📣 Only now is it safe to tell anyone about the writes
+    let effects = std::mem::take(&mut *slot.after_commit.lock().unwrap());
+    for effect in effects {
+        effect.await;
+    }
+    response
+}
@@ -0,0 +1,45 @@
+use chrono::NaiveDateTime;
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "login_event")]
+/// One attempt to log in to an existing account.
+///
+/// # Fields
+/// - `id`: The unique identifier for the event. This is the primary key.
+/// - `user_id`: The account someone tried to log in to.
+/// - `ip`: The client IP, as resolved by `ClientIp`; `None` if unknown.
+/// - `user_agent`: The `User-Agent` header sent, if any.
+/// - `success`: Whether a token was issued.
+/// - `created_at`: When the attempt was made (naive UTC).
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub user_id: i32,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub ip: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub user_agent: Option<String>,
+    pub success: bool,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
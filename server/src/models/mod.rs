@@ -2,7 +2,11 @@
 
 pub mod prelude;
 
+pub mod login_event;
+pub mod password_reset;
 pub mod tag;
+pub mod tag_subscription;
 pub mod ticket;
 pub mod ticket_tag;
+pub mod ticket_tag_event;
 pub mod user;
@@ -0,0 +1,44 @@
+use chrono::NaiveDateTime;
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "password_reset")]
+/// A pending or spent password reset token.
+///
+/// # Fields
+/// - `id`: The unique identifier for the reset. This is the primary key.
+/// - `user_id`: The user whose password the token may change.
+/// - `token_hash`: SHA-256 of the token mailed to the user; the token itself is never stored.
+/// - `expires_at`: When the token stops working (naive UTC).
+/// - `used_at`: When the token was redeemed; `None` while it is still usable.
+/// - `created_at`: When the reset was requested.
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub user_id: i32,
+    #[sea_orm(column_type = "Text", unique)]
+    pub token_hash: String,
+    pub expires_at: NaiveDateTime,
+    pub used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -11,21 +11,43 @@ use serde::{Deserialize, Serialize};
 /// # Fields
 /// - `id`: The unique identifier for the tag. This is the primary key.
 /// - `name`: The name of the tag, stored as text.
+/// - `description`: Free-form notes on when to use the tag. Optional.
+/// - `owner_id`: The agent who owns the tag; `None` for a global tag.
 /// - `created_at`: The timestamp when the tag was created. Optional.
 /// - `updated_at`: The timestamp when the tag was last updated. Optional.
+/// - `deleted_at`: When the tag was soft-deleted; `None` for live tags.
+///
+/// Timestamps are stored as naive UTC and serialized as RFC 3339 with a `Z`
+/// suffix (see [`crate::utils::time`]).
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     #[sea_orm(column_type = "Text")]
     pub name: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub owner_id: Option<i32>,
+    #[serde(default, with = "crate::utils::time::rfc3339")]
     pub created_at: Option<NaiveDateTime>,
+    #[serde(default, with = "crate::utils::time::rfc3339")]
     pub updated_at: Option<NaiveDateTime>,
+    #[serde(default, with = "crate::utils::time::rfc3339")]
+    pub deleted_at: Option<NaiveDateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     #[sea_orm(has_many = "super::ticket_tag::Entity")]
     TicketTag,
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::OwnerId",
+        to = "super::user::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    Owner,
 }
 
 impl Related<super::ticket_tag::Entity> for Entity {
@@ -13,9 +13,14 @@ use serde::Serialize;
 /// - `title`: The title of the ticket.
 /// - `description`: An optional description of the ticket.
 /// - `status`: An optional status of the ticket.
-/// - `user_id`: The optional ID of the user associated with the ticket.
+/// - `user_id`: The optional ID of the user the ticket is assigned to.
+/// - `created_by`: The optional ID of the user who created the ticket.
+/// - `updated_by`: The optional ID of the user who last updated the ticket.
 /// - `created_at`: The optional timestamp when the ticket was created.
 /// - `updated_at`: The optional timestamp when the ticket was last updated.
+///
+/// Timestamps are stored as naive UTC and serialized as RFC 3339 with a `Z`
+/// suffix (see [`crate::utils::time`]).
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
@@ -26,7 +31,11 @@ pub struct Model {
     #[sea_orm(column_type = "Text", nullable)]
     pub status: Option<String>,
     pub user_id: Option<i32>,
+    pub created_by: Option<i32>,
+    pub updated_by: Option<i32>,
+    #[serde(serialize_with = "crate::utils::time::rfc3339::serialize")]
     pub created_at: Option<NaiveDateTime>,
+    #[serde(serialize_with = "crate::utils::time::rfc3339::serialize")]
     pub updated_at: Option<NaiveDateTime>,
 }
 
@@ -0,0 +1,78 @@
+use chrono::NaiveDateTime;
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "ticket_tag_event")]
+/// One tag being attached to or detached from a ticket.
+///
+/// # Fields
+/// - `id`: The unique identifier for the event. This is the primary key.
+/// - `ticket_id`: The ticket whose tags changed.
+/// - `tag_id`: The tag that was attached or detached.
+/// - `user_id`: Who made the change; `None` once that user is deleted.
+/// - `action`: `attached` or `detached` (see [`TagAction`]).
+/// - `created_at`: When the change happened (naive UTC).
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub ticket_id: i32,
+    pub tag_id: i32,
+    pub user_id: Option<i32>,
+    #[sea_orm(column_type = "Text")]
+    pub action: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::ticket::Entity",
+        from = "Column::TicketId",
+        to = "super::ticket::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Ticket,
+    #[sea_orm(
+        belongs_to = "super::tag::Entity",
+        from = "Column::TagId",
+        to = "super::tag::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Tag,
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    User,
+}
+
+impl Related<super::tag::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Tag.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// What happened to the tag, stored as lowercase text in `ticket_tag_event.action`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TagAction {
+    Attached,
+    Detached,
+}
+
+impl TagAction {
+    /// The value stored in the `action` column.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TagAction::Attached => "attached",
+            TagAction::Detached => "detached",
+        }
+    }
+}
@@ -14,6 +14,10 @@ use sea_orm::entity::prelude::*;
 /// - `password`: The user's hashed password.
 /// - `role`: The user's role (e.g., admin, user).
 /// - `created_at`: The timestamp when the user was created.
+/// - `tokens_valid_after`: Tokens issued before this UTC time are rejected (force-logout).
+/// - `email_verified`: Whether the user has followed their verification link.
+/// - `email_verification_token`: SHA-256 of the pending verification token, if any.
+/// - `is_active`: Whether the user may log in; cleared to disable an account without deleting it.
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
@@ -26,6 +30,13 @@ pub struct Model {
     #[sea_orm(column_type = "Text")]
     pub role: String,
     pub created_at: Option<NaiveDateTime>,
+    pub tokens_valid_after: Option<NaiveDateTime>,
+    #[sea_orm(default_value = true)]
+    pub email_verified: bool,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub email_verification_token: Option<String>,
+    #[sea_orm(default_value = true)]
+    pub is_active: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -41,3 +52,56 @@ impl Related<super::ticket::Entity> for Entity {
 }
 
 impl ActiveModelBehavior for ActiveModel {}
+
+/// Roles a user can hold, stored as lowercase text in `user.role`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    Admin,
+    #[default]
+    Agent,
+}
+
+impl Role {
+    /// Read a stored or requested role; `None` for anything else.
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim() {
+            "admin" => Some(Role::Admin),
+            "agent" => Some(Role::Agent),
+            _ => None,
+        }
+    }
+
+    /// The value stored in the `role` column.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::Agent => "agent",
+        }
+    }
+}
+
+/// A user as exposed by the API, without the password hash.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+pub struct UserPublic {
+    pub id: i32,
+    pub email: String,
+    pub name: String,
+    pub role: String,
+    #[serde(serialize_with = "crate::utils::time::rfc3339::serialize")]
+    pub created_at: Option<NaiveDateTime>,
+    pub is_active: bool,
+}
+
+impl From<Model> for UserPublic {
+    fn from(user: Model) -> Self {
+        Self {
+            id: user.id,
+            email: user.email,
+            name: user.name,
+            role: user.role,
+            created_at: user.created_at,
+            is_active: user.is_active,
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Email alerts for users subscribed to a tag.
+//!
+//! When a tag lands on a ticket, everyone following that tag who may see
+//! the ticket gets a message through [`AppState::email`]. Delivery problems
+//! are logged and never fail the request that attached the tag.
+
+use std::collections::BTreeSet;
+
+use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter};
+
+use crate::{
+    models::{
+        tag, tag_subscription, ticket,
+        user::{self, Role},
+    },
+    state::AppState,
+};
+
+/// Email the subscribers of `tag_ids` that `ticket` now carries those tags.
+///
+/// Subscribers are filtered with the same rule as `get_tickets`: admins
+/// hear about every ticket, everyone else only about tickets assigned to
+/// them. The actor who attached the tags and deactivated accounts are
+/// skipped, and a subscriber following several of the tags gets one email.
+///
+/// # Returns
+/// - The number of emails handed to the sender
+/// - `Err(DbErr)` if the subscribers couldn't be loaded
+pub async fn notify_tag_subscribers<C: ConnectionTrait>(
+    state: &AppState,
+    db: &C,
+    ticket: &ticket::Model,
+    tag_ids: &[i32],
+    actor_id: i32,
+) -> Result<usize, DbErr> {
+    if tag_ids.is_empty() {
+        return Ok(0);
+    }
+
+    // 🔍 Subscriptions on these tags, with the subscriber and tag loaded
+    let subscriptions = tag_subscription::Entity::find()
+        .filter(tag_subscription::Column::TagId.is_in(tag_ids.iter().copied()))
+        .filter(tag_subscription::Column::UserId.ne(actor_id))
+        .all(db)
+        .await?;
+    if subscriptions.is_empty() {
+        return Ok(0);
+    }
+    let user_ids: BTreeSet<i32> = subscriptions.iter().map(|s| s.user_id).collect();
+    let users = user::Entity::find()
+        .filter(user::Column::Id.is_in(user_ids))
+        .filter(user::Column::IsActive.eq(true))
+        .all(db)
+        .await?;
+    let tags = tag::Entity::find()
+        .filter(tag::Column::Id.is_in(tag_ids.iter().copied()))
+        .all(db)
+        .await?;
+
+    // 📧 One message per visible subscriber, naming the tags they follow
+    let mut sent = 0;
+    for user in users {
+        let visible = user.role == Role::Admin.as_str() || ticket.user_id == Some(user.id);
+        if !visible {
+            continue;
+        }
+        let followed: Vec<&str> = tags
+            .iter()
+            .filter(|t| {
+                subscriptions
+                    .iter()
+                    .any(|s| s.user_id == user.id && s.tag_id == t.id)
+            })
+            .map(|t| t.name.as_str())
+            .collect();
+        if followed.is_empty() {
+            continue;
+        }
+        let subject = format!("Ticket #{} tagged {}", ticket.id, followed.join(", "));
+        let body = format!(
+            "Ticket #{} \"{}\" was tagged {}, which you follow.",
+            ticket.id,
+            ticket.title,
+            followed.join(", ")
+        );
+        match state.email.send(&user.email, &subject, &body).await {
+            Ok(()) => sent += 1,
+            Err(e) => tracing::warn!(user_id = user.id, error = %e, "tag notification not sent"),
+        }
+    }
+    Ok(sent)
+}
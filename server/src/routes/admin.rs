@@ -8,12 +8,60 @@
 /// Returns an error if the database reset operation fails.
 ///
 /// # Example
-/// ```
+/// ```rust,ignore
 /// reset_db();
 /// ```
-use crate::handlers::admin::reset_db;
-use axum::{Router, routing::post};
+use crate::handlers::admin::{
+    bulk_delete_tickets, check_integrity, cleanup_orphans, export_tags, get_dashboard, get_job,
+    get_maintenance, get_ticket_counts_by_user, get_user_ticket_activity, impersonate_user,
+    import_tags, logout_all, reassign_tickets, reset_db, reset_user_password, set_maintenance,
+    set_user_active, update_user_role,
+};
+use crate::state::AppState;
+use axum::{
+    Router,
+    routing::{get, post, put},
+};
 
-pub fn routes() -> Router {
-    Router::new().route("/reset-db", post(reset_db))
+/// Admin routes, nested under `/admin`.
+///
+/// - `POST /dev/reset-db`: Wipe and reseed the database (`?async=true` to run it as a job).
+/// - `GET /dev/jobs/{id}`: Poll a background job.
+/// - `PUT /users/{id}/role`: Change a user's role.
+/// - `PUT /users/{id}/active`: Enable or disable a user's account.
+/// - `POST /users/{id}/logout-all`: Revoke every token issued to a user.
+/// - `POST /users/{id}/reset-password`: Set or generate a new password and revoke old tokens.
+/// - `POST /users/{id}/impersonate`: Get a short-lived token that acts as the user.
+/// - `GET /users/{id}/activity/tickets`: Tickets the user updated most recently.
+/// - `POST /users/{from}/reassign-to/{into}`: Move all of one user's tickets to another.
+/// - `POST /tickets/bulk-delete`: Delete every ticket matching a filter (requires `confirm: true`).
+/// - `GET /tags/export`: Dump every tag as a JSON backup.
+/// - `POST /tags/import`: Upsert tags by name from such a backup.
+/// - `GET /integrity`: Report orphaned relations, ownerless tickets, and duplicate tag names.
+/// - `POST /integrity/cleanup`: Delete orphaned `ticket_tag` rows.
+/// - `GET /dashboard`: System-wide counts for the admin dashboard.
+/// - `GET /stats/by-user`: Tickets created per user, busiest first.
+/// - `GET /maintenance`, `PUT /maintenance`: Read or switch maintenance mode.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/dev/reset-db", post(reset_db))
+        .route("/dev/jobs/{id}", get(get_job))
+        .route("/users/{id}/role", put(update_user_role))
+        .route("/users/{id}/active", put(set_user_active))
+        .route("/users/{id}/logout-all", post(logout_all))
+        .route("/users/{id}/reset-password", post(reset_user_password))
+        .route("/users/{id}/impersonate", post(impersonate_user))
+        .route(
+            "/users/{id}/activity/tickets",
+            get(get_user_ticket_activity),
+        )
+        .route("/users/{from}/reassign-to/{into}", post(reassign_tickets))
+        .route("/tickets/bulk-delete", post(bulk_delete_tickets))
+        .route("/tags/export", get(export_tags))
+        .route("/tags/import", post(import_tags))
+        .route("/integrity", get(check_integrity))
+        .route("/integrity/cleanup", post(cleanup_orphans))
+        .route("/dashboard", get(get_dashboard))
+        .route("/stats/by-user", get(get_ticket_counts_by_user))
+        .route("/maintenance", get(get_maintenance).put(set_maintenance))
 }
@@ -1,7 +1,11 @@
 use crate::handlers::{
-    auth::{login_user, me, register_user},
-    ticket::get_tickets,
+    auth::{
+        forgot_password, get_my_logins, login_user, me, register_user, reset_password, verify_email,
+    },
+    tag::get_my_tags,
+    ticket::{get_my_ticket_stats, get_tickets},
 };
+use crate::state::AppState;
 use axum::{
     Router,
     routing::{get, post},
@@ -14,6 +18,50 @@ pub struct LoginRequest {
     pub password: String,
 }
 
+/// How `login_user` hands the token back.
+///
+/// - `body`: As JSON, for API clients (the default).
+/// - `cookie`: As an `HttpOnly` cookie, for browsers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoginMode {
+    #[default]
+    Body,
+    Cookie,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct LoginQuery {
+    pub mode: Option<LoginMode>,
+}
+
+/// Query parameters accepted by `register_user`.
+#[derive(Debug, Default, Deserialize)]
+pub struct RegisterQuery {
+    /// Return the existing user instead of `409` when the email is taken.
+    #[serde(default)]
+    pub upsert: bool,
+}
+
+/// `?token=` on the link sent to verify an email address.
+#[derive(Debug, Deserialize)]
+pub struct VerifyEmailQuery {
+    pub token: String,
+}
+
+/// Body of `POST /auth/forgot-password`.
+#[derive(Debug, Deserialize)]
+pub struct ForgotPasswordRequest {
+    pub email: String,
+}
+
+/// Body of `POST /auth/reset-password`.
+#[derive(Debug, Deserialize)]
+pub struct ResetPasswordRequest {
+    pub token: String,
+    pub new_password: String,
+}
+
 #[derive(Serialize)]
 pub struct LoginResponse {
     pub token: String,
@@ -31,13 +79,19 @@ pub struct RegisterRequest {
     pub email: String,
     pub name: String,
     pub password: String,
-    pub role: String, // "agent" or "admin"
+    pub role: Option<String>, // "agent" or "admin"; defaults to `Config::default_role`
 }
 
-pub fn routes() -> Router {
+pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/register", post(register_user))
         .route("/login", post(login_user))
+        .route("/verify-email", get(verify_email))
+        .route("/forgot-password", post(forgot_password))
+        .route("/reset-password", post(reset_password))
         .route("/me", get(me))
+        .route("/me/stats", get(get_my_ticket_stats))
+        .route("/me/tags", get(get_my_tags))
+        .route("/me/logins", get(get_my_logins))
         .route("/", get(get_tickets))
 }
@@ -3,12 +3,81 @@
 /// This module imports necessary components from the Axum framework,
 /// specifically the `get` routing method and the `Router` type,
 /// to set up HTTP GET endpoints for health checks.
-use axum::{Router, routing::get};
+use axum::{
+    Json, Router,
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use sea_orm_migration::MigratorTrait;
+use serde::Serialize;
 
-pub fn routes() -> Router {
-    Router::new().route("/", get(health_check))
+use crate::{db::migration::Migrator, middleware::error::DbError, state::AppState};
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(health_check))
+        .route("/version", get(version))
+        .route("/ready", get(ready))
 }
 
 async fn health_check() -> &'static str {
     "✅ TagBlaze is healthy!"
 }
+
+/// Build metadata baked in at compile time (see `build.rs`).
+#[derive(Debug, Serialize)]
+pub struct VersionInfo {
+    pub version: &'static str,
+    pub git_sha: &'static str,
+    pub built_at: &'static str,
+}
+
+/// The version of this binary.
+pub const VERSION_INFO: VersionInfo = VersionInfo {
+    version: env!("CARGO_PKG_VERSION"),
+    git_sha: env!("TAGBLAZE_GIT_SHA"),
+    built_at: env!("TAGBLAZE_BUILT_AT"),
+};
+
+/// Report which build is running, so deploys can be confirmed.
+///
+/// # Returns
+/// - `200 OK` with `{ "version", "git_sha", "built_at" }`
+async fn version() -> Json<VersionInfo> {
+    Json(VERSION_INFO)
+}
+
+/// Whether the database schema matches what this binary expects.
+#[derive(Debug, Serialize)]
+pub struct Readiness {
+    pub up_to_date: bool,
+    pub pending: Vec<String>,
+}
+
+/// Compare applied migrations with the ones built into this binary.
+///
+/// Catches deploys that forgot to migrate. Checking creates the
+/// `seaql_migrations` bookkeeping table if it doesn't exist yet.
+///
+/// # Returns
+/// - `200 OK` with `{ "up_to_date": true, "pending": [] }`
+/// - `503 SERVICE_UNAVAILABLE` with `up_to_date: false` and the names of pending migrations
+/// - `500 INTERNAL_SERVER_ERROR` if the migration table can't be read
+async fn ready(State(state): State<AppState>) -> Response {
+    let pending = match Migrator::get_pending_migrations(&state.db).await {
+        Ok(pending) => pending,
+        Err(e) => return DbError(e).into_response(),
+    };
+    let readiness = Readiness {
+        up_to_date: pending.is_empty(),
+        pending: pending.iter().map(|m| m.name().to_string()).collect(),
+    };
+    let status = if readiness.up_to_date {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(readiness)).into_response()
+}
@@ -12,19 +12,52 @@ pub mod ticket;
 /// server architectures.
 ///
 /// # Example
-/// ```rust
+/// ```rust,ignore
 /// use axum::Router;
 ///
 /// let app = Router::new();
 /// ```
-use axum::Router;
+use axum::{
+    Router,
+    middleware::{from_fn, from_fn_with_state},
+};
 
-pub fn create_router() -> Router {
-    Router::new()
+use crate::{
+    handlers::fallback::{method_not_allowed, not_found},
+    middleware::{
+        cors::{cors_layer, plain_options},
+        maintenance::maintenance_mode,
+        rate_limit::rate_limit_headers,
+        read_only::read_only_mode,
+        security_headers::security_headers,
+        transaction::request_transaction,
+    },
+    state::AppState,
+};
+
+/// Build the full application router and attach the shared [`AppState`].
+pub fn create_router(state: AppState) -> Router {
+    let cors = cors_layer(&state.config);
+
+    let api = Router::new()
         .nest("/health", health::routes())
-        .nest("/auth", auth::routes())
+        .nest(
+            "/auth",
+            auth::routes().route_layer(from_fn_with_state(state.clone(), rate_limit_headers)),
+        )
         .nest("/tickets", ticket::routes())
         .nest("/tags", tag::routes())
         .nest("/relations", relations::routes())
-        .nest("/admin/dev", admin::routes())
+        .nest("/admin", admin::routes())
+        .route_layer(from_fn(request_transaction))
+        .fallback(not_found)
+        .method_not_allowed_fallback(method_not_allowed)
+        .layer(from_fn_with_state(state.clone(), read_only_mode))
+        .layer(from_fn_with_state(state.clone(), maintenance_mode))
+        .with_state(state.clone());
+
+    api.clone()
+        .layer(cors)
+        .layer(from_fn_with_state(api, plain_options))
+        .layer(from_fn_with_state(state, security_headers))
 }
@@ -3,17 +3,22 @@
 /// - `attach_tag`: Attaches a tag to a ticket.
 /// - `detach_tag`: Detaches a tag from a ticket.
 /// - `get_tags_for_ticket`: Retrieves all tags associated with a specific ticket.
-use crate::handlers::relations::{attach_tag, detach_tag, get_tags_for_ticket};
+/// - `get_tags_for_tickets`: Retrieves the tags of several tickets in one query.
+use crate::handlers::relations::{
+    attach_tag, detach_tag, get_tags_for_ticket, get_tags_for_tickets,
+};
+use crate::state::AppState;
 use axum::{
     Router,
     routing::{get, post},
 };
 
-pub fn routes() -> Router {
+pub fn routes() -> Router<AppState> {
     Router::new()
         .route(
             "/{ticket_id}/tags/{tag_id}",
             post(attach_tag).delete(detach_tag),
         )
         .route("/{ticket_id}/tags", get(get_tags_for_ticket))
+        .route("/tags/batch", post(get_tags_for_tickets))
 }
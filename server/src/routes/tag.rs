@@ -6,21 +6,46 @@
 /// - `get_tag_by_id`: Fetches a tag by its unique identifier.
 /// - `update_tag_by_id`: Updates an existing tag by its ID.
 /// - `delete_tag_by_id`: Deletes a tag by its ID.
+/// - `merge_tags`: Merges a duplicate tag into another (admin only).
+/// - `count_tickets_for_tag`: Counts the tickets carrying a tag.
+/// - `get_tag_summary`: Returns a tag with its tickets counted by status.
+/// - `get_tag_trend`: Counts how often a tag was attached, per day or week.
+/// - `batch_create_tags`: Creates many tags at once, skipping duplicates.
+/// - `restore_tag`: Restores a soft-deleted tag (admin only).
+/// - `get_tag_management`: Lists every tag with its usage (admin only).
+/// - `validate_tag_ids`: Reports which of a batch of tag IDs exist.
+/// - `subscribe_to_tag` / `unsubscribe_from_tag`: Follow or stop following a tag.
 use crate::handlers::tag::{
-    create_tag, delete_tag_by_id, get_tag_by_id, get_tags, update_tag_by_id,
+    batch_create_tags, count_tickets_for_tag, create_tag, delete_tag_by_id, get_tag_by_id,
+    get_tag_management, get_tag_summary, get_tag_trend, get_tags, merge_tags, restore_tag,
+    subscribe_to_tag, unsubscribe_from_tag, update_tag_by_id, validate_tag_ids,
 };
+use crate::state::AppState;
 use axum::{
     Router,
     routing::{get, post},
 };
 
-pub fn routes() -> Router {
+pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/", post(create_tag).get(get_tags))
+        .route("/batch", post(batch_create_tags))
+        .route("/merge", post(merge_tags))
+        .route("/validate", post(validate_tag_ids))
+        .route("/manage", get(get_tag_management))
         .route(
             "/{id}",
             get(get_tag_by_id)
                 .put(update_tag_by_id)
+                .patch(update_tag_by_id)
                 .delete(delete_tag_by_id),
         )
+        .route("/{id}/restore", post(restore_tag))
+        .route(
+            "/{id}/subscribe",
+            post(subscribe_to_tag).delete(unsubscribe_from_tag),
+        )
+        .route("/{id}/tickets/count", get(count_tickets_for_tag))
+        .route("/{id}/summary", get(get_tag_summary))
+        .route("/{id}/trend", get(get_tag_trend))
 }
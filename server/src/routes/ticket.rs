@@ -6,21 +6,48 @@
 /// - `get_ticket_by_id`: Fetches a ticket by its unique identifier.
 /// - `delete_ticket_by_id`: Deletes a ticket by its unique identifier.
 /// - `update_ticket_by_id`: Updates a ticket by its unique identifier.
+/// - `ticket_stream`: Streams ticket changes as server-sent events.
+/// - `get_ticket_statuses`: Lists the distinct statuses in use, with counts.
+/// - `download_ticket`: Downloads a ticket and its tags as a JSON attachment.
+/// - `get_available_tags`: Lists all tags, flagging those attached to a ticket.
+/// - `get_ticket_feed`: Lists recently updated tickets, newest first.
+/// - `bulk_update_status`: Sets the status of many tickets, reporting each one.
+/// - `get_tickets_in_order`: Fetches tickets in a caller-given ID order.
+/// - `get_tag_history`: Lists a ticket's tag attach/detach events, oldest first.
+/// - `get_timeline`: Merges a ticket's creation and tag history into one list.
+/// - `export_ticket_bundle` / `import_ticket_bundle`: Move a ticket, its tags, and
+///   its tag history between servers as a portable JSON bundle.
+use crate::handlers::bundle::{export_ticket_bundle, import_ticket_bundle};
+use crate::handlers::relations::{get_tag_history, get_timeline};
 use crate::handlers::ticket::{
-    create_ticket, delete_ticket_by_id, get_ticket_by_id, get_tickets, update_ticket_by_id,
+    bulk_update_status, create_ticket, delete_ticket_by_id, download_ticket, get_available_tags,
+    get_ticket_by_id, get_ticket_feed, get_ticket_statuses, get_tickets, get_tickets_in_order,
+    ticket_stream, update_ticket_by_id,
 };
+use crate::state::AppState;
 use axum::{
     Router,
     routing::{get, post},
 };
 
-pub fn routes() -> Router {
+pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/", post(create_ticket).get(get_tickets))
+        .route("/stream", get(ticket_stream))
+        .route("/statuses", get(get_ticket_statuses))
+        .route("/feed", get(get_ticket_feed))
+        .route("/bulk-status", post(bulk_update_status))
+        .route("/ordered", post(get_tickets_in_order))
+        .route("/import", post(import_ticket_bundle))
         .route(
             "/{id}",
             get(get_ticket_by_id)
                 .delete(delete_ticket_by_id)
                 .put(update_ticket_by_id),
         )
+        .route("/{id}/download", get(download_ticket))
+        .route("/{id}/tags/available", get(get_available_tags))
+        .route("/{id}/tag-history", get(get_tag_history))
+        .route("/{id}/timeline", get(get_timeline))
+        .route("/{id}/bundle", get(export_ticket_bundle))
 }
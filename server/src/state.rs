@@ -0,0 +1,83 @@
+use std::{sync::Arc, time::Duration};
+
+use sea_orm::DatabaseConnection;
+use tokio::sync::broadcast;
+
+use crate::{
+    cache::TagCache,
+    config::Config,
+    email::{EmailSender, LoggingEmailSender},
+    events::TicketEvent,
+    jobs::JobRegistry,
+    metrics::Metrics,
+    middleware::{maintenance::Maintenance, rate_limit::RateLimiter},
+};
+
+/// Shared state handed to every handler through `State<AppState>`.
+///
+/// Cloning is cheap: the connection is a pool handle and the rest is
+/// reference-counted.
+///
+/// # Fields
+/// - `db`: Pooled database connection used by all handlers.
+/// - `read_db`: Pool for read-only listings and stats (`get_tickets`, `get_tags`,
+///   counts); the same pool as `db` unless replaced with [`AppState::with_read_db`].
+/// - `config`: Configuration resolved at startup.
+/// - `events`: Broadcast channel for live ticket updates.
+/// - `metrics`: Counters such as `login_failures_total`.
+/// - `rate_limiter`: Per-client request counter behind the `X-RateLimit-*` headers.
+/// - `tag_cache`: Cached tag list served by `GET /tags`.
+/// - `jobs`: Background jobs such as an asynchronous database reset.
+/// - `maintenance`: Runtime maintenance-mode switch, seeded from `maintenance_mode`.
+/// - `email`: Outgoing mail; logs messages unless replaced with [`AppState::with_email_sender`].
+#[derive(Clone)]
+pub struct AppState {
+    pub db: DatabaseConnection,
+    pub read_db: DatabaseConnection,
+    pub config: Arc<Config>,
+    pub events: broadcast::Sender<TicketEvent>,
+    pub metrics: Arc<Metrics>,
+    pub rate_limiter: Arc<RateLimiter>,
+    pub tag_cache: Arc<TagCache>,
+    pub jobs: Arc<JobRegistry>,
+    pub maintenance: Arc<Maintenance>,
+    pub email: Arc<dyn EmailSender>,
+}
+
+impl AppState {
+    /// Bundle a database connection and configuration into application state.
+    pub fn new(db: DatabaseConnection, config: Config) -> Self {
+        let rate_limiter = RateLimiter::new(
+            config.rate_limit_requests,
+            Duration::from_secs(config.rate_limit_window_secs),
+        );
+        let maintenance = Maintenance::new(config.maintenance_mode);
+        Self {
+            read_db: db.clone(),
+            db,
+            config: Arc::new(config),
+            events: crate::events::channel(),
+            metrics: Arc::new(Metrics::default()),
+            rate_limiter: Arc::new(rate_limiter),
+            tag_cache: Arc::new(TagCache::default()),
+            jobs: Arc::new(JobRegistry::default()),
+            maintenance: Arc::new(maintenance),
+            email: Arc::new(LoggingEmailSender),
+        }
+    }
+
+    /// Serve read-only listings and stats from `read_db`, such as a replica.
+    ///
+    /// Replicas may lag the primary, so a list fetched right after a write
+    /// can briefly miss it.
+    pub fn with_read_db(mut self, read_db: DatabaseConnection) -> Self {
+        self.read_db = read_db;
+        self
+    }
+
+    /// Deliver mail through `sender` instead of the log.
+    pub fn with_email_sender(mut self, sender: Arc<dyn EmailSender>) -> Self {
+        self.email = sender;
+        self
+    }
+}
@@ -0,0 +1,58 @@
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+
+/// What happened to one item of a bulk request.
+///
+/// `status` is the HTTP status the item would have got on its own;
+/// `error` is a short code, present only for failures.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BulkItem {
+    pub id: i32,
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<&'static str>,
+}
+
+impl BulkItem {
+    pub fn ok(id: i32) -> Self {
+        Self {
+            id,
+            status: StatusCode::OK.as_u16(),
+            error: None,
+        }
+    }
+
+    pub fn failed(id: i32, status: StatusCode, error: &'static str) -> Self {
+        Self {
+            id,
+            status: status.as_u16(),
+            error: Some(error),
+        }
+    }
+
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Per-item results shared by bulk endpoints.
+///
+/// Renders as `200 OK` when every item succeeded, otherwise
+/// `207 MULTI_STATUS`, both with the array of [`BulkItem`]s in request order.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BulkResults(pub Vec<BulkItem>);
+
+impl IntoResponse for BulkResults {
+    fn into_response(self) -> Response {
+        let status = if self.0.iter().all(BulkItem::succeeded) {
+            StatusCode::OK
+        } else {
+            StatusCode::MULTI_STATUS
+        };
+        (status, Json(self.0)).into_response()
+    }
+}
@@ -0,0 +1,33 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, NaiveDateTime};
+
+/// Position in a list ordered by `(created_at, id)`, for keyset pagination.
+///
+/// Travels to clients as an opaque URL-safe string (see [`encode`](Self::encode)),
+/// so rows inserted between page loads can't shift later pages the way
+/// they shift an offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    pub created_at: NaiveDateTime,
+    pub id: i32,
+}
+
+impl Cursor {
+    /// The opaque form handed out as `next_cursor`.
+    pub fn encode(&self) -> String {
+        let micros = self.created_at.and_utc().timestamp_micros();
+        URL_SAFE_NO_PAD.encode(format!("{micros}:{}", self.id))
+    }
+
+    /// Read back a cursor produced by [`encode`](Self::encode); `None` if it
+    /// wasn't one.
+    pub fn decode(raw: &str) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(raw.trim()).ok()?;
+        let text = String::from_utf8(bytes).ok()?;
+        let (micros, id) = text.split_once(':')?;
+        Some(Self {
+            created_at: DateTime::from_timestamp_micros(micros.parse().ok()?)?.naive_utc(),
+            id: id.parse().ok()?,
+        })
+    }
+}
@@ -0,0 +1,104 @@
+use axum::{
+    Json,
+    extract::{FromRequestParts, Query},
+    http::{StatusCode, request::Parts},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, NaiveDateTime};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, Select};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::state::AppState;
+
+/// Raw `?created_after=&created_before=` query parameters.
+#[derive(Debug, Default, Deserialize)]
+struct RangeParams {
+    created_after: Option<String>,
+    created_before: Option<String>,
+}
+
+/// Inclusive `created_at` bounds shared by list endpoints.
+///
+/// Extracted from `?created_after=` and `?created_before=`, both RFC 3339
+/// timestamps. They are converted to UTC to match how `created_at` is
+/// stored. An unparsable value or `created_after > created_before` is
+/// rejected with `400 BAD_REQUEST` and `{ "error": "invalid_created_range" }`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CreatedRange {
+    pub after: Option<NaiveDateTime>,
+    pub before: Option<NaiveDateTime>,
+}
+
+impl CreatedRange {
+    /// Parse and validate the raw bounds.
+    pub fn parse(after: Option<&str>, before: Option<&str>) -> Result<Self, String> {
+        let range = Self {
+            after: after.map(parse_bound).transpose()?,
+            before: before.map(parse_bound).transpose()?,
+        };
+        if let (Some(after), Some(before)) = (range.after, range.before)
+            && after > before
+        {
+            return Err("created_after must not be later than created_before".into());
+        }
+        Ok(range)
+    }
+
+    /// Restrict `select` to rows whose `column` falls within the bounds.
+    pub fn apply<E, C>(&self, mut select: Select<E>, column: C) -> Select<E>
+    where
+        E: EntityTrait,
+        C: ColumnTrait,
+    {
+        if let Some(after) = self.after {
+            select = select.filter(column.gte(after));
+        }
+        if let Some(before) = self.before {
+            select = select.filter(column.lte(before));
+        }
+        select
+    }
+
+    /// Whether `value` falls within the bounds, matching [`apply`](Self::apply)
+    /// for rows that are already in memory. A missing value only passes an
+    /// unbounded range.
+    pub fn contains(&self, value: Option<NaiveDateTime>) -> bool {
+        match value {
+            Some(value) => {
+                self.after.is_none_or(|after| value >= after)
+                    && self.before.is_none_or(|before| value <= before)
+            }
+            None => self.after.is_none() && self.before.is_none(),
+        }
+    }
+}
+
+fn parse_bound(raw: &str) -> Result<NaiveDateTime, String> {
+    DateTime::parse_from_rfc3339(raw.trim())
+        .map(|dt| dt.naive_utc())
+        .map_err(|_| format!("'{raw}' is not an RFC 3339 timestamp"))
+}
+
+fn bad_range(details: String) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({ "error": "invalid_created_range", "details": details })),
+    )
+        .into_response()
+}
+
+impl FromRequestParts<AppState> for CreatedRange {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Response> {
+        let Query(params) = Query::<RangeParams>::from_request_parts(parts, state)
+            .await
+            .map_err(|e| bad_range(e.body_text()))?;
+        Self::parse(
+            params.created_after.as_deref(),
+            params.created_before.as_deref(),
+        )
+        .map_err(bad_range)
+    }
+}
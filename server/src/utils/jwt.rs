@@ -1,18 +1,82 @@
-use axum::http::StatusCode;
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
 use chrono::{Duration, Utc};
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use jsonwebtoken::{
+    DecodingKey, EncodingKey, Header, Validation, decode, encode, errors::ErrorKind,
+};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 
 /// The payload structure embedded within a JWT token.
-/// 
+///
 /// - `sub`: Subject identifier (usually a unique user ID or email).
 /// - `exp`: Expiration time as a UNIX timestamp (in seconds).
+/// - `iat`: Issue time as a UNIX timestamp (in seconds); compared against a
+///   user's `tokens_valid_after` to honour force-logouts.
+/// - `impersonated_by`: Email of the admin acting as `sub`, for tokens from
+///   `POST /admin/users/{id}/impersonate`; absent on normal logins.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: String, // Typically the user's email or ID
     pub exp: usize,  // Expiration timestamp (as seconds since epoch)
+    pub iat: usize,  // Issued-at timestamp (as seconds since epoch)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impersonated_by: Option<String>,
 }
 
+/// Why a token was rejected.
+///
+/// Clients should refresh on `Expired` and re-authenticate on `Missing`,
+/// `Invalid`, or `Revoked`. All render as `401 UNAUTHORIZED` with
+/// `{ "error": "token_expired" }`, `{ "error": "token_missing" }`,
+/// `{ "error": "token_invalid" }`, or `{ "error": "token_revoked" }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JwtError {
+    Expired,
+    /// `Bearer` was sent with an empty or whitespace-only token.
+    Missing,
+    Invalid,
+    /// Issued before the user's sessions were force-logged-out.
+    Revoked,
+}
+
+impl JwtError {
+    /// The machine-readable error code sent to clients.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JwtError::Expired => "token_expired",
+            JwtError::Missing => "token_missing",
+            JwtError::Invalid => "token_invalid",
+            JwtError::Revoked => "token_revoked",
+        }
+    }
+}
+
+impl From<jsonwebtoken::errors::Error> for JwtError {
+    fn from(err: jsonwebtoken::errors::Error) -> Self {
+        match err.kind() {
+            ErrorKind::ExpiredSignature => JwtError::Expired,
+            _ => JwtError::Invalid,
+        }
+    }
+}
+
+impl IntoResponse for JwtError {
+    fn into_response(self) -> Response {
+        (
+            StatusCode::UNAUTHORIZED,
+            Json(json!({ "error": self.as_str() })),
+        )
+            .into_response()
+    }
+}
+
+/// How long a login token stays valid.
+pub const TOKEN_TTL: Duration = Duration::hours(24);
+
 /// Creates a JWT token for a given subject (e.g., user ID or email).
 ///
 /// # Arguments
@@ -24,19 +88,49 @@ pub struct Claims {
 /// - `Err(jsonwebtoken::errors::Error)`: If token creation fails.
 ///
 /// # Example
-/// ```rust
+/// ```rust,ignore
 /// let token = create_jwt("user@example.com", "my-secret-key")?;
 /// ```
 pub fn create_jwt(sub: &str, secret: &str) -> Result<String, jsonwebtoken::errors::Error> {
-    // Set token expiration to 24 hours from now
-    let expiration = Utc::now()
-        .checked_add_signed(Duration::hours(24))
+    sign(sub, None, TOKEN_TTL, secret)
+}
+
+/// Creates a short-lived JWT that lets an admin act as `sub`.
+///
+/// The token authenticates as `sub` like any other, but carries
+/// `impersonated_by` so every request made with it can be traced back to
+/// the admin.
+///
+/// # Example
+/// ```rust,ignore
+/// let token = create_impersonation_jwt("user@example.com", "admin@example.com", Duration::minutes(15), secret)?;
+/// ```
+pub fn create_impersonation_jwt(
+    sub: &str,
+    admin: &str,
+    ttl: Duration,
+    secret: &str,
+) -> Result<String, jsonwebtoken::errors::Error> {
+    sign(sub, Some(admin), ttl, secret)
+}
+
+fn sign(
+    sub: &str,
+    impersonated_by: Option<&str>,
+    ttl: Duration,
+    secret: &str,
+) -> Result<String, jsonwebtoken::errors::Error> {
+    let now = Utc::now();
+    let expiration = now
+        .checked_add_signed(ttl)
         .expect("valid timestamp")
         .timestamp();
 
     let claims = Claims {
         sub: sub.to_owned(),
         exp: expiration as usize,
+        iat: now.timestamp() as usize,
+        impersonated_by: impersonated_by.map(str::to_owned),
     };
 
     // Sign the JWT with the provided secret
@@ -47,34 +141,72 @@ pub fn create_jwt(sub: &str, secret: &str) -> Result<String, jsonwebtoken::error
     )
 }
 
+/// Takes the token out of an `Authorization: Bearer <token>` header value.
+///
+/// # Returns
+/// - `Ok(&str)`: The token, with surrounding whitespace trimmed.
+/// - `Err(JwtError::Missing)`: If the token after `Bearer` is empty or whitespace.
+/// - `Err(JwtError::Invalid)`: If the value isn't a `Bearer` credential.
+pub fn bearer_token(header: &str) -> Result<&str, JwtError> {
+    let rest = header.strip_prefix("Bearer").ok_or(JwtError::Invalid)?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return Err(JwtError::Invalid); // e.g. "Bearerabc"
+    }
+    match rest.trim() {
+        "" => Err(JwtError::Missing),
+        token => Ok(token),
+    }
+}
+
 /// Decodes and validates a JWT string, extracting the embedded claims.
 ///
 /// # Arguments
 /// - `token`: The JWT string to decode and verify.
+/// - `secret`: Secret key the token was signed with.
 ///
 /// # Returns
 /// - `Ok(Claims)`: The decoded claims if the token is valid.
-/// - `Err(StatusCode::UNAUTHORIZED)`: If the token is invalid or expired.
-///
-/// # Panics
-/// - If the `JWT_SECRET` environment variable is not set.
+/// - `Err(JwtError::Expired)`: If the token's `exp` has passed.
+/// - `Err(JwtError::Missing)`: If the token is empty or whitespace.
+/// - `Err(JwtError::Invalid)`: For any other decoding or signature failure.
 ///
 /// # Example
-/// ```rust
-/// let claims = extract_claims(token)?;
+/// ```rust,ignore
+/// let claims = extract_claims(token, &state.config.jwt_secret)?;
 /// println!("Token subject: {}", claims.sub);
 /// ```
-pub fn extract_claims(token: &str) -> Result<Claims, StatusCode> {
-    // Fetch secret key from environment
-    let secret = std::env::var("JWT_SECRET").expect("JWT_SECRET must be set");
+pub fn extract_claims(token: &str, secret: &str) -> Result<Claims, JwtError> {
+    decode_claims(token, secret, Validation::default())
+}
+
+/// Like [`extract_claims`], but accepts a token whose `exp` has passed.
+///
+/// Only for the refresh path, which needs the `sub` of an expired access
+/// token to find whose refresh token to check. The signature is still
+/// verified, so a forged or tampered token is rejected as `Invalid`.
+/// Everything else must use the strict [`extract_claims`].
+///
+/// # Returns
+/// - `Ok(Claims)`: The decoded claims, expired or not.
+/// - `Err(JwtError::Missing)`: If the token is empty or whitespace.
+/// - `Err(JwtError::Invalid)`: For any decoding or signature failure.
+pub fn extract_claims_ignoring_expiry(token: &str, secret: &str) -> Result<Claims, JwtError> {
+    let mut validation = Validation::default();
+    validation.validate_exp = false;
+    decode_claims(token, secret, validation)
+}
+
+fn decode_claims(token: &str, secret: &str, validation: Validation) -> Result<Claims, JwtError> {
+    if token.trim().is_empty() {
+        return Err(JwtError::Missing);
+    }
 
     // Decode and validate the token
     let token_data = decode::<Claims>(
         token,
         &DecodingKey::from_secret(secret.as_bytes()),
-        &Validation::default(),
-    )
-    .map_err(|_| StatusCode::UNAUTHORIZED)?; // Any failure results in 401
+        &validation,
+    )?;
 
     Ok(token_data.claims)
-}
\ No newline at end of file
+}
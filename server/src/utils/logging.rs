@@ -0,0 +1,68 @@
+use tracing::Subscriber;
+use tracing_subscriber::EnvFilter;
+
+/// Output format for log lines, selected with `LOG_FORMAT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable, multi-line output for local development.
+    Pretty,
+    /// One JSON object per line, for log aggregators.
+    Json,
+}
+
+impl LogFormat {
+    /// Parse a `LOG_FORMAT` value. Anything other than `json` means pretty.
+    pub fn parse(value: &str) -> Self {
+        if value.trim().eq_ignore_ascii_case("json") {
+            LogFormat::Json
+        } else {
+            LogFormat::Pretty
+        }
+    }
+
+    /// Read the format from the `LOG_FORMAT` environment variable.
+    pub fn from_env() -> Self {
+        std::env::var("LOG_FORMAT")
+            .map(|v| Self::parse(&v))
+            .unwrap_or(LogFormat::Pretty)
+    }
+}
+
+/// Build the level filter from the environment.
+///
+/// `RUST_LOG` wins when set (it supports per-target directives), then
+/// `LOG_LEVEL`, and finally `info`.
+pub fn env_filter() -> EnvFilter {
+    if let Ok(filter) = EnvFilter::try_from_default_env() {
+        return filter;
+    }
+
+    match std::env::var("LOG_LEVEL") {
+        Ok(level) if !level.trim().is_empty() => {
+            EnvFilter::try_new(level.trim()).unwrap_or_else(|_| EnvFilter::new("info"))
+        }
+        _ => EnvFilter::new("info"),
+    }
+}
+
+/// Construct (without installing) a subscriber for the given format and filter.
+pub fn build_subscriber(
+    format: LogFormat,
+    filter: EnvFilter,
+) -> Box<dyn Subscriber + Send + Sync + 'static> {
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+    match format {
+        LogFormat::Pretty => Box::new(builder.pretty().finish()),
+        LogFormat::Json => Box::new(builder.json().finish()),
+    }
+}
+
+/// Install the global subscriber configured from `RUST_LOG`/`LOG_LEVEL` and `LOG_FORMAT`.
+///
+/// # Panics
+/// Panics if a global subscriber has already been installed.
+pub fn init() {
+    let subscriber = build_subscriber(LogFormat::from_env(), env_filter());
+    tracing::subscriber::set_global_default(subscriber)
+        .expect("Failed to install tracing subscriber");
+}
@@ -1,2 +1,13 @@
 pub mod auth;
+pub mod bulk;
+pub mod client_ip;
+pub mod cursor;
+pub mod date_range;
 pub mod jwt;
+pub mod logging;
+pub mod normalize;
+pub mod pagination;
+pub mod path;
+pub mod time;
+pub mod token;
+pub mod validation;
@@ -0,0 +1,23 @@
+/// Trim leading and trailing whitespace, or `None` if nothing is left.
+///
+/// Used for free text such as ticket titles, where inner spacing is the
+/// author's business.
+pub fn trimmed(raw: &str) -> Option<String> {
+    let value = raw.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Trim and collapse every run of inner whitespace to a single space, or
+/// `None` if nothing is left.
+///
+/// Used for names (tags, users), so `"  Needs   triage "` and
+/// `"Needs triage"` can't both exist.
+pub fn name(raw: &str) -> Option<String> {
+    let value = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!value.is_empty()).then_some(value)
+}
+
+/// Normalize an email address; currently the same as [`trimmed`].
+pub fn email(raw: &str) -> Option<String> {
+    trimmed(raw)
+}
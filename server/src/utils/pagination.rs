@@ -0,0 +1,142 @@
+use axum::{
+    extract::{FromRequestParts, Query, rejection::QueryRejection},
+    http::{HeaderMap, HeaderValue, Uri, header, request::Parts},
+};
+use serde::Deserialize;
+
+use crate::{config::Config, state::AppState};
+
+/// Raw `?page=&per_page=` query parameters.
+#[derive(Debug, Default, Deserialize)]
+struct PageParams {
+    page: Option<u64>,
+    per_page: Option<u64>,
+}
+
+/// Page selection shared by every list endpoint.
+///
+/// Extracted from `?page=` (1-based, default `1`) and `?per_page=`
+/// (default `default_page_size`). A `per_page` above `max_page_size` is
+/// clamped rather than rejected; `clamped` records that so the response
+/// can say so.
+///
+/// # Example
+/// ```rust,ignore
+/// pub async fn get_tags(State(state): State<AppState>, pagination: Pagination) -> impl IntoResponse {
+///     let tags = tag::Entity::find()
+///         .offset(pagination.offset())
+///         .limit(pagination.per_page)
+///         .all(&state.db)
+///         .await?;
+///     (pagination.headers(), Json(tags))
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pagination {
+    pub page: u64,
+    pub per_page: u64,
+    pub clamped: bool,
+}
+
+impl Pagination {
+    /// Apply the configured default and cap to the requested values.
+    pub fn resolve(page: Option<u64>, per_page: Option<u64>, config: &Config) -> Self {
+        let max = config.max_page_size.max(1);
+        let requested = per_page.unwrap_or(config.default_page_size);
+        Self {
+            page: page.unwrap_or(1).max(1),
+            per_page: requested.clamp(1, max),
+            clamped: requested > max,
+        }
+    }
+
+    /// Rows to skip before this page starts.
+    ///
+    /// Saturates at `i64::MAX`, the largest `OFFSET` Postgres accepts, so an
+    /// absurd `?page=` just yields an empty page.
+    pub fn offset(&self) -> u64 {
+        (self.page - 1)
+            .saturating_mul(self.per_page)
+            .min(i64::MAX as u64)
+    }
+
+    /// `X-Page` and `X-Per-Page`, plus `X-Pagination-Note` when `per_page` was clamped.
+    pub fn headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-page", HeaderValue::from(self.page));
+        headers.insert("x-per-page", HeaderValue::from(self.per_page));
+        if self.clamped {
+            let note = format!("per_page clamped to {}", self.per_page);
+            if let Ok(value) = HeaderValue::from_str(&note) {
+                headers.insert("x-pagination-note", value);
+            }
+        }
+        headers
+    }
+
+    /// [`headers`](Self::headers) plus an RFC 8288 `Link` header.
+    ///
+    /// `uri` is the request's original URI (see `OriginalUri`) and `total`
+    /// the number of rows across all pages. Links keep every other query
+    /// parameter and point at the neighbouring `page`: `rel="prev"` is left
+    /// out on the first page and `rel="next"` on the last, and no `Link` is
+    /// sent when neither applies.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// // GET /tags?page=2&per_page=20 with 50 tags
+    /// // Link: </tags?page=3&per_page=20>; rel="next", </tags?page=1&per_page=20>; rel="prev"
+    /// (pagination.headers_with_links(&uri, 50), Json(tags))
+    /// ```
+    pub fn headers_with_links(&self, uri: &Uri, total: u64) -> HeaderMap {
+        let mut headers = self.headers();
+
+        let mut links = Vec::new();
+        if self.page.saturating_mul(self.per_page) < total {
+            links.push(format!(
+                "<{}>; rel=\"next\"",
+                self.page_uri(uri, self.page + 1)
+            ));
+        }
+        if self.page > 1 {
+            links.push(format!(
+                "<{}>; rel=\"prev\"",
+                self.page_uri(uri, self.page - 1)
+            ));
+        }
+        if !links.is_empty()
+            && let Ok(value) = HeaderValue::from_str(&links.join(", "))
+        {
+            headers.insert(header::LINK, value);
+        }
+        headers
+    }
+
+    /// `uri` with `page` swapped for another page and `per_page` pinned.
+    fn page_uri(&self, uri: &Uri, page: u64) -> String {
+        let mut params: Vec<&str> = uri
+            .query()
+            .unwrap_or_default()
+            .split('&')
+            .filter(|pair| {
+                let key = pair.split('=').next().unwrap_or_default();
+                !pair.is_empty() && key != "page" && key != "per_page"
+            })
+            .collect();
+        let paging = format!("page={page}&per_page={}", self.per_page);
+        params.push(&paging);
+        format!("{}?{}", uri.path(), params.join("&"))
+    }
+}
+
+impl FromRequestParts<AppState> for Pagination {
+    type Rejection = QueryRejection;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let Query(params) = Query::<PageParams>::from_request_parts(parts, state).await?;
+        Ok(Self::resolve(params.page, params.per_page, &state.config))
+    }
+}
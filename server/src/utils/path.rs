@@ -0,0 +1,43 @@
+use axum::{
+    Json,
+    extract::{FromRequestParts, rejection::PathRejection},
+    http::{StatusCode, request::Parts},
+    response::{IntoResponse, Response},
+};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+
+/// Drop-in for [`axum::extract::Path`] whose rejection matches our JSON errors.
+///
+/// A segment that doesn't parse (e.g. `/tickets/abc` for an `i32` ID) is
+/// rejected with `400 BAD_REQUEST` and
+/// `{ "error": "invalid_path_param", "detail": "..." }` instead of Axum's
+/// plain-text body.
+///
+/// # Example
+/// ```rust,ignore
+/// pub async fn get_ticket_by_id(Path(id): Path<i32>) -> impl IntoResponse { /* ... */ }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Path<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for Path<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Response> {
+        match axum::extract::Path::<T>::from_request_parts(parts, state).await {
+            Ok(axum::extract::Path(value)) => Ok(Path(value)),
+            Err(PathRejection::FailedToDeserializePathParams(e)) => Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": "invalid_path_param", "detail": e.body_text() })),
+            )
+                .into_response()),
+            // Missing params mean the route and handler disagree: a server bug
+            Err(other) => Err(other.into_response()),
+        }
+    }
+}
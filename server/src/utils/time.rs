@@ -0,0 +1,45 @@
+use chrono::{DateTime, NaiveDateTime, SecondsFormat};
+
+/// Format a stored timestamp, which is naive UTC, as RFC 3339 with a `Z` suffix.
+///
+/// Fractional seconds are only printed when present, e.g.
+/// `2024-01-01T09:00:00Z` or `2024-01-01T09:00:00.250Z`.
+pub fn to_rfc3339(at: NaiveDateTime) -> String {
+    at.and_utc().to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+/// Parse an RFC 3339 timestamp into naive UTC for storage.
+///
+/// A timestamp without an offset is taken to be UTC already, so older
+/// exports without the `Z` still load.
+pub fn parse(raw: &str) -> Option<NaiveDateTime> {
+    let raw = raw.trim();
+    DateTime::parse_from_rfc3339(raw)
+        .map(|dt| dt.naive_utc())
+        .ok()
+        .or_else(|| raw.parse::<NaiveDateTime>().ok())
+}
+
+/// Serde adapter for `Option<NaiveDateTime>` fields, used as
+/// `#[serde(default, with = "crate::utils::time::rfc3339")]`.
+pub mod rfc3339 {
+    use chrono::NaiveDateTime;
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer>(at: &Option<NaiveDateTime>, s: S) -> Result<S::Ok, S::Error> {
+        match at {
+            Some(at) => s.serialize_str(&super::to_rfc3339(*at)),
+            None => s.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<NaiveDateTime>, D::Error> {
+        Option::<String>::deserialize(d)?
+            .map(|raw| {
+                super::parse(&raw).ok_or_else(|| {
+                    D::Error::custom(format!("'{raw}' is not an RFC 3339 timestamp"))
+                })
+            })
+            .transpose()
+    }
+}
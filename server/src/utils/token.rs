@@ -0,0 +1,35 @@
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+/// Random bytes in a one-time token, before hex encoding.
+const TOKEN_BYTES: usize = 32;
+
+/// Random bytes in a generated password, before hex encoding.
+const PASSWORD_BYTES: usize = 12;
+
+/// Generate a one-time token for links sent by email.
+///
+/// Returns `(token, hash)`: the token goes to the user, only the hash is
+/// stored, so a leaked database row can't be replayed.
+pub fn generate() -> (String, String) {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    rand::rng().fill_bytes(&mut bytes);
+    let token = hex::encode(bytes);
+    let hash = hash(&token);
+    (token, hash)
+}
+
+/// SHA-256 of a token, hex-encoded, as stored in the database.
+///
+/// Tokens are long and random, so a fast unsalted hash is enough and keeps
+/// them searchable by equality.
+pub fn hash(token: &str) -> String {
+    hex::encode(Sha256::digest(token.trim().as_bytes()))
+}
+
+/// Generate a throwaway password for an admin to hand to a locked-out user.
+pub fn password() -> String {
+    let mut bytes = [0u8; PASSWORD_BYTES];
+    rand::rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
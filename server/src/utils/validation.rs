@@ -0,0 +1,60 @@
+use std::collections::BTreeMap;
+
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde_json::json;
+
+/// Every problem found in a request body, keyed by field name.
+///
+/// Handlers check all fields before giving up, so a client learns about
+/// every mistake at once. Renders as `422 UNPROCESSABLE_ENTITY` with
+/// `{ "errors": { "<field>": "<message>", ... } }`.
+///
+/// # Example
+/// ```rust,ignore
+/// let mut errors = ValidationErrors::default();
+/// let title = errors.require("title", normalize::trimmed(&payload.title), "must not be blank");
+/// let name = errors.require("name", normalize::name(&payload.name), "must not be blank");
+/// let (Some(title), Some(name)) = (title, name) else {
+///     return errors.into_response();
+/// };
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ValidationErrors(BTreeMap<&'static str, String>);
+
+impl ValidationErrors {
+    /// Record a problem with `field`; the first message per field wins.
+    pub fn add(&mut self, field: &'static str, message: impl Into<String>) {
+        self.0.entry(field).or_insert_with(|| message.into());
+    }
+
+    /// Pass `value` through, recording `message` for `field` if it is `None`.
+    pub fn require<T>(
+        &mut self,
+        field: &'static str,
+        value: Option<T>,
+        message: &str,
+    ) -> Option<T> {
+        if value.is_none() {
+            self.add(field, message);
+        }
+        value
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl IntoResponse for ValidationErrors {
+    fn into_response(self) -> Response {
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(json!({ "errors": self.0 })),
+        )
+            .into_response()
+    }
+}
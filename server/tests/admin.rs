@@ -0,0 +1,643 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::{ADMIN, AGENT, OTHER_AGENT, TestApp};
+use sea_orm::{ActiveModelTrait, ConnectionTrait, Set, TransactionTrait};
+use serde_json::{Value, json};
+use tagblaze::{
+    db::seed::seed_users,
+    models::{tag, ticket, ticket_tag},
+};
+
+async fn set_role(app: &TestApp, as_user: &str, id: i32, role: &str) -> (StatusCode, Value) {
+    app.send(
+        Method::PUT,
+        &format!("/admin/users/{id}/role"),
+        Some(as_user),
+        Some(json!({ "role": role })),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn admin_can_promote_an_agent() {
+    let app = TestApp::seeded().await;
+    let zoya = app.user_id(AGENT).await;
+
+    let (status, user) = set_role(&app, ADMIN, zoya, "admin").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(user["role"], "admin");
+    assert_eq!(user["email"], AGENT);
+    assert!(user.get("password").is_none());
+}
+
+#[tokio::test]
+async fn admin_can_be_demoted_while_another_admin_remains() {
+    let app = TestApp::seeded().await;
+    let zoya = app.user_id(AGENT).await;
+    let ankit = app.user_id(ADMIN).await;
+    set_role(&app, ADMIN, zoya, "admin").await;
+
+    let (status, user) = set_role(&app, AGENT, ankit, "agent").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(user["role"], "agent");
+}
+
+#[tokio::test]
+async fn last_admin_cannot_be_demoted() {
+    let app = TestApp::seeded().await;
+    let ankit = app.user_id(ADMIN).await;
+
+    let (status, body) = set_role(&app, ADMIN, ankit, "agent").await;
+
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["error"], "last_admin");
+}
+
+#[tokio::test]
+async fn disabled_admins_dont_count_toward_keeping_one() {
+    let app = TestApp::seeded().await;
+    let ankit = app.user_id(ADMIN).await;
+    let divya = app.user_id(OTHER_AGENT).await;
+    assert_eq!(
+        set_role(&app, ADMIN, divya, "admin").await.0,
+        StatusCode::OK
+    );
+    let (status, _) = app
+        .send(
+            Method::PUT,
+            &format!("/admin/users/{divya}/active"),
+            Some(ADMIN),
+            Some(json!({ "active": false })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = set_role(&app, ADMIN, ankit, "agent").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["error"], "last_admin");
+
+    // The disabled admin can still be demoted
+    assert_eq!(
+        set_role(&app, ADMIN, divya, "agent").await.0,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn agents_and_unknown_roles_are_rejected() {
+    let app = TestApp::seeded().await;
+    let zoya = app.user_id(AGENT).await;
+
+    let (status, _) = set_role(&app, AGENT, zoya, "admin").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = set_role(&app, ADMIN, zoya, "superuser").await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn tag_export_round_trips_into_a_fresh_database() {
+    let source = TestApp::seeded().await;
+    let bug = source.tag_id("Bug").await;
+    source
+        .send(Method::DELETE, &format!("/tags/{bug}"), None, None)
+        .await;
+
+    let (status, _) = source.get("/admin/tags/export", Some(AGENT)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, export) = source.get("/admin/tags/export", Some(ADMIN)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(export.as_array().unwrap().len(), 3);
+
+    // Users only, so the admin guard has someone to authenticate
+    let target = TestApp::new().await;
+    seed_users(target.db()).await.unwrap();
+
+    let (status, body) = target
+        .send(
+            Method::POST,
+            "/admin/tags/import",
+            Some(ADMIN),
+            Some(export.clone()),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "created": 3, "updated": 0 }));
+
+    let (_, copied) = target.get("/admin/tags/export", Some(ADMIN)).await;
+    assert_eq!(copied, export);
+
+    // Importing again matches by name instead of duplicating
+    let (_, body) = target
+        .send(
+            Method::POST,
+            "/admin/tags/import",
+            Some(ADMIN),
+            Some(export),
+        )
+        .await;
+    assert_eq!(body, json!({ "created": 0, "updated": 3 }));
+    let (_, tags) = target.get("/tags?include_deleted=true", None).await;
+    assert_eq!(tags.as_array().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn tag_import_matches_within_each_owner() {
+    let app = TestApp::seeded_with(|c| c.tag_ownership = true).await;
+    let zoya = app.user_id(AGENT).await;
+    let (status, owned) = app
+        .send(
+            Method::POST,
+            "/tags",
+            Some(AGENT),
+            Some(json!({ "name": "bug" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(owned["owner_id"], zoya);
+
+    let (_, mut export) = app.get("/admin/tags/export", Some(ADMIN)).await;
+    let entry = export
+        .as_array_mut()
+        .unwrap()
+        .iter_mut()
+        .find(|t| t["owner_id"] == zoya)
+        .unwrap();
+    assert_eq!(entry["name"], "bug");
+    entry["name"] = json!("BUG");
+    entry["description"] = json!("Mine");
+
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/admin/tags/import",
+            Some(ADMIN),
+            Some(export),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "created": 0, "updated": 4 }));
+
+    let (_, mine) = app.get(&format!("/tags/{}", owned["id"]), None).await;
+    assert_eq!(mine["name"], "BUG");
+    assert_eq!(mine["description"], "Mine");
+    assert_eq!(mine["owner_id"], zoya);
+    let (_, global) = app
+        .get(&format!("/tags/{}", app.tag_id("Bug").await), None)
+        .await;
+    assert_eq!(global["owner_id"], serde_json::Value::Null);
+    assert_eq!(global["description"], serde_json::Value::Null);
+
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/admin/tags/import",
+            Some(ADMIN),
+            Some(json!([{ "name": "Ghost", "owner_id": 999_999 }])),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body,
+        json!({ "error": "unknown_owner", "owner_id": 999_999 })
+    );
+}
+
+/// Write rows the foreign keys would normally refuse, returning the orphaned
+/// `ticket_tag` ID.
+async fn insert_orphaned_relation(app: &TestApp) -> i32 {
+    let bug = app.tag_id("Bug").await;
+    let txn = app.db().begin().await.unwrap();
+    txn.execute_unprepared("SET LOCAL session_replication_role = replica")
+        .await
+        .unwrap();
+    let orphan = ticket_tag::ActiveModel {
+        ticket_id: Set(999_999),
+        tag_id: Set(bug),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .unwrap();
+    txn.commit().await.unwrap();
+    orphan.id
+}
+
+#[tokio::test]
+async fn integrity_report_lists_each_kind_of_problem() {
+    let app = TestApp::seeded().await;
+
+    let (status, _) = app.get("/admin/integrity", Some(AGENT)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, clean) = app.get("/admin/integrity", Some(ADMIN)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(clean["ok"], true);
+
+    let orphan = insert_orphaned_relation(&app).await;
+    let ownerless = ticket::ActiveModel {
+        title: Set("Nobody's".into()),
+        ..Default::default()
+    }
+    .insert(app.db())
+    .await
+    .unwrap();
+    let bug = app.tag_id("Bug").await;
+    // An agent's own "bug" is fine; a second global one predates the index
+    tag::ActiveModel {
+        name: Set("bug".into()),
+        owner_id: Set(Some(app.user_id(AGENT).await)),
+        ..Default::default()
+    }
+    .insert(app.db())
+    .await
+    .unwrap();
+    app.db()
+        .execute_unprepared("DROP INDEX tag_global_lower_name")
+        .await
+        .unwrap();
+    let duplicate = tag::ActiveModel {
+        name: Set("bug".into()),
+        ..Default::default()
+    }
+    .insert(app.db())
+    .await
+    .unwrap();
+
+    let (_, report) = app.get("/admin/integrity", Some(ADMIN)).await;
+    assert_eq!(
+        report,
+        json!({
+            "ok": false,
+            "orphaned_ticket_tags": [orphan],
+            "tickets_without_user": [ownerless.id],
+            "duplicate_tag_names": [{ "owner_id": null, "name": "bug", "ids": [bug, duplicate.id] }],
+        })
+    );
+}
+
+#[tokio::test]
+async fn orphan_cleanup_is_idempotent() {
+    let app = TestApp::seeded().await;
+
+    let (status, body) = app
+        .send(Method::POST, "/admin/integrity/cleanup", Some(ADMIN), None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "removed": 0 }));
+
+    insert_orphaned_relation(&app).await;
+    let (_, body) = app
+        .send(Method::POST, "/admin/integrity/cleanup", Some(ADMIN), None)
+        .await;
+    assert_eq!(body, json!({ "removed": 1 }));
+
+    let (_, report) = app.get("/admin/integrity", Some(ADMIN)).await;
+    assert_eq!(report["ok"], true);
+    let (_, body) = app
+        .send(Method::POST, "/admin/integrity/cleanup", Some(ADMIN), None)
+        .await;
+    assert_eq!(body, json!({ "removed": 0 }));
+
+    let (status, _) = app
+        .send(Method::POST, "/admin/integrity/cleanup", Some(AGENT), None)
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn async_reset_runs_as_a_pollable_job() {
+    let app = TestApp::new().await;
+
+    let (status, started) = app
+        .send(Method::POST, "/admin/dev/reset-db?async=true", None, None)
+        .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(started["status"], "pending");
+    let uri = format!("/admin/dev/jobs/{}", started["job_id"]);
+
+    let mut job = Value::Null;
+    for _ in 0..100 {
+        let (status, body) = app.get(&uri, None).await;
+        assert_eq!(status, StatusCode::OK);
+        job = body;
+        if job["status"] == "done" || job["status"] == "failed" {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+
+    assert_eq!(job["status"], "done");
+    assert_eq!(job["result"]["reset"], true);
+    assert_eq!(job["result"]["users_seeded"], 3);
+    // Panics unless the seeded admin is really there
+    app.user_id(ADMIN).await;
+
+    let (status, _) = app.get("/admin/dev/jobs/999999", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn dry_run_reset_reports_the_plan_and_changes_nothing() {
+    let app = TestApp::seeded().await;
+    let (status, _) = app
+        .send(
+            Method::POST,
+            "/tickets",
+            Some(AGENT),
+            Some(json!({ "title": "Keep me" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, before) = app.get("/tickets", Some(ADMIN)).await;
+
+    let (status, plan) = app
+        .send(Method::POST, "/admin/dev/reset-db?dry_run=true", None, None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(plan["dry_run"], true);
+    assert_eq!(
+        plan["would_truncate"],
+        json!({
+            "login_event": 0,
+            "password_reset": 0,
+            "tag": 3,
+            "tag_subscription": 0,
+            "ticket": 3,
+            "ticket_tag": 3,
+            "ticket_tag_event": 0,
+            "user": 3,
+        })
+    );
+
+    let (_, after) = app.get("/tickets", Some(ADMIN)).await;
+    assert_eq!(after, before);
+
+    // The planned totals are what a real reset seeds
+    let (status, summary) = app
+        .send(Method::POST, "/admin/dev/reset-db", None, None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let seeded = &plan["would_seed"];
+    assert_eq!(summary["users_seeded"], seeded["users"]);
+    assert_eq!(summary["tags_seeded"], seeded["tags"]);
+    assert_eq!(summary["tickets_seeded"], seeded["tickets"]);
+    assert_eq!(summary["relations_seeded"], seeded["relations"]);
+}
+
+#[tokio::test]
+async fn ticket_updates_show_up_in_the_updaters_activity() {
+    let app = TestApp::seeded().await;
+    let agent = app.user_id(AGENT).await;
+    let (_, tickets) = app.get("/tickets", Some(AGENT)).await;
+    let ticket_id = tickets[0]["id"].as_i64().unwrap();
+    let activity = format!("/admin/users/{agent}/activity/tickets");
+
+    let (status, body) = app.get(&activity, Some(ADMIN)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!([]));
+
+    let (status, updated) = app
+        .send(
+            Method::PUT,
+            &format!("/tickets/{ticket_id}"),
+            Some(AGENT),
+            Some(json!({ "status": "closed" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["updated_by"], agent);
+
+    let (_, body) = app.get(&activity, Some(ADMIN)).await;
+    let ids: Vec<i64> = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["id"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, [ticket_id]);
+    assert_eq!(body[0]["status"], "closed");
+
+    // Someone else's later update moves it to their activity
+    let (_, _) = app
+        .send(
+            Method::PUT,
+            &format!("/tickets/{ticket_id}"),
+            Some(ADMIN),
+            Some(json!({ "title": "Taken over" })),
+        )
+        .await;
+    let (_, body) = app.get(&activity, Some(ADMIN)).await;
+    assert_eq!(body, json!([]));
+
+    let (status, _) = app.get(&activity, Some(AGENT)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = app
+        .get("/admin/users/999999/activity/tickets", Some(ADMIN))
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn reassign_moves_every_ticket_to_the_target_user() {
+    let app = TestApp::seeded().await;
+    for title in ["Second", "Third"] {
+        let (status, _) = app
+            .send(
+                Method::POST,
+                "/tickets",
+                Some(AGENT),
+                Some(json!({ "title": title })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+    let from = app.user_id(AGENT).await;
+    let into = app.user_id(OTHER_AGENT).await;
+    let uri = format!("/admin/users/{from}/reassign-to/{into}");
+
+    let (status, _) = app.send(Method::POST, &uri, Some(AGENT), None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = app.send(Method::POST, &uri, Some(ADMIN), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["moved"], 3);
+
+    let (_, mine) = app.get("/tickets", Some(AGENT)).await;
+    assert_eq!(mine.as_array().unwrap().len(), 0);
+    let (_, theirs) = app.get("/tickets", Some(OTHER_AGENT)).await;
+    assert_eq!(theirs.as_array().unwrap().len(), 3);
+
+    let (status, _) = app
+        .send(
+            Method::POST,
+            &format!("/admin/users/{from}/reassign-to/999999"),
+            Some(ADMIN),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn bulk_delete_removes_only_matching_tickets_and_their_tags() {
+    let app = TestApp::seeded().await;
+    let bug = app.tag_id("Bug").await;
+    for (as_user, title) in [
+        (AGENT, "spam one"),
+        (AGENT, "spam two"),
+        (OTHER_AGENT, "spam three"),
+    ] {
+        let (status, _) = app
+            .send(
+                Method::POST,
+                "/tickets",
+                Some(as_user),
+                Some(json!({ "title": title, "status": "spam", "tag_ids": [bug] })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+    let agent = app.user_id(AGENT).await;
+    let filter = json!({ "status": "spam", "user_id": agent });
+
+    // Without confirm nothing happens
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/admin/tickets/bulk-delete",
+            Some(ADMIN),
+            Some(filter.clone()),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "confirmation_required");
+
+    let mut confirmed = filter;
+    confirmed["confirm"] = json!(true);
+    let (status, _) = app
+        .send(
+            Method::POST,
+            "/admin/tickets/bulk-delete",
+            Some(AGENT),
+            Some(confirmed.clone()),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/admin/tickets/bulk-delete",
+            Some(ADMIN),
+            Some(confirmed),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["deleted"], 2);
+
+    // The agent keeps their seeded ticket; the other agent's spam survives
+    let (_, mine) = app.get("/tickets", Some(AGENT)).await;
+    assert!(
+        mine.as_array()
+            .unwrap()
+            .iter()
+            .all(|t| t["status"] != "spam")
+    );
+    assert!(!mine.as_array().unwrap().is_empty());
+    let (_, theirs) = app.get("/tickets", Some(OTHER_AGENT)).await;
+    assert!(
+        theirs
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|t| t["title"] == "spam three")
+    );
+
+    // No tag links are left pointing at deleted tickets
+    let (_, report) = app.get("/admin/integrity", Some(ADMIN)).await;
+    assert_eq!(report["ok"], true);
+}
+
+#[tokio::test]
+async fn dashboard_counts_users_tickets_tags_and_recent_days() {
+    let app = TestApp::seeded().await;
+    let now = chrono::Utc::now().naive_utc();
+    for (status, days_ago) in [("open", 3), ("open", 30)] {
+        ticket::ActiveModel {
+            title: Set(format!("{days_ago} days old")),
+            status: Set(Some(status.into())),
+            created_at: Set(Some(now - chrono::Duration::days(days_ago))),
+            ..Default::default()
+        }
+        .insert(app.db())
+        .await
+        .unwrap();
+    }
+
+    let (status, _) = app.get("/admin/dashboard", Some(AGENT)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = app.get("/admin/dashboard", Some(ADMIN)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["users"], 3);
+    assert_eq!(body["tags"], 3);
+    assert_eq!(
+        body["tickets"],
+        json!([
+            { "status": "open", "count": 2 },
+            { "status": null, "count": 2 },
+        ])
+    );
+
+    let days: Vec<i64> = body["tickets_per_day_last_7"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| d["count"].as_i64().unwrap())
+        .collect();
+    assert_eq!(days, [0, 0, 0, 1, 0, 0, 2]);
+    assert_eq!(
+        body["tickets_per_day_last_7"][6]["date"],
+        now.date().to_string()
+    );
+}
+
+#[tokio::test]
+async fn stats_by_user_ranks_users_by_tickets_created() {
+    let app = TestApp::seeded().await;
+    for (creator, n) in [(OTHER_AGENT, 3), (ADMIN, 1)] {
+        for i in 0..n {
+            let (status, _) = app
+                .send(
+                    Method::POST,
+                    "/tickets",
+                    Some(creator),
+                    Some(json!({ "title": format!("Ticket {i}") })),
+                )
+                .await;
+            assert_eq!(status, StatusCode::OK);
+        }
+    }
+
+    let (status, _) = app.get("/admin/stats/by-user", Some(AGENT)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = app.get("/admin/stats/by-user", Some(ADMIN)).await;
+    assert_eq!(status, StatusCode::OK);
+    let ranking: Vec<(&str, i64)> = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|row| {
+            (
+                row["user"]["email"].as_str().unwrap(),
+                row["ticket_count"].as_i64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(ranking, [(OTHER_AGENT, 3), (ADMIN, 2), (AGENT, 1)]);
+    assert!(body[0]["user"].get("password").is_none());
+}
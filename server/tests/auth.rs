@@ -0,0 +1,966 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode, header},
+};
+use common::{ADMIN, AGENT, CapturingEmailSender, TEST_JWT_SECRET, TestApp};
+use jsonwebtoken::{EncodingKey, Header, encode};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter, Set};
+use serde_json::{Value, json};
+use tagblaze::{
+    models::user,
+    routes::create_router,
+    utils::{
+        self,
+        jwt::{Claims, JwtError},
+        token,
+    },
+};
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn failed_login_increments_failure_counter() {
+    let app = TestApp::seeded().await;
+    assert_eq!(app.state.metrics.login_failures(), 0);
+
+    let (status, _) = app
+        .send(
+            Method::POST,
+            "/auth/login",
+            None,
+            Some(json!({ "email": AGENT, "password": "wrong" })),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(app.state.metrics.login_failures(), 1);
+    assert_eq!(app.state.metrics.login_successes(), 0);
+}
+
+#[tokio::test]
+async fn successful_login_increments_success_counter() {
+    let app = TestApp::seeded().await;
+
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/auth/login",
+            None,
+            Some(json!({ "email": AGENT, "password": "devpass123" })),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["token"].is_string());
+    assert_eq!(app.state.metrics.login_successes(), 1);
+    assert_eq!(app.state.metrics.login_failures(), 0);
+}
+
+fn sign(sub: &str, iat: i64, exp: i64) -> String {
+    let claims = Claims {
+        sub: sub.into(),
+        exp: exp as usize,
+        iat: iat as usize,
+        impersonated_by: None,
+    };
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(TEST_JWT_SECRET.as_bytes()),
+    )
+    .unwrap()
+}
+
+async fn list_tickets_with_token(token: &str) -> (StatusCode, Value) {
+    let app = create_router(common::lazy_state().await);
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/tickets")
+                .header(header::AUTHORIZATION, format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    (response.status(), common::read_json(response).await)
+}
+
+#[tokio::test]
+async fn expired_token_is_reported_as_expired() {
+    let now = chrono::Utc::now().timestamp();
+    let token = sign(AGENT, now - 7200, now - 3600);
+
+    let (status, body) = list_tickets_with_token(&token).await;
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body, json!({ "error": "token_expired" }));
+}
+
+#[test]
+fn expiry_can_be_ignored_only_by_the_lenient_decoder() {
+    let now = chrono::Utc::now().timestamp();
+    let token = sign(AGENT, now - 7200, now - 3600);
+
+    let err = utils::jwt::extract_claims(&token, TEST_JWT_SECRET).unwrap_err();
+    assert_eq!(err, JwtError::Expired);
+
+    let claims = utils::jwt::extract_claims_ignoring_expiry(&token, TEST_JWT_SECRET).unwrap();
+    assert_eq!(claims.sub, AGENT);
+    assert_eq!(claims.exp as i64, now - 3600);
+
+    // The signature is still checked
+    let err = utils::jwt::extract_claims_ignoring_expiry(&token, "other-secret").unwrap_err();
+    assert_eq!(err, JwtError::Invalid);
+}
+
+#[tokio::test]
+async fn garbage_token_is_reported_as_invalid() {
+    let (status, body) = list_tickets_with_token("not.a.jwt").await;
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body, json!({ "error": "token_invalid" }));
+}
+
+#[tokio::test]
+async fn empty_bearer_token_is_reported_as_missing() {
+    for token in ["", "   "] {
+        let (status, body) = list_tickets_with_token(token).await;
+
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body, json!({ "error": "token_missing" }));
+    }
+}
+
+#[test]
+fn request_decoder_reports_empty_bearer_token_as_missing() {
+    for value in ["Bearer ", "Bearer \t ", "Bearer"] {
+        let request = Request::builder()
+            .header(header::AUTHORIZATION, value)
+            .body(())
+            .unwrap();
+
+        let err = utils::auth::extract_claims(&request, TEST_JWT_SECRET).unwrap_err();
+        assert_eq!(err, JwtError::Missing, "{value:?}");
+    }
+
+    let request = Request::builder()
+        .header(header::AUTHORIZATION, "Basic abc")
+        .body(())
+        .unwrap();
+    let err = utils::auth::extract_claims(&request, TEST_JWT_SECRET).unwrap_err();
+    assert_eq!(err, JwtError::Invalid);
+}
+
+#[tokio::test]
+async fn logout_all_revokes_earlier_tokens_only() {
+    let app = TestApp::seeded().await;
+    let zoya = app.user_id(AGENT).await;
+    let now = chrono::Utc::now().timestamp();
+    let old_token = sign(AGENT, now - 60, now + 3600);
+
+    let (status, _) = app
+        .send(
+            Method::POST,
+            &format!("/admin/users/{zoya}/logout-all"),
+            Some(ADMIN),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let response = app
+        .request(
+            Request::builder()
+                .uri("/tickets")
+                .header(header::AUTHORIZATION, format!("Bearer {old_token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        common::read_json(response).await,
+        json!({ "error": "token_revoked" })
+    );
+
+    // A token issued now (as on a fresh login) is accepted
+    let (status, _) = app.get("/tickets", Some(AGENT)).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+async fn login(app: &TestApp, uri: &str, accept: Option<&str>) -> axum::response::Response {
+    let mut builder = Request::builder()
+        .method(Method::POST)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/json");
+    if let Some(accept) = accept {
+        builder = builder.header(header::ACCEPT, accept);
+    }
+    let body = json!({ "email": AGENT, "password": "devpass123" }).to_string();
+    app.request(builder.body(Body::from(body)).unwrap()).await
+}
+
+#[tokio::test]
+async fn cookie_login_round_trips_through_the_extractor() {
+    let app = TestApp::seeded().await;
+
+    let response = login(&app, "/auth/login?mode=cookie", None).await;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let set_cookie = response.headers()[header::SET_COOKIE].to_str().unwrap();
+    let max_age = format!("Max-Age={}", utils::jwt::TOKEN_TTL.num_seconds());
+    for attribute in ["HttpOnly", "Secure", "SameSite=Strict", "Path=/", &max_age] {
+        assert!(set_cookie.contains(attribute), "{set_cookie}");
+    }
+    let cookie = set_cookie.split(';').next().unwrap().to_string();
+    assert!(cookie.starts_with("tagblaze_token="));
+
+    let response = app
+        .request(
+            Request::builder()
+                .uri("/auth/me")
+                .header(header::COOKIE, &cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = common::read_json(response).await;
+    assert!(body.as_str().unwrap().contains(AGENT));
+
+    // An explicit Authorization header wins over the cookie
+    let response = app
+        .request(
+            Request::builder()
+                .uri("/auth/me")
+                .header(header::COOKIE, &cookie)
+                .header(header::AUTHORIZATION, "Bearer not.a.jwt")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn browser_accept_header_selects_cookie_mode() {
+    let app = TestApp::seeded().await;
+
+    let html = "text/html,application/xhtml+xml,*/*;q=0.8";
+    let response = login(&app, "/auth/login", Some(html)).await;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert!(response.headers().contains_key(header::SET_COOKIE));
+
+    let response = login(&app, "/auth/login?mode=body", Some(html)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(!response.headers().contains_key(header::SET_COOKIE));
+
+    let response = login(&app, "/auth/login", Some("application/json")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(common::read_json(response).await["token"].is_string());
+}
+
+async fn register(app: &TestApp, email: &str) {
+    let (status, _) = app
+        .send(
+            Method::POST,
+            "/auth/register",
+            None,
+            Some(json!({
+                "email": email,
+                "name": "New Agent",
+                "password": "newpass123",
+                "role": "agent",
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+}
+
+async fn find_user(app: &TestApp, email: &str) -> user::Model {
+    user::Entity::find()
+        .filter(user::Column::Email.eq(email))
+        .one(app.db())
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn verification_token_verifies_once() {
+    let app = TestApp::seeded().await;
+    register(&app, "new@tagblaze.dev").await;
+
+    let registered = find_user(&app, "new@tagblaze.dev").await;
+    assert!(!registered.email_verified);
+
+    // The raw token only ever leaves the server in the link, so plant a known one
+    let (raw, hash) = token::generate();
+    let mut active = registered.into_active_model();
+    active.email_verification_token = Set(Some(hash));
+    active.update(app.db()).await.unwrap();
+
+    let uri = format!("/auth/verify-email?token={raw}");
+    let (status, body) = app.get(&uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["email_verified"], true);
+
+    let verified = find_user(&app, "new@tagblaze.dev").await;
+    assert!(verified.email_verified);
+    assert_eq!(verified.email_verification_token, None);
+
+    let (status, body) = app.get(&uri, None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_token");
+}
+
+#[tokio::test]
+async fn unverified_users_cannot_log_in_when_verification_is_required() {
+    let app = TestApp::seeded_with(|c| c.require_email_verification = true).await;
+    register(&app, "new@tagblaze.dev").await;
+
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/auth/login",
+            None,
+            Some(json!({ "email": "new@tagblaze.dev", "password": "newpass123" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["error"], "email_not_verified");
+
+    // Seeded accounts predate verification and count as verified
+    let (status, _) = app
+        .send(
+            Method::POST,
+            "/auth/login",
+            None,
+            Some(json!({ "email": AGENT, "password": "devpass123" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn registration_mails_a_working_verification_link() {
+    let mailer = std::sync::Arc::new(CapturingEmailSender::default());
+    let app = TestApp::seeded().await.with_email_sender(mailer.clone());
+    register(&app, "new@tagblaze.dev").await;
+
+    let sent = mailer.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].to, "new@tagblaze.dev");
+
+    let link = sent[0].body.split_whitespace().last().unwrap();
+    assert!(link.starts_with("/auth/verify-email?token="));
+    let (status, body) = app.get(link, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["email_verified"], true);
+}
+
+/// Request a reset for `email` and return the token from the mailed body.
+async fn request_reset(app: &TestApp, mailer: &CapturingEmailSender, email: &str) -> String {
+    let (status, _) = app
+        .send(
+            Method::POST,
+            "/auth/forgot-password",
+            None,
+            Some(json!({ "email": email })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let sent = mailer.sent();
+    let mail = sent.last().expect("reset email sent");
+    assert_eq!(mail.to, email);
+    mail.body.split_whitespace().last().unwrap().to_string()
+}
+
+async fn reset(app: &TestApp, token: &str, new_password: &str) -> (StatusCode, Value) {
+    app.send(
+        Method::POST,
+        "/auth/reset-password",
+        None,
+        Some(json!({ "token": token, "new_password": new_password })),
+    )
+    .await
+}
+
+async fn login_status(app: &TestApp, email: &str, password: &str) -> StatusCode {
+    let (status, _) = app
+        .send(
+            Method::POST,
+            "/auth/login",
+            None,
+            Some(json!({ "email": email, "password": password })),
+        )
+        .await;
+    status
+}
+
+#[tokio::test]
+async fn password_reset_changes_the_password() {
+    let mailer = std::sync::Arc::new(CapturingEmailSender::default());
+    let app = TestApp::seeded().await.with_email_sender(mailer.clone());
+
+    let token = request_reset(&app, &mailer, AGENT).await;
+    let (status, _) = reset(&app, &token, "brand-new-pass").await;
+    assert_eq!(status, StatusCode::OK);
+
+    assert_eq!(
+        login_status(&app, AGENT, "devpass123").await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        login_status(&app, AGENT, "brand-new-pass").await,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn password_reset_revokes_earlier_tokens() {
+    let mailer = std::sync::Arc::new(CapturingEmailSender::default());
+    let app = TestApp::seeded().await.with_email_sender(mailer.clone());
+    let now = chrono::Utc::now().timestamp();
+    let old_token = sign(AGENT, now - 60, now + 3600);
+
+    let token = request_reset(&app, &mailer, AGENT).await;
+    let (status, _) = reset(&app, &token, "brand-new-pass").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = list_tickets_with(&app, &old_token).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body, json!({ "error": "token_revoked" }));
+}
+
+#[tokio::test]
+async fn forgot_password_does_not_reveal_unknown_emails() {
+    let mailer = std::sync::Arc::new(CapturingEmailSender::default());
+    let app = TestApp::seeded().await.with_email_sender(mailer.clone());
+
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/auth/forgot-password",
+            None,
+            Some(json!({ "email": "nobody@tagblaze.dev" })),
+        )
+        .await;
+    let (_, known) = app
+        .send(
+            Method::POST,
+            "/auth/forgot-password",
+            None,
+            Some(json!({ "email": AGENT })),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, known);
+    assert_eq!(mailer.sent().len(), 1);
+}
+
+#[tokio::test]
+async fn expired_reset_token_is_rejected() {
+    let mailer = std::sync::Arc::new(CapturingEmailSender::default());
+    let app = TestApp::seeded_with(|c| c.password_reset_ttl_secs = 0)
+        .await
+        .with_email_sender(mailer.clone());
+
+    let token = request_reset(&app, &mailer, AGENT).await;
+    let (status, body) = reset(&app, &token, "brand-new-pass").await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "token_expired");
+    assert_eq!(
+        login_status(&app, AGENT, "devpass123").await,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn reset_token_works_only_once() {
+    let mailer = std::sync::Arc::new(CapturingEmailSender::default());
+    let app = TestApp::seeded().await.with_email_sender(mailer.clone());
+
+    let token = request_reset(&app, &mailer, AGENT).await;
+    let (status, _) = reset(&app, &token, "brand-new-pass").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = reset(&app, &token, "another-pass").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_token");
+    assert_eq!(
+        login_status(&app, AGENT, "brand-new-pass").await,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn empty_new_password_is_rejected_and_keeps_the_token() {
+    let mailer = std::sync::Arc::new(CapturingEmailSender::default());
+    let app = TestApp::seeded().await.with_email_sender(mailer.clone());
+
+    let token = request_reset(&app, &mailer, AGENT).await;
+    let (status, body) = reset(&app, &token, "").await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"]["new_password"], "must not be empty");
+    assert_eq!(
+        login_status(&app, AGENT, "devpass123").await,
+        StatusCode::OK
+    );
+
+    let (status, _) = reset(&app, &token, "brand-new-pass").await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn registration_normalizes_email_and_name() {
+    let app = TestApp::seeded().await;
+
+    let (status, _) = app
+        .send(
+            Method::POST,
+            "/auth/register",
+            None,
+            Some(json!({
+                "email": "  spaced@tagblaze.dev ",
+                "name": "  Spaced   Out ",
+                "password": "newpass123",
+                "role": "agent",
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(
+        find_user(&app, "spaced@tagblaze.dev").await.name,
+        "Spaced Out"
+    );
+
+    let (status, _) = app
+        .send(
+            Method::POST,
+            "/auth/register",
+            None,
+            Some(json!({
+                "email": "blank@tagblaze.dev",
+                "name": "   ",
+                "password": "newpass123",
+                "role": "agent",
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn registration_reports_every_invalid_field() {
+    let app = TestApp::seeded().await;
+
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/auth/register",
+            None,
+            Some(json!({
+                "email": "not-an-email",
+                "name": "",
+                "password": "",
+                "role": "owner",
+            })),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    let errors = body["errors"].as_object().unwrap();
+    let mut fields: Vec<&str> = errors.keys().map(String::as_str).collect();
+    fields.sort_unstable();
+    assert_eq!(fields, ["email", "name", "password", "role"]);
+}
+
+async fn tickets_status(app: &TestApp, header: &str, value: &str) -> StatusCode {
+    let request = Request::builder()
+        .uri("/tickets")
+        .header(header, value)
+        .body(Body::empty())
+        .unwrap();
+    app.request(request).await.status()
+}
+
+#[tokio::test]
+async fn custom_jwt_header_is_honoured_only_when_configured() {
+    let token = utils::jwt::create_jwt(AGENT, TEST_JWT_SECRET).unwrap();
+
+    // Default: only Authorization counts
+    let app = TestApp::seeded().await;
+    let bearer = format!("Bearer {token}");
+    assert_eq!(
+        tickets_status(&app, "authorization", &bearer).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        tickets_status(&app, "x-auth-token", &token).await,
+        StatusCode::UNAUTHORIZED
+    );
+
+    // Configured: the bare token in the custom header works, Bearer still does
+    let app = TestApp::seeded_with(|c| c.jwt_header = Some("x-auth-token".into())).await;
+    assert_eq!(
+        tickets_status(&app, "x-auth-token", &token).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        tickets_status(&app, "authorization", &bearer).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        tickets_status(&app, "x-auth-token", "not.a.jwt").await,
+        StatusCode::UNAUTHORIZED
+    );
+}
+
+async fn register_as(
+    app: &TestApp,
+    as_user: Option<&str>,
+    email: &str,
+    role: Option<&str>,
+) -> (StatusCode, Value) {
+    let mut body = json!({ "email": email, "name": "Someone", "password": "pass12345" });
+    if let Some(role) = role {
+        body["role"] = json!(role);
+    }
+    app.send(Method::POST, "/auth/register", as_user, Some(body))
+        .await
+}
+
+#[tokio::test]
+async fn open_registration_defaults_to_agent_and_blocks_self_made_admins() {
+    let app = TestApp::seeded().await;
+
+    let (status, _) = register_as(&app, None, "plain@tagblaze.dev", None).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(find_user(&app, "plain@tagblaze.dev").await.role, "agent");
+
+    let (status, body) = register_as(&app, None, "sneaky@tagblaze.dev", Some("admin")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["error"], "admin_registration_forbidden");
+    let (status, _) = register_as(&app, Some(AGENT), "sneaky@tagblaze.dev", Some("admin")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // An existing admin may create another
+    let (status, _) = register_as(&app, Some(ADMIN), "boss@tagblaze.dev", Some("admin")).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(find_user(&app, "boss@tagblaze.dev").await.role, "admin");
+}
+
+#[tokio::test]
+async fn self_admin_registration_can_be_allowed_by_config() {
+    let app = TestApp::seeded_with(|c| c.allow_self_admin_registration = true).await;
+
+    let (status, _) = register_as(&app, None, "founder@tagblaze.dev", Some("admin")).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(find_user(&app, "founder@tagblaze.dev").await.role, "admin");
+}
+
+#[tokio::test]
+async fn admin_can_set_or_generate_a_users_password() {
+    let app = TestApp::seeded().await;
+    let zoya = app.user_id(AGENT).await;
+    let uri = format!("/admin/users/{zoya}/reset-password");
+    let now = chrono::Utc::now().timestamp();
+    let old_token = sign(AGENT, now - 60, now + 3600);
+
+    let (status, _) = app
+        .send(Method::POST, &uri, Some(AGENT), Some(json!({})))
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Provided password
+    let (status, _) = app
+        .send(
+            Method::POST,
+            &uri,
+            Some(ADMIN),
+            Some(json!({ "password": "chosen-by-admin" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert_eq!(
+        login_status(&app, AGENT, "devpass123").await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        login_status(&app, AGENT, "chosen-by-admin").await,
+        StatusCode::OK
+    );
+    let (status, _) = list_tickets_with(&app, &old_token).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Generated password, returned once
+    let (status, body) = app.send(Method::POST, &uri, Some(ADMIN), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let generated = body["password"].as_str().unwrap();
+    assert!(generated.len() >= 16);
+    assert_eq!(
+        login_status(&app, AGENT, "chosen-by-admin").await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(login_status(&app, AGENT, generated).await, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn admin_impersonation_tokens_act_as_the_user_and_name_the_admin() {
+    let app = TestApp::seeded().await;
+    let zoya = app.user_id(AGENT).await;
+    let uri = format!("/admin/users/{zoya}/impersonate");
+
+    let (status, _) = app.send(Method::POST, &uri, Some(AGENT), None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = app
+        .send(
+            Method::POST,
+            "/admin/users/999999/impersonate",
+            Some(ADMIN),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = app.send(Method::POST, &uri, Some(ADMIN), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["expires_in"], 900);
+    assert_eq!(body["impersonated_by"], ADMIN);
+    let token = body["token"].as_str().unwrap();
+
+    let claims = utils::jwt::extract_claims(token, TEST_JWT_SECRET).unwrap();
+    assert_eq!(claims.sub, AGENT);
+    assert_eq!(claims.impersonated_by.as_deref(), Some(ADMIN));
+    assert_eq!(claims.exp - claims.iat, 900);
+
+    let (status, _) = list_tickets_with(&app, token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // An impersonation token can't mint another, even one acting as an admin
+    let admin_id = app.user_id(ADMIN).await;
+    let (_, body) = app
+        .send(
+            Method::POST,
+            &format!("/admin/users/{admin_id}/impersonate"),
+            Some(ADMIN),
+            None,
+        )
+        .await;
+    let response = app
+        .request(
+            Request::builder()
+                .method(Method::POST)
+                .uri(&uri)
+                .header(
+                    header::AUTHORIZATION,
+                    format!("Bearer {}", body["token"].as_str().unwrap()),
+                )
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+async fn list_tickets_with(app: &TestApp, token: &str) -> (StatusCode, Value) {
+    let response = app
+        .request(
+            Request::builder()
+                .uri("/tickets")
+                .header(header::AUTHORIZATION, format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    (response.status(), common::read_json(response).await)
+}
+
+#[tokio::test]
+async fn registering_a_taken_email_conflicts_by_default() {
+    let app = TestApp::seeded().await;
+
+    let (status, body) = register_as(&app, None, AGENT, None).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["error"], "email_taken");
+    assert_eq!(find_user(&app, AGENT).await.name, "Zoya");
+}
+
+#[tokio::test]
+async fn upsert_registration_returns_the_existing_user() {
+    let app = TestApp::seeded().await;
+    let before = find_user(&app, AGENT).await;
+
+    let body = json!({ "email": AGENT, "name": "Someone", "password": "pass12345" });
+    let (status, body) = app
+        .send(Method::POST, "/auth/register?upsert=true", None, Some(body))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["id"], before.id);
+    assert_eq!(body["email"], AGENT);
+    assert_eq!(body["name"], before.name);
+    assert!(body.get("password").is_none());
+    assert_eq!(find_user(&app, AGENT).await, before);
+
+    // A new email is still created
+    let body = json!({ "email": "fresh@tagblaze.dev", "name": "Fresh", "password": "pass12345" });
+    let (status, _) = app
+        .send(Method::POST, "/auth/register?upsert=true", None, Some(body))
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+}
+
+async fn set_active(app: &TestApp, as_user: &str, id: i32, active: bool) -> (StatusCode, Value) {
+    app.send(
+        Method::PUT,
+        &format!("/admin/users/{id}/active"),
+        Some(as_user),
+        Some(json!({ "active": active })),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn disabled_accounts_cannot_log_in_until_enabled() {
+    let app = TestApp::seeded().await;
+    let agent = app.user_id(AGENT).await;
+    let now = chrono::Utc::now().timestamp();
+    let token = &sign(AGENT, now - 60, now + 3600);
+    assert_eq!(list_tickets_with(&app, token).await.0, StatusCode::OK);
+
+    let (status, body) = set_active(&app, ADMIN, agent, false).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["is_active"], false);
+
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/auth/login",
+            None,
+            Some(json!({ "email": AGENT, "password": "devpass123" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body, json!({ "error": "account_disabled" }));
+    // Tokens issued before the account was disabled stop working too
+    assert_eq!(
+        list_tickets_with(&app, token).await,
+        (
+            StatusCode::UNAUTHORIZED,
+            json!({ "error": "token_revoked" })
+        )
+    );
+
+    let (status, body) = set_active(&app, ADMIN, agent, true).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["is_active"], true);
+    assert_eq!(
+        login_status(&app, AGENT, "devpass123").await,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn only_admins_toggle_accounts_and_the_last_admin_stays_active() {
+    let app = TestApp::seeded().await;
+    let admin = app.user_id(ADMIN).await;
+    let agent = app.user_id(AGENT).await;
+
+    let (status, _) = set_active(&app, AGENT, agent, false).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = set_active(&app, ADMIN, admin, false).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["error"], "last_admin");
+
+    let (status, _) = set_active(&app, ADMIN, 999_999, false).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn failed_and_successful_logins_show_up_in_the_users_history() {
+    let app = TestApp::seeded().await;
+
+    assert_eq!(
+        login_status(&app, AGENT, "wrong").await,
+        StatusCode::UNAUTHORIZED
+    );
+    let request = Request::post("/auth/login")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::USER_AGENT, "tagblaze-test/1.0")
+        .body(Body::from(
+            json!({ "email": AGENT, "password": "devpass123" }).to_string(),
+        ))
+        .unwrap();
+    assert_eq!(app.request(request).await.status(), StatusCode::OK);
+
+    let (status, logins) = app.get("/auth/me/logins", Some(AGENT)).await;
+    assert_eq!(status, StatusCode::OK);
+    let logins = logins.as_array().unwrap();
+    assert_eq!(logins.len(), 2);
+    assert_eq!(logins[0]["success"], true);
+    assert_eq!(logins[0]["user_agent"], "tagblaze-test/1.0");
+    assert_eq!(logins[1]["success"], false);
+    assert_eq!(logins[1]["user_agent"], Value::Null);
+
+    // Paginated, and private to the account
+    let (_, page) = app.get("/auth/me/logins?per_page=1", Some(AGENT)).await;
+    assert_eq!(page.as_array().unwrap().len(), 1);
+    assert_eq!(page[0]["id"], logins[0]["id"]);
+    let (_, others) = app.get("/auth/me/logins", Some(ADMIN)).await;
+    assert_eq!(others, json!([]));
+}
+
+fn stored_cost(user: &user::Model) -> u32 {
+    user.password
+        .parse::<bcrypt::HashParts>()
+        .unwrap()
+        .get_cost()
+}
+
+#[tokio::test]
+async fn login_upgrades_a_low_cost_hash_to_the_configured_cost() {
+    let app = TestApp::seeded_with(|c| c.bcrypt_cost = 5).await;
+    let stored = user::Entity::find()
+        .filter(user::Column::Email.eq(AGENT))
+        .one(app.db())
+        .await
+        .unwrap()
+        .unwrap();
+    let mut weak = stored.into_active_model();
+    weak.password = Set(bcrypt::hash("devpass123", 4).unwrap());
+    let weak = weak.update(app.db()).await.unwrap();
+    assert_eq!(stored_cost(&weak), 4);
+
+    // A failed login leaves the hash alone
+    assert_eq!(
+        login_status(&app, AGENT, "wrong").await,
+        StatusCode::UNAUTHORIZED
+    );
+    let after_failure = user::Entity::find_by_id(weak.id)
+        .one(app.db())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(after_failure.password, weak.password);
+
+    assert_eq!(
+        login_status(&app, AGENT, "devpass123").await,
+        StatusCode::OK
+    );
+    let upgraded = user::Entity::find_by_id(weak.id)
+        .one(app.db())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored_cost(&upgraded), 5);
+    assert!(bcrypt::verify("devpass123", &upgraded.password).unwrap());
+    assert_eq!(
+        login_status(&app, AGENT, "devpass123").await,
+        StatusCode::OK
+    );
+}
@@ -0,0 +1,60 @@
+use std::{
+    fs,
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tagblaze::config::select_env_file;
+
+/// A scratch directory containing the given dotenv files.
+fn dir_with(files: &[&str]) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!("tagblaze_env_{}_{nanos}_{n}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    for file in files {
+        fs::write(dir.join(file), "").unwrap();
+    }
+    dir
+}
+
+#[test]
+fn app_env_selects_its_own_file() {
+    let dir = dir_with(&[".env", ".env.test", ".env.production"]);
+
+    assert_eq!(
+        select_env_file(&dir, Some("test")),
+        Some(dir.join(".env.test"))
+    );
+    assert_eq!(
+        select_env_file(&dir, Some("production")),
+        Some(dir.join(".env.production"))
+    );
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn missing_or_unset_app_env_falls_back_to_dot_env() {
+    let dir = dir_with(&[".env"]);
+
+    assert_eq!(
+        select_env_file(&dir, Some("staging")),
+        Some(dir.join(".env"))
+    );
+    assert_eq!(select_env_file(&dir, None), Some(dir.join(".env")));
+    assert_eq!(select_env_file(&dir, Some("  ")), Some(dir.join(".env")));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn no_files_means_nothing_to_load() {
+    let dir = dir_with(&[]);
+
+    assert_eq!(select_env_file(&dir, Some("test")), None);
+    fs::remove_dir_all(dir).unwrap();
+}
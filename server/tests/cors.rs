@@ -0,0 +1,116 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{HeaderMap, Request, StatusCode, header},
+};
+use tagblaze::{config::Config, routes::create_router};
+use tower::ServiceExt;
+
+async fn preflight(configure: impl FnOnce(&mut Config), origin: &str) -> (StatusCode, HeaderMap) {
+    let app = create_router(common::lazy_state_with(configure).await);
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("OPTIONS")
+                .uri("/tickets")
+                .header(header::ORIGIN, origin)
+                .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    (response.status(), response.headers().clone())
+}
+
+#[tokio::test]
+async fn credentialed_origin_is_echoed_with_credentials_and_max_age() {
+    let (status, headers) = preflight(
+        |c| {
+            c.cors_allowed_origins = vec!["https://app.tagblaze.dev".into()];
+            c.cors_allow_credentials = true;
+            c.cors_max_age_secs = Some(600);
+        },
+        "https://app.tagblaze.dev",
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        headers[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+        "https://app.tagblaze.dev"
+    );
+    assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_CREDENTIALS], "true");
+    assert_eq!(headers[header::ACCESS_CONTROL_MAX_AGE], "600");
+}
+
+#[tokio::test]
+async fn unlisted_origin_gets_no_cors_headers() {
+    let (_, headers) = preflight(
+        |c| {
+            c.cors_allowed_origins = vec!["https://app.tagblaze.dev".into()];
+            c.cors_allow_credentials = true;
+        },
+        "https://evil.example",
+    )
+    .await;
+
+    assert!(!headers.contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+}
+
+#[tokio::test]
+async fn wildcard_origin_never_allows_credentials() {
+    let (_, headers) = preflight(
+        |c| c.cors_allow_credentials = true,
+        "https://anywhere.example",
+    )
+    .await;
+
+    assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+    assert!(!headers.contains_key(header::ACCESS_CONTROL_ALLOW_CREDENTIALS));
+    assert!(!headers.contains_key(header::ACCESS_CONTROL_MAX_AGE));
+}
+
+async fn get_with_origin(configure: impl FnOnce(&mut Config), origin: &str) -> HeaderMap {
+    let app = create_router(common::lazy_state_with(configure).await);
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/health")
+                .header(header::ORIGIN, origin)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    response.headers().clone()
+}
+
+fn varies_on_origin(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::VARY)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .any(|v| v.trim().eq_ignore_ascii_case("origin"))
+}
+
+#[tokio::test]
+async fn reflected_origins_vary_on_origin() {
+    let listed = |c: &mut Config| c.cors_allowed_origins = vec!["https://app.tagblaze.dev".into()];
+
+    let headers = get_with_origin(listed, "https://app.tagblaze.dev").await;
+    assert_eq!(
+        headers[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+        "https://app.tagblaze.dev"
+    );
+    assert!(varies_on_origin(&headers), "{headers:?}");
+
+    // The answer differs by origin even when it's refused
+    let headers = get_with_origin(listed, "https://evil.example").await;
+    assert!(varies_on_origin(&headers), "{headers:?}");
+
+    let (_, headers) = preflight(listed, "https://app.tagblaze.dev").await;
+    assert!(varies_on_origin(&headers), "{headers:?}");
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::TestApp;
+use sea_orm_migration::MigratorTrait;
+use tagblaze::db::migration::Migrator;
+
+#[tokio::test]
+async fn health_check_returns_ok() {
+    let app = TestApp::new().await;
+
+    let (status, body) = app.get("/health", None).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "✅ TagBlaze is healthy!");
+}
+
+#[tokio::test]
+async fn seeded_app_serves_tags() {
+    let app = TestApp::seeded().await;
+
+    let (status, body) = app.get("/tags", None).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.as_array().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn version_reports_the_crate_version() {
+    let app = TestApp::new().await;
+
+    let (status, body) = app.get("/health/version", None).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+    assert!(!body["git_sha"].as_str().unwrap().is_empty());
+    assert!(
+        chrono::DateTime::parse_from_rfc3339(body["built_at"].as_str().unwrap()).is_ok(),
+        "{body}"
+    );
+}
+
+#[tokio::test]
+async fn readiness_reports_pending_migrations_until_applied() {
+    let app = TestApp::empty().await;
+
+    let (status, body) = app.get("/health/ready", None).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["up_to_date"], false);
+    assert_eq!(body["pending"][0], "m20250601_000001_initial_schema");
+
+    Migrator::up(app.db(), None).await.unwrap();
+
+    let (status, body) = app.get("/health/ready", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body,
+        serde_json::json!({ "up_to_date": true, "pending": [] })
+    );
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode, header},
+};
+use common::{ADMIN, AGENT, TestApp, bearer, read_json};
+use serde_json::json;
+
+#[tokio::test]
+async fn maintenance_blocks_everyone_but_admins_and_health_checks() {
+    let app = TestApp::seeded_with(|c| c.maintenance_mode = true).await;
+
+    let response = app
+        .request(
+            Request::builder()
+                .uri("/tickets")
+                .header(header::AUTHORIZATION, bearer(AGENT))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(response.headers()[header::RETRY_AFTER], "120");
+    assert_eq!(read_json(response).await["error"], "maintenance");
+
+    let (status, _) = app.get("/tags", None).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+
+    let (status, _) = app.get("/health", None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = app.get("/tickets", Some(ADMIN)).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn admins_can_switch_maintenance_at_runtime() {
+    let app = TestApp::seeded().await;
+
+    let (status, _) = app
+        .send(
+            Method::PUT,
+            "/admin/maintenance",
+            Some(AGENT),
+            Some(json!({ "enabled": true })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = app
+        .send(
+            Method::PUT,
+            "/admin/maintenance",
+            Some(ADMIN),
+            Some(json!({ "enabled": true })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "enabled": true }));
+    assert_eq!(
+        app.get("/tickets", Some(AGENT)).await.0,
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+    assert_eq!(
+        app.get("/admin/maintenance", Some(ADMIN)).await.1,
+        json!({ "enabled": true })
+    );
+
+    app.send(
+        Method::PUT,
+        "/admin/maintenance",
+        Some(ADMIN),
+        Some(json!({ "enabled": false })),
+    )
+    .await;
+    assert_eq!(app.get("/tickets", Some(AGENT)).await.0, StatusCode::OK);
+}
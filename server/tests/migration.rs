@@ -0,0 +1,78 @@
+mod common;
+
+use std::collections::BTreeSet;
+
+use common::TestApp;
+use sea_orm::{ConnectionTrait, DatabaseConnection, Statement};
+use sea_orm_migration::MigratorTrait;
+use tagblaze::db::migration::Migrator;
+
+/// Every column, constraint and index in `public`, one line each, leaving
+/// out the migrations' own bookkeeping table. Column order is ignored.
+async fn schema_of(db: &DatabaseConnection) -> BTreeSet<String> {
+    let queries = [
+        "SELECT concat_ws(' ', 'column', table_name, column_name, data_type, is_nullable, column_default) \
+         FROM information_schema.columns \
+         WHERE table_schema = 'public' AND table_name <> 'seaql_migrations'",
+        "SELECT concat_ws(' ', 'constraint', conrelid::regclass::text, conname, pg_get_constraintdef(oid)) \
+         FROM pg_constraint \
+         WHERE connamespace = 'public'::regnamespace AND conrelid::regclass::text <> 'seaql_migrations'",
+        "SELECT concat_ws(' ', 'index', indexdef) \
+         FROM pg_indexes \
+         WHERE schemaname = 'public' AND tablename <> 'seaql_migrations'",
+    ];
+
+    let mut schema = BTreeSet::new();
+    for sql in queries {
+        let rows = db
+            .query_all(Statement::from_string(db.get_database_backend(), sql))
+            .await
+            .unwrap();
+        for row in rows {
+            schema.insert(row.try_get_by_index::<String>(0).unwrap());
+        }
+    }
+    schema
+}
+
+/// Assert both databases have the same schema, listing what differs.
+async fn assert_same_schema(actual: &DatabaseConnection, expected: &DatabaseConnection) {
+    let (actual, expected) = (schema_of(actual).await, schema_of(expected).await);
+    let missing: Vec<_> = expected.difference(&actual).collect();
+    let unexpected: Vec<_> = actual.difference(&expected).collect();
+    assert!(
+        missing.is_empty() && unexpected.is_empty(),
+        "missing: {missing:#?}\nunexpected: {unexpected:#?}"
+    );
+}
+
+#[tokio::test]
+async fn migrations_build_the_schema_the_entities_describe() {
+    let from_entities = TestApp::new().await;
+    let migrated = TestApp::empty().await;
+
+    Migrator::up(migrated.db(), None).await.unwrap();
+
+    assert_same_schema(migrated.db(), from_entities.db()).await;
+}
+
+#[tokio::test]
+async fn migrations_catch_up_a_database_from_before_migrations() {
+    let from_entities = TestApp::new().await;
+    let old = TestApp::empty().await;
+    let db = old.db();
+
+    // 🏚️ The original tables plus one later column added by hand, with no
+    // record of any migration
+    Migrator::up(db, Some(1)).await.unwrap();
+    for sql in [
+        "DROP TABLE seaql_migrations",
+        "ALTER TABLE ticket ADD COLUMN created_by integer",
+    ] {
+        db.execute_unprepared(sql).await.unwrap();
+    }
+
+    Migrator::up(db, None).await.unwrap();
+
+    assert_same_schema(db, from_entities.db()).await;
+}
@@ -0,0 +1,150 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode, Uri, header},
+};
+use common::{ADMIN, TestApp, bearer};
+use tagblaze::{config::Config, utils::pagination::Pagination};
+
+fn config(default_page_size: u64, max_page_size: u64) -> Config {
+    Config {
+        default_page_size,
+        max_page_size,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn omitted_values_use_configured_defaults() {
+    let pagination = Pagination::resolve(None, None, &config(25, 100));
+
+    assert_eq!(pagination.page, 1);
+    assert_eq!(pagination.per_page, 25);
+    assert!(!pagination.clamped);
+    assert_eq!(pagination.offset(), 0);
+}
+
+#[test]
+fn oversized_pages_are_clamped_and_flagged() {
+    let pagination = Pagination::resolve(Some(3), Some(500), &config(25, 100));
+
+    assert_eq!(pagination.per_page, 100);
+    assert!(pagination.clamped);
+    assert_eq!(pagination.offset(), 200);
+    assert_eq!(
+        pagination.headers()["x-pagination-note"],
+        "per_page clamped to 100"
+    );
+}
+
+#[test]
+fn zero_page_and_size_are_raised_to_one() {
+    let pagination = Pagination::resolve(Some(0), Some(0), &config(25, 100));
+
+    assert_eq!((pagination.page, pagination.per_page), (1, 1));
+    assert!(!pagination.clamped);
+}
+
+#[test]
+fn huge_page_saturates_the_offset() {
+    let pagination = Pagination::resolve(Some(u64::MAX), Some(50), &config(25, 100));
+
+    assert_eq!(pagination.offset(), i64::MAX as u64);
+}
+
+#[tokio::test]
+async fn tag_list_is_paged_and_clamped() {
+    let app = TestApp::seeded().await;
+
+    let (status, first) = app.get("/tags?per_page=2", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(first.as_array().unwrap().len(), 2);
+
+    let (_, second) = app.get("/tags?page=2&per_page=2", None).await;
+    assert_eq!(second.as_array().unwrap().len(), 1);
+
+    let (status, beyond) = app.get("/tags?page=18446744073709551615", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(beyond, serde_json::json!([]));
+
+    let response = app
+        .request(
+            Request::builder()
+                .uri("/tags?per_page=1000")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(response.headers()["x-per-page"], "100");
+    assert_eq!(
+        response.headers()["x-pagination-note"],
+        "per_page clamped to 100"
+    );
+}
+
+#[test]
+fn link_header_keeps_other_parameters_and_pins_page_size() {
+    let pagination = Pagination::resolve(Some(2), Some(10), &config(25, 100));
+    let uri: Uri = "/tickets?page=2&scope=assigned_to_me&per_page=10"
+        .parse()
+        .unwrap();
+
+    let headers = pagination.headers_with_links(&uri, 35);
+    assert_eq!(
+        headers[header::LINK],
+        "</tickets?scope=assigned_to_me&page=3&per_page=10>; rel=\"next\", \
+         </tickets?scope=assigned_to_me&page=1&per_page=10>; rel=\"prev\""
+    );
+
+    // A single page links nowhere
+    let only = Pagination::resolve(None, None, &config(25, 100));
+    assert!(
+        only.headers_with_links(&uri, 25)
+            .get(header::LINK)
+            .is_none()
+    );
+}
+
+async fn link(app: &TestApp, uri: &str) -> String {
+    let response = app
+        .request(
+            Request::builder()
+                .uri(uri)
+                .header(header::AUTHORIZATION, bearer(ADMIN))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    response.headers()[header::LINK]
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+#[tokio::test]
+async fn tag_and_ticket_lists_link_to_neighbouring_pages() {
+    let app = TestApp::seeded().await;
+
+    // Three seeded tags, one per page
+    let first = link(&app, "/tags?per_page=1").await;
+    assert_eq!(first, "</tags?page=2&per_page=1>; rel=\"next\"");
+    let middle = link(&app, "/tags?page=2&per_page=1").await;
+    assert!(
+        middle.contains("page=3&per_page=1>; rel=\"next\""),
+        "{middle}"
+    );
+    assert!(
+        middle.contains("page=1&per_page=1>; rel=\"prev\""),
+        "{middle}"
+    );
+    let last = link(&app, "/tags?page=3&per_page=1").await;
+    assert_eq!(last, "</tags?page=2&per_page=1>; rel=\"prev\"");
+
+    // Two seeded tickets, visible to the admin
+    let first = link(&app, "/tickets?per_page=1").await;
+    assert_eq!(first, "</tickets?page=2&per_page=1>; rel=\"next\"");
+    let last = link(&app, "/tickets?page=2&per_page=1").await;
+    assert_eq!(last, "</tickets?page=1&per_page=1>; rel=\"prev\"");
+}
@@ -0,0 +1,155 @@
+mod common;
+
+use std::net::{IpAddr, SocketAddr};
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{HeaderMap, HeaderValue, Request},
+};
+use tagblaze::{
+    routes::create_router,
+    utils::client_ip::{Cidr, resolve},
+};
+use tower::ServiceExt;
+
+fn header(headers: &HeaderMap, name: &str) -> Option<u64> {
+    headers.get(name)?.to_str().ok()?.parse().ok()
+}
+
+async fn call(app: &axum::Router, uri: &str) -> HeaderMap {
+    let response = app
+        .clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    response.headers().clone()
+}
+
+#[tokio::test]
+async fn remaining_budget_decrements_across_requests() {
+    let app = create_router(
+        common::lazy_state_with(|c| {
+            c.rate_limit_requests = 3;
+            c.rate_limit_window_secs = 60;
+        })
+        .await,
+    );
+
+    let mut remaining = Vec::new();
+    for _ in 0..4 {
+        let headers = call(&app, "/auth/me").await;
+        assert_eq!(header(&headers, "x-ratelimit-limit"), Some(3));
+        assert!(header(&headers, "x-ratelimit-reset").unwrap() <= 60);
+        remaining.push(header(&headers, "x-ratelimit-remaining").unwrap());
+    }
+
+    assert_eq!(remaining, vec![2, 1, 0, 0]);
+}
+
+#[tokio::test]
+async fn unlimited_routes_and_disabled_limiter_send_no_headers() {
+    let app = create_router(common::lazy_state_with(|c| c.rate_limit_requests = 3).await);
+    assert!(
+        call(&app, "/health")
+            .await
+            .get("x-ratelimit-limit")
+            .is_none()
+    );
+
+    let disabled = create_router(common::lazy_state().await);
+    assert!(
+        call(&disabled, "/auth/me")
+            .await
+            .get("x-ratelimit-limit")
+            .is_none()
+    );
+}
+
+fn ip(s: &str) -> IpAddr {
+    s.parse().unwrap()
+}
+
+fn forwarded(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    for (name, value) in pairs {
+        headers.append(*name, HeaderValue::from_static(value));
+    }
+    headers
+}
+
+#[test]
+fn forwarding_headers_are_believed_only_from_trusted_proxies() {
+    let trusted: Vec<Cidr> = vec!["10.0.0.0/8".parse().unwrap(), "::1".parse().unwrap()];
+    let headers = forwarded(&[("x-forwarded-for", "198.51.100.9, 203.0.113.7, 10.0.0.1")]);
+
+    // Rightmost hop outside the trusted networks wins; spoofed entries to its left are ignored
+    assert_eq!(
+        resolve(Some(ip("10.0.0.2")), &headers, &trusted),
+        Some(ip("203.0.113.7"))
+    );
+    // An untrusted peer can't claim to be someone else
+    assert_eq!(
+        resolve(Some(ip("192.0.2.50")), &headers, &trusted),
+        Some(ip("192.0.2.50"))
+    );
+    assert_eq!(
+        resolve(Some(ip("10.0.0.2")), &headers, &[]),
+        Some(ip("10.0.0.2"))
+    );
+
+    let real_ip = forwarded(&[("x-real-ip", "203.0.113.8")]);
+    assert_eq!(
+        resolve(Some(ip("::1")), &real_ip, &trusted),
+        Some(ip("203.0.113.8"))
+    );
+    assert_eq!(
+        resolve(Some(ip("::ffff:10.1.2.3")), &real_ip, &trusted),
+        Some(ip("203.0.113.8"))
+    );
+    assert_eq!(resolve(None, &real_ip, &trusted), None);
+
+    assert!("10.0.0.0/33".parse::<Cidr>().is_err());
+    assert!("not-an-ip".parse::<Cidr>().is_err());
+}
+
+async fn call_from(app: &axum::Router, peer: &str, client: &'static str) -> HeaderMap {
+    let mut request = Request::builder()
+        .uri("/auth/me")
+        .header("x-forwarded-for", client)
+        .body(Body::empty())
+        .unwrap();
+    request
+        .extensions_mut()
+        .insert(ConnectInfo(SocketAddr::new(ip(peer), 4000)));
+    app.clone()
+        .oneshot(request)
+        .await
+        .unwrap()
+        .headers()
+        .clone()
+}
+
+#[tokio::test]
+async fn rate_limit_keys_on_the_forwarded_client_only_behind_a_trusted_proxy() {
+    let app = create_router(
+        common::lazy_state_with(|c| {
+            c.rate_limit_requests = 5;
+            c.rate_limit_window_secs = 60;
+            c.trusted_proxies = vec!["10.0.0.0/8".parse().unwrap()];
+        })
+        .await,
+    );
+
+    // Two clients behind the proxy get separate budgets
+    let a = call_from(&app, "10.0.0.2", "203.0.113.1").await;
+    let b = call_from(&app, "10.0.0.2", "203.0.113.2").await;
+    assert_eq!(header(&a, "x-ratelimit-remaining"), Some(4));
+    assert_eq!(header(&b, "x-ratelimit-remaining"), Some(4));
+
+    // A direct client rotating the header still shares one budget
+    let c = call_from(&app, "192.0.2.50", "203.0.113.3").await;
+    let d = call_from(&app, "192.0.2.50", "203.0.113.4").await;
+    assert_eq!(header(&c, "x-ratelimit-remaining"), Some(4));
+    assert_eq!(header(&d, "x-ratelimit-remaining"), Some(3));
+}
@@ -0,0 +1,35 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::{AGENT, TestApp};
+use serde_json::json;
+
+#[tokio::test]
+async fn read_only_mode_blocks_writes_but_not_reads_or_login() {
+    let app = TestApp::seeded_with(|c| c.read_only = true).await;
+
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/tags",
+            Some(AGENT),
+            Some(json!({ "name": "Blocked" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["error"], "read_only_mode");
+
+    let (status, _) = app.get("/tickets", Some(AGENT)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/auth/login",
+            None,
+            Some(json!({ "email": AGENT, "password": "devpass123" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["token"].is_string(), "{body}");
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use axum::http::{Method, StatusCode};
+use common::{ADMIN, AGENT, TestApp};
+use serde_json::json;
+
+/// A second pool on the test database that records every statement it runs.
+async fn replica(app: &TestApp) -> (sea_orm::DatabaseConnection, Arc<Mutex<Vec<String>>>) {
+    let mut config = app.state.config.as_ref().clone();
+    config.database_read_url = Some(app.database_url());
+    let mut read_db = tagblaze::db::db::connect_read(&config).await.unwrap();
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let log = seen.clone();
+    read_db.set_metric_callback(move |info| log.lock().unwrap().push(info.statement.to_string()));
+    (read_db, seen)
+}
+
+#[tokio::test]
+async fn listings_and_stats_read_from_the_replica_and_writes_do_not() {
+    let app = TestApp::seeded().await;
+    let (read_db, seen) = replica(&app).await;
+    let app = app.with_read_db(read_db);
+
+    for uri in ["/tickets", "/tags", "/tickets/statuses", "/auth/me/stats"] {
+        let (status, _) = app.get(uri, Some(AGENT)).await;
+        assert_eq!(status, StatusCode::OK, "{uri}");
+    }
+    let reads = std::mem::take(&mut *seen.lock().unwrap());
+    assert!(
+        reads.iter().any(|sql| sql.contains(r#"FROM "ticket""#)),
+        "{reads:?}"
+    );
+    assert!(
+        reads.iter().any(|sql| sql.contains(r#"FROM "tag""#)),
+        "{reads:?}"
+    );
+
+    let (status, _) = app
+        .send(
+            Method::POST,
+            "/tags",
+            Some(ADMIN),
+            Some(json!({ "name": "Replicated" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(seen.lock().unwrap().is_empty(), "write went to the replica");
+}
+
+#[tokio::test]
+async fn feeds_trends_and_tag_overviews_read_from_the_replica() {
+    let app = TestApp::seeded().await;
+    let bug = app.tag_id("Bug").await;
+    let (read_db, seen) = replica(&app).await;
+    let app = app.with_read_db(read_db);
+
+    for (uri, as_user) in [
+        ("/tickets/feed".to_string(), AGENT),
+        (format!("/tags/{bug}/trend"), AGENT),
+        ("/auth/me/tags".to_string(), AGENT),
+        ("/tags/manage".to_string(), ADMIN),
+    ] {
+        let (status, _) = app.get(&uri, Some(as_user)).await;
+        assert_eq!(status, StatusCode::OK, "{uri}");
+        let reads = std::mem::take(&mut *seen.lock().unwrap());
+        assert!(!reads.is_empty(), "{uri} didn't use the replica");
+    }
+}
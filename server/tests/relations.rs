@@ -0,0 +1,314 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::{ADMIN, AGENT, CapturingEmailSender, OTHER_AGENT, TestApp};
+use sea_orm::{ActiveModelTrait, Set};
+use serde_json::{Value, json};
+use tagblaze::{models::ticket_tag_event, utils::time};
+
+fn names(tags: &Value) -> Vec<&str> {
+    tags.as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap())
+        .collect()
+}
+
+async fn agent_ticket(app: &TestApp) -> i64 {
+    let (_, tickets) = app.get("/tickets", Some(AGENT)).await;
+    tickets[0]["id"].as_i64().unwrap()
+}
+
+#[tokio::test]
+async fn attach_returns_the_full_tag_list() {
+    let app = TestApp::seeded().await;
+    let ticket = agent_ticket(&app).await;
+    let feature = app.tag_id("Feature").await;
+
+    let (status, tags) = app
+        .send(
+            Method::POST,
+            &format!("/relations/{ticket}/tags/{feature}"),
+            Some(AGENT),
+            None,
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(names(&tags), ["Bug", "Feature", "Urgent"]);
+}
+
+#[tokio::test]
+async fn attaching_an_attached_tag_is_idempotent_even_when_racing() {
+    let app = TestApp::seeded().await;
+    let ticket = agent_ticket(&app).await;
+    let feature = app.tag_id("Feature").await;
+    let uri = format!("/relations/{ticket}/tags/{feature}");
+
+    // Both requests pass straight to the insert; one loses on the unique index
+    let (first, second) = tokio::join!(
+        app.send(Method::POST, &uri, Some(AGENT), None),
+        app.send(Method::POST, &uri, Some(AGENT), None),
+    );
+    let mut statuses = [first.0, second.0];
+    statuses.sort();
+    assert_eq!(statuses, [StatusCode::OK, StatusCode::CREATED]);
+    assert_eq!(names(&first.1), ["Bug", "Feature", "Urgent"]);
+    assert_eq!(names(&second.1), ["Bug", "Feature", "Urgent"]);
+
+    let (status, tags) = app.send(Method::POST, &uri, Some(AGENT), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(names(&tags), ["Bug", "Feature", "Urgent"]);
+
+    // Only the winning attach made it into the history
+    let (_, history) = app
+        .get(&format!("/tickets/{ticket}/tag-history"), Some(AGENT))
+        .await;
+    assert_eq!(history.as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn detach_returns_the_remaining_tags() {
+    let app = TestApp::seeded().await;
+    let ticket = agent_ticket(&app).await;
+    let bug = app.tag_id("Bug").await;
+
+    let (status, tags) = app
+        .send(
+            Method::DELETE,
+            &format!("/relations/{ticket}/tags/{bug}"),
+            Some(AGENT),
+            None,
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(names(&tags), ["Urgent"]);
+}
+
+#[tokio::test]
+async fn attach_then_detach_is_recorded_in_order() {
+    let app = TestApp::seeded().await;
+    let ticket = agent_ticket(&app).await;
+    let feature = app.tag_id("Feature").await;
+    let uri = format!("/relations/{ticket}/tags/{feature}");
+
+    let (status, _) = app.send(Method::POST, &uri, Some(AGENT), None).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = app.send(Method::DELETE, &uri, Some(AGENT), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, history) = app
+        .get(&format!("/tickets/{ticket}/tag-history"), Some(AGENT))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let history = history.as_array().unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0]["action"], "attached");
+    assert_eq!(history[1]["action"], "detached");
+    for event in history {
+        assert_eq!(event["tag_name"], "Feature");
+        assert_eq!(event["actor_email"], AGENT);
+    }
+
+    let (status, _) = app
+        .get(&format!("/tickets/{ticket}/tag-history"), Some(OTHER_AGENT))
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn tags_given_at_creation_are_recorded_as_attached() {
+    let app = TestApp::seeded().await;
+    let bug = app.tag_id("Bug").await;
+    let feature = app.tag_id("Feature").await;
+
+    let (status, ticket) = app
+        .send(
+            Method::POST,
+            "/tickets",
+            Some(AGENT),
+            Some(json!({ "title": "Tagged from the start", "tag_ids": [bug, feature] })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, history) = app
+        .get(
+            &format!("/tickets/{}/tag-history", ticket["id"]),
+            Some(AGENT),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let history = history.as_array().unwrap();
+    let mut tag_names: Vec<&str> = history
+        .iter()
+        .map(|e| e["tag_name"].as_str().unwrap())
+        .collect();
+    tag_names.sort();
+    assert_eq!(tag_names, ["Bug", "Feature"]);
+    for event in history {
+        assert_eq!(event["action"], "attached");
+        assert_eq!(event["actor_email"], AGENT);
+    }
+}
+
+#[tokio::test]
+async fn timeline_interleaves_creation_and_tag_events_by_time() {
+    let app = TestApp::seeded().await;
+    let ticket = agent_ticket(&app).await;
+    let bug = app.tag_id("Bug").await;
+    let feature = app.tag_id("Feature").await;
+
+    // Imported history from before the ticket existed here
+    ticket_tag_event::ActiveModel {
+        ticket_id: Set(ticket as i32),
+        tag_id: Set(bug),
+        action: Set("attached".into()),
+        created_at: Set(time::parse("2000-01-01T00:00:00Z").unwrap()),
+        ..Default::default()
+    }
+    .insert(app.db())
+    .await
+    .unwrap();
+    let (status, _) = app
+        .send(
+            Method::POST,
+            &format!("/relations/{ticket}/tags/{feature}"),
+            Some(AGENT),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, timeline) = app
+        .get(&format!("/tickets/{ticket}/timeline"), Some(AGENT))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let summary: Vec<(&str, Option<&str>)> = timeline
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| (e["type"].as_str().unwrap(), e["tag_name"].as_str()))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("tag", Some("Bug")),
+            ("created", None),
+            ("tag", Some("Feature"))
+        ]
+    );
+
+    let (status, _) = app
+        .get(&format!("/tickets/{ticket}/timeline"), Some(OTHER_AGENT))
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn batch_tags_match_individual_fetches() {
+    let app = TestApp::seeded().await;
+    let (_, tickets) = app.get("/tickets", Some(ADMIN)).await;
+    let mut ids: Vec<i64> = tickets
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["id"].as_i64().unwrap())
+        .collect();
+    assert!(ids.len() >= 2);
+    let missing = 999_999;
+    ids.push(missing);
+
+    let (status, batch) = app
+        .send(
+            Method::POST,
+            "/relations/tags/batch",
+            None,
+            Some(json!({ "ticket_ids": ids })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(batch.as_object().unwrap().len(), ids.len());
+
+    for id in &ids[..ids.len() - 1] {
+        let (_, single) = app.get(&format!("/relations/{id}/tags"), None).await;
+        assert_eq!(batch[id.to_string()], single, "ticket {id}");
+    }
+    assert_eq!(batch[missing.to_string()], json!([]));
+}
+
+#[tokio::test]
+async fn attaching_a_subscribed_tag_notifies_subscribers_who_can_see_the_ticket() {
+    let mailer = std::sync::Arc::new(CapturingEmailSender::default());
+    let app = TestApp::seeded().await.with_email_sender(mailer.clone());
+    let ticket_id = agent_ticket(&app).await;
+    let feature = app.tag_id("Feature").await;
+
+    let uri = format!("/tags/{feature}/subscribe");
+    let (status, _) = app.send(Method::POST, &uri, Some(ADMIN), None).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = app.send(Method::POST, &uri, Some(ADMIN), None).await;
+    assert_eq!(status, StatusCode::OK);
+    // Can't see the agent's ticket, so stays quiet
+    let (status, _) = app.send(Method::POST, &uri, Some(OTHER_AGENT), None).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let attach = format!("/relations/{ticket_id}/tags/{feature}");
+    let (status, _) = app.send(Method::POST, &attach, Some(AGENT), None).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let sent = mailer.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].to, ADMIN);
+    assert!(sent[0].subject.contains("Feature"));
+
+    // Re-attaching changes nothing, so nobody hears about it again
+    let (status, _) = app.send(Method::POST, &attach, Some(AGENT), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(mailer.sent().len(), 1);
+
+    // Unsubscribed admins aren't told about the next ticket
+    let (status, _) = app.send(Method::DELETE, &uri, Some(ADMIN), None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = app
+        .send(
+            Method::POST,
+            "/tickets",
+            Some(AGENT),
+            Some(json!({ "title": "Dark mode", "tag_ids": [feature] })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(mailer.sent().len(), 1);
+}
+
+#[tokio::test]
+async fn creating_a_ticket_with_a_subscribed_tag_notifies_subscribers() {
+    let mailer = std::sync::Arc::new(CapturingEmailSender::default());
+    let app = TestApp::seeded().await.with_email_sender(mailer.clone());
+    let bug = app.tag_id("Bug").await;
+
+    let uri = format!("/tags/{bug}/subscribe");
+    let (status, _) = app.send(Method::POST, &uri, Some(ADMIN), None).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = app
+        .send(Method::POST, "/tags/9999/subscribe", Some(ADMIN), None)
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = app
+        .send(
+            Method::POST,
+            "/tickets",
+            Some(AGENT),
+            Some(json!({ "title": "Crash on save", "tag_ids": [bug] })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let sent = mailer.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].to, ADMIN);
+    assert!(sent[0].body.contains("Crash on save"));
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode, header},
+};
+use tagblaze::routes::create_router;
+use tower::ServiceExt;
+
+async fn call(method: &str, uri: &str) -> axum::response::Response {
+    let app = create_router(common::lazy_state().await);
+    app.oneshot(
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn unsupported_method_lists_allowed_methods() {
+    let response = call("DELETE", "/health").await;
+
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(response.headers()[header::ALLOW], "GET,HEAD");
+
+    let body = common::read_json(response).await;
+    assert_eq!(body["error"], "method_not_allowed");
+    assert_eq!(body["method"], "DELETE");
+}
+
+#[tokio::test]
+async fn allow_header_covers_every_method_on_a_nested_path() {
+    let response = call("PATCH", "/tickets").await;
+
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    let allow = response.headers()[header::ALLOW].to_str().unwrap();
+    let mut methods: Vec<_> = allow.split(',').collect();
+    methods.sort();
+    assert_eq!(methods, ["GET", "HEAD", "POST"]);
+}
+
+#[tokio::test]
+async fn options_on_a_known_path_advertises_allowed_methods() {
+    let response = call("OPTIONS", "/tickets").await;
+
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let allow = response.headers()[header::ALLOW].to_str().unwrap();
+    let mut methods: Vec<_> = allow.split(',').collect();
+    methods.sort();
+    assert_eq!(methods, ["GET", "HEAD", "POST"]);
+
+    let response = call("OPTIONS", "/definitely/not/here").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn unknown_path_returns_json_404() {
+    let response = call("GET", "/definitely/not/here").await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body = common::read_json(response).await;
+    assert_eq!(body["error"], "not_found");
+    assert_eq!(body["path"], "/definitely/not/here");
+}
+
+#[tokio::test]
+async fn unknown_path_under_a_nested_router_returns_json_404() {
+    let response = call("GET", "/tags/1/nope").await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body = common::read_json(response).await;
+    assert_eq!(body["path"], "/tags/1/nope");
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{HeaderMap, Request, header},
+};
+use tagblaze::{config::Config, routes::create_router};
+use tower::ServiceExt;
+
+async fn headers_for(configure: impl FnOnce(&mut Config), uri: &str) -> HeaderMap {
+    let app = create_router(common::lazy_state_with(configure).await);
+    let response = app
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    response.headers().clone()
+}
+
+#[tokio::test]
+async fn security_headers_are_set_on_responses() {
+    let headers = headers_for(
+        |c| c.content_security_policy = "default-src 'none'".into(),
+        "/no-such-route",
+    )
+    .await;
+
+    assert_eq!(headers[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+    assert_eq!(headers[header::X_FRAME_OPTIONS], "DENY");
+    assert_eq!(headers[header::REFERRER_POLICY], "no-referrer");
+    assert_eq!(
+        headers[header::CONTENT_SECURITY_POLICY],
+        "default-src 'none'"
+    );
+}
+
+#[tokio::test]
+async fn empty_csp_is_not_sent() {
+    let headers = headers_for(|c| c.content_security_policy.clear(), "/no-such-route").await;
+
+    assert!(!headers.contains_key(header::CONTENT_SECURITY_POLICY));
+    assert_eq!(headers[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+}
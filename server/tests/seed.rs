@@ -0,0 +1,36 @@
+mod common;
+
+use common::TestApp;
+use sea_orm::{ActiveModelTrait, EntityTrait, PaginatorTrait, Set};
+use tagblaze::{
+    db::seed::seed_if_empty,
+    models::{tag, user},
+};
+
+#[tokio::test]
+async fn empty_database_gets_seeded() {
+    let app = TestApp::new().await;
+
+    assert!(seed_if_empty(app.db()).await.unwrap());
+
+    assert_eq!(user::Entity::find().count(app.db()).await.unwrap(), 3);
+    assert_eq!(tag::Entity::find().count(app.db()).await.unwrap(), 3);
+}
+
+#[tokio::test]
+async fn populated_database_is_left_untouched() {
+    let app = TestApp::seeded().await;
+    tag::ActiveModel {
+        name: Set("Custom".into()),
+        ..Default::default()
+    }
+    .insert(app.db())
+    .await
+    .unwrap();
+
+    assert!(!seed_if_empty(app.db()).await.unwrap());
+
+    let tags = tag::Entity::find().all(app.db()).await.unwrap();
+    assert_eq!(tags.len(), 4);
+    assert!(tags.iter().any(|t| t.name == "Custom"));
+}
@@ -0,0 +1,826 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode, header},
+};
+use chrono::{Datelike, Duration, NaiveDateTime, Utc};
+use common::{ADMIN, AGENT, OTHER_AGENT, TestApp};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, Set};
+use serde_json::json;
+use tagblaze::{
+    handlers::tag::MAX_TAG_NAME_LEN,
+    models::{tag, ticket_tag, ticket_tag_event},
+    utils::time,
+};
+
+#[tokio::test]
+async fn merging_a_tag_into_itself_is_rejected() {
+    let app = TestApp::seeded().await;
+    let bug = app.tag_id("Bug").await;
+
+    let (status, _) = app
+        .send(
+            Method::POST,
+            "/tags/merge",
+            Some(ADMIN),
+            Some(json!({ "from_id": bug, "into_id": bug })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn non_admins_cannot_merge_even_a_tag_into_itself() {
+    let app = TestApp::seeded().await;
+    let bug = app.tag_id("Bug").await;
+
+    let (status, _) = app
+        .send(
+            Method::POST,
+            "/tags/merge",
+            Some(AGENT),
+            Some(json!({ "from_id": bug, "into_id": bug })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn merging_into_a_tag_the_ticket_already_has_keeps_one_relation() {
+    let app = TestApp::seeded().await;
+    let (_, tickets) = app.get("/tickets", Some(AGENT)).await;
+    let ticket_id = tickets[0]["id"].as_i64().unwrap() as i32;
+    let (bug, urgent) = (app.tag_id("Bug").await, app.tag_id("Urgent").await);
+
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/tags/merge",
+            Some(ADMIN),
+            Some(json!({ "from_id": bug, "into_id": urgent })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["duplicates_removed"], 1);
+
+    let relations = ticket_tag::Entity::find()
+        .filter(ticket_tag::Column::TicketId.eq(ticket_id))
+        .filter(ticket_tag::Column::TagId.eq(urgent))
+        .count(app.db())
+        .await
+        .unwrap();
+    assert_eq!(relations, 1);
+    let leftover = ticket_tag::Entity::find()
+        .filter(ticket_tag::Column::TagId.eq(bug))
+        .count(app.db())
+        .await
+        .unwrap();
+    assert_eq!(leftover, 0);
+}
+
+#[tokio::test]
+async fn ticket_count_matches_the_tickets_carrying_the_tag() {
+    let app = TestApp::seeded().await;
+    let bug = app.tag_id("Bug").await;
+    let (_, tickets) = app.get("/tickets", Some(ADMIN)).await;
+    for ticket in tickets.as_array().unwrap() {
+        app.send(
+            Method::POST,
+            &format!("/relations/{}/tags/{bug}", ticket["id"]),
+            Some(ADMIN),
+            None,
+        )
+        .await;
+    }
+
+    let (status, body) = app.get(&format!("/tags/{bug}/tickets/count"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "count": 2 }));
+}
+
+#[tokio::test]
+async fn ticket_count_is_zero_for_an_unused_tag() {
+    let app = TestApp::seeded().await;
+    let (_, docs) = app
+        .send(
+            Method::POST,
+            "/tags",
+            Some(ADMIN),
+            Some(json!({ "name": "Docs" })),
+        )
+        .await;
+
+    let (status, body) = app
+        .get(&format!("/tags/{}/tickets/count", docs["id"]), None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "count": 0 }));
+}
+
+#[tokio::test]
+async fn ticket_count_for_an_unknown_tag_is_not_found() {
+    let app = TestApp::seeded().await;
+
+    let (status, _) = app.get("/tags/999999/tickets/count", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+async fn rename(app: &TestApp, id: i32, name: &str) -> (StatusCode, serde_json::Value) {
+    app.send(
+        Method::PATCH,
+        &format!("/tags/{id}"),
+        Some(ADMIN),
+        Some(json!({ "name": name })),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn renaming_to_a_taken_name_conflicts() {
+    let app = TestApp::seeded().await;
+
+    let bug = app.tag_id("Bug").await;
+
+    let (status, _) = rename(&app, bug, "Feature").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (_, tag) = app.get(&format!("/tags/{bug}"), None).await;
+    assert_eq!(tag["name"], "Bug");
+}
+
+#[tokio::test]
+async fn renaming_to_the_same_name_is_a_no_op() {
+    let app = TestApp::seeded().await;
+    let bug = app.tag_id("Bug").await;
+    let (_, before) = app.get(&format!("/tags/{bug}"), None).await;
+
+    let (status, after) = rename(&app, bug, "Bug").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(after, before);
+}
+
+#[tokio::test]
+async fn a_no_op_update_skips_the_write_and_says_so() {
+    let app = TestApp::seeded().await;
+    let bug = app.tag_id("Bug").await;
+    let (_, before) = app.get(&format!("/tags/{bug}"), None).await;
+    let patch = |body: serde_json::Value| {
+        app.request(
+            Request::builder()
+                .method(Method::PATCH)
+                .uri(format!("/tags/{bug}"))
+                .header(header::AUTHORIZATION, common::bearer(ADMIN))
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+    };
+
+    let response = patch(json!({ "name": "Bug", "description": null })).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-modified"], "false");
+    let after = common::read_json(response).await;
+    assert_eq!(after["updated_at"], before["updated_at"]);
+
+    let response = patch(json!({ "name": "Defect" })).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-modified"], "true");
+}
+
+#[tokio::test]
+async fn renaming_applies_the_create_name_rules() {
+    let app = TestApp::seeded().await;
+    let bug = app.tag_id("Bug").await;
+
+    let (status, tag) = rename(&app, bug, "  Defect  ").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(tag["name"], "Defect");
+
+    for bad in ["   ".to_owned(), "x".repeat(MAX_TAG_NAME_LEN + 1)] {
+        let (status, body) = rename(&app, bug, &bad).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "invalid_tag_name");
+    }
+
+    // Padding doesn't sneak past the case-insensitive uniqueness check
+    let (status, _) = rename(&app, bug, " feature ").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn renaming_to_a_free_name_succeeds() {
+    let app = TestApp::seeded().await;
+
+    let bug = app.tag_id("Bug").await;
+
+    let (status, tag) = rename(&app, bug, "Defect").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(tag["name"], "Defect");
+}
+
+#[tokio::test]
+async fn updating_a_non_name_field_advances_updated_at() {
+    let app = TestApp::seeded().await;
+    let bug = app.tag_id("Bug").await;
+    let (_, before) = app.get(&format!("/tags/{bug}"), None).await;
+
+    let (status, after) = app
+        .send(
+            Method::PATCH,
+            &format!("/tags/{bug}"),
+            Some(ADMIN),
+            Some(json!({ "description": "Something is broken" })),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(after["name"], "Bug");
+    assert_eq!(after["description"], "Something is broken");
+    let parse = |v: &serde_json::Value| time::parse(v.as_str().unwrap()).unwrap();
+    assert!(parse(&after["updated_at"]) > parse(&before["updated_at"]));
+}
+
+#[tokio::test]
+async fn batch_creates_new_names_and_skips_duplicates() {
+    let app = TestApp::seeded().await;
+
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/tags/batch",
+            Some(ADMIN),
+            Some(json!({ "names": ["bug", "Docs", " Performance ", "docs", "Urgent"] })),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::OK);
+    let created: Vec<_> = body["created"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(created, ["Docs", "Performance"]);
+    assert_eq!(body["skipped"], json!(["bug", "docs", "Urgent"]));
+
+    let (_, tags) = app.get("/tags", None).await;
+    assert_eq!(tags.as_array().unwrap().len(), 5);
+}
+
+#[tokio::test]
+async fn one_invalid_name_fails_the_whole_batch() {
+    let app = TestApp::seeded().await;
+    let too_long = "x".repeat(65);
+
+    for bad in ["  ", too_long.as_str()] {
+        let (status, body) = app
+            .send(
+                Method::POST,
+                "/tags/batch",
+                Some(ADMIN),
+                Some(json!({ "names": ["Docs", bad] })),
+            )
+            .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "invalid_tag_name");
+    }
+
+    let (_, tags) = app.get("/tags", None).await;
+    assert_eq!(tags.as_array().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn deleted_tags_are_hidden_until_restored() {
+    let app = TestApp::seeded().await;
+    let bug = app.tag_id("Bug").await;
+
+    let (status, _) = app
+        .send(Method::DELETE, &format!("/tags/{bug}"), None, None)
+        .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (_, tags) = app.get("/tags", None).await;
+    assert!(tags.as_array().unwrap().iter().all(|t| t["name"] != "Bug"));
+    let (_, all) = app.get("/tags?include_deleted=true", None).await;
+    assert_eq!(all.as_array().unwrap().len(), 3);
+
+    // Tickets still resolve the deleted tag, marked as deleted
+    let (_, tickets) = app.get("/tickets", Some(AGENT)).await;
+    let (_, ticket_tags) = app
+        .get(&format!("/relations/{}/tags", tickets[0]["id"]), None)
+        .await;
+    let deleted = ticket_tags
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|t| t["name"] == "Bug")
+        .unwrap();
+    assert!(!deleted["deleted_at"].is_null());
+
+    let (status, _) = app
+        .send(
+            Method::POST,
+            &format!("/tags/{bug}/restore"),
+            Some(AGENT),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, restored) = app
+        .send(
+            Method::POST,
+            &format!("/tags/{bug}/restore"),
+            Some(ADMIN),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(restored["deleted_at"].is_null());
+
+    let (_, tags) = app.get("/tags", None).await;
+    assert_eq!(tags.as_array().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn creating_a_tag_invalidates_the_cached_list() {
+    let app = TestApp::seeded().await;
+
+    let (_, tags) = app.get("/tags", None).await;
+    assert_eq!(tags.as_array().unwrap().len(), 3);
+
+    // Written behind the API's back, so the warm cache can't see it
+    tag::ActiveModel {
+        name: Set("Stale".into()),
+        ..Default::default()
+    }
+    .insert(app.db())
+    .await
+    .unwrap();
+    let (_, tags) = app.get("/tags", None).await;
+    assert_eq!(tags.as_array().unwrap().len(), 3);
+
+    let (status, _) = app
+        .send(
+            Method::POST,
+            "/tags",
+            Some(AGENT),
+            Some(json!({ "name": "Docs" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, tags) = app.get("/tags", None).await;
+    let names: Vec<&str> = tags
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names.len(), 5);
+    assert_eq!(names[3..], ["Stale", "Docs"]);
+}
+
+#[tokio::test]
+async fn tag_names_are_normalized_and_blank_names_rejected() {
+    let app = TestApp::new().await;
+    tagblaze::db::seed::seed_users(app.db()).await.unwrap();
+
+    let create = |name: &'static str| {
+        app.send(
+            Method::POST,
+            "/tags",
+            Some(AGENT),
+            Some(json!({ "name": name })),
+        )
+    };
+
+    let (status, tag) = create("  Bug  ").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(tag["name"], "Bug");
+
+    let (status, tag) = create(" Needs \t  triage ").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(tag["name"], "Needs triage");
+
+    let (status, body) = create("   ").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_tag_name");
+}
+
+#[tokio::test]
+async fn tag_ownership_limits_changes_to_owner_and_admins() {
+    let app = TestApp::seeded_with(|c| c.tag_ownership = true).await;
+
+    let (status, created) = app
+        .send(
+            Method::POST,
+            "/tags",
+            Some(AGENT),
+            Some(json!({ "name": "Mine" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(created["owner_id"], app.user_id(AGENT).await);
+    let mine = created["id"].as_i64().unwrap();
+
+    // Another agent can neither rename nor delete it
+    let (status, _) = app
+        .send(
+            Method::PATCH,
+            &format!("/tags/{mine}"),
+            Some(OTHER_AGENT),
+            Some(json!({ "name": "Theirs" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = app
+        .send(
+            Method::DELETE,
+            &format!("/tags/{mine}"),
+            Some(OTHER_AGENT),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Anonymous callers must identify themselves
+    let (status, _) = app
+        .send(Method::DELETE, &format!("/tags/{mine}"), None, None)
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // The owner can
+    let (status, _) = app
+        .send(
+            Method::PATCH,
+            &format!("/tags/{mine}"),
+            Some(AGENT),
+            Some(json!({ "name": "Still mine" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Global tags belong to admins
+    let bug = app.tag_id("Bug").await;
+    let (status, _) = app
+        .send(Method::DELETE, &format!("/tags/{bug}"), Some(AGENT), None)
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = app
+        .send(Method::DELETE, &format!("/tags/{bug}"), Some(ADMIN), None)
+        .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = app
+        .send(Method::DELETE, &format!("/tags/{mine}"), Some(AGENT), None)
+        .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn tag_summary_breaks_tickets_down_by_status() {
+    let app = TestApp::seeded().await;
+    let bug = app.tag_id("Bug").await;
+    for status in ["open", "closed"] {
+        let (code, _) = app
+            .send(
+                Method::POST,
+                "/tickets",
+                Some(AGENT),
+                Some(
+                    json!({ "title": format!("{status} bug"), "status": status, "tag_ids": [bug] }),
+                ),
+            )
+            .await;
+        assert_eq!(code, StatusCode::OK);
+    }
+
+    let (status, summary) = app.get(&format!("/tags/{bug}/summary"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(summary["name"], "Bug");
+    assert_eq!(summary["total_tickets"], 3);
+    // The seeded ticket has no status
+    assert_eq!(
+        summary["by_status"],
+        json!({ "closed": 1, "none": 1, "open": 1 })
+    );
+
+    let (status, _) = app.get("/tags/999999/summary", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn tag_trend_buckets_attaches_by_monday_starting_week_and_fills_gaps() {
+    let app = TestApp::seeded().await;
+    let bug = app.tag_id("Bug").await;
+    let (_, tickets) = app.get("/tickets", Some(AGENT)).await;
+    let ticket = tickets[0]["id"].as_i64().unwrap() as i32;
+
+    let today = Utc::now().date_naive();
+    let monday = today - Duration::days(today.weekday().num_days_from_monday().into());
+    let week_start = monday.and_hms_opt(0, 0, 0).unwrap();
+    let record = |at: NaiveDateTime, action: &str| ticket_tag_event::ActiveModel {
+        ticket_id: Set(ticket),
+        tag_id: Set(bug),
+        user_id: Set(None),
+        action: Set(action.into()),
+        created_at: Set(at),
+        ..Default::default()
+    };
+    for (at, action) in [
+        (week_start, "attached"),
+        (week_start, "detached"),
+        (week_start - Duration::seconds(1), "attached"),
+        (week_start - Duration::weeks(1), "attached"),
+        (week_start - Duration::days(15), "attached"),
+    ] {
+        record(at, action).insert(app.db()).await.unwrap();
+    }
+
+    let (status, body) = app
+        .get(&format!("/tags/{bug}/trend?bucket=week&weeks=3"), None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["bucket"], "week");
+    let fmt = |d: chrono::NaiveDate| d.format("%Y-%m-%d").to_string();
+    assert_eq!(
+        body["buckets"],
+        json!([
+            { "start": fmt(monday - Duration::weeks(2)), "count": 0 },
+            { "start": fmt(monday - Duration::weeks(1)), "count": 2 },
+            { "start": fmt(monday), "count": 1 },
+        ])
+    );
+
+    let (_, body) = app
+        .get(&format!("/tags/{bug}/trend?bucket=day&weeks=1"), None)
+        .await;
+    let days = body["buckets"].as_array().unwrap();
+    assert_eq!(days.len(), 7);
+    assert_eq!(days[6]["start"], fmt(today));
+
+    let (status, body) = app
+        .get(&format!("/tags/{bug}/trend?bucket=month&weeks=0"), None)
+        .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body["errors"]["bucket"].is_string());
+    assert!(body["errors"]["weeks"].is_string());
+
+    let (status, _) = app.get("/tags/999999/trend", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+fn uses(tags: &serde_json::Value) -> Vec<(String, i64)> {
+    tags.as_array()
+        .unwrap()
+        .iter()
+        .map(|t| {
+            (
+                t["name"].as_str().unwrap().to_string(),
+                t["uses"].as_i64().unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn my_tags_are_the_callers_own_ordered_by_use() {
+    let app = TestApp::seeded().await;
+    let urgent = app.tag_id("Urgent").await;
+    let feature = app.tag_id("Feature").await;
+
+    // The seeded agent ticket already carries Bug and Urgent
+    let (status, _) = app
+        .send(
+            Method::POST,
+            "/tickets",
+            Some(AGENT),
+            Some(json!({ "title": "Second", "tag_ids": [urgent, feature] })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, mine) = app.get("/auth/me/tags", Some(AGENT)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        uses(&mine),
+        [
+            ("Urgent".into(), 2),
+            ("Bug".into(), 1),
+            ("Feature".into(), 1)
+        ]
+    );
+    assert_eq!(mine[0]["id"], urgent);
+
+    let (_, theirs) = app.get("/auth/me/tags", Some(ADMIN)).await;
+    assert_eq!(uses(&theirs), [("Feature".to_string(), 1)]);
+    let (_, none) = app.get("/auth/me/tags", Some(OTHER_AGENT)).await;
+    assert_eq!(none, json!([]));
+
+    // Soft-deleted tags drop out of the picker
+    let bug = app.tag_id("Bug").await;
+    let (status, _) = app
+        .send(Method::DELETE, &format!("/tags/{bug}"), Some(ADMIN), None)
+        .await;
+    assert!(status.is_success());
+    let (_, mine) = app.get("/auth/me/tags", Some(AGENT)).await;
+    assert_eq!(uses(&mine), [("Urgent".into(), 2), ("Feature".into(), 1)]);
+}
+
+#[tokio::test]
+async fn tag_management_lists_every_tag_and_flags_unused_ones() {
+    let app = TestApp::seeded().await;
+    let (status, _) = app
+        .send(
+            Method::POST,
+            "/tags",
+            Some(ADMIN),
+            Some(json!({ "name": "Archive" })),
+        )
+        .await;
+    assert!(status.is_success());
+
+    let (status, tags) = app.get("/tags/manage", Some(ADMIN)).await;
+    assert_eq!(status, StatusCode::OK);
+    let rows: Vec<(&str, bool, i64)> = tags
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| {
+            (
+                t["name"].as_str().unwrap(),
+                t["in_use"].as_bool().unwrap(),
+                t["ticket_count"].as_i64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        rows,
+        [
+            ("Archive", false, 0),
+            ("Bug", true, 1),
+            ("Feature", true, 1),
+            ("Urgent", true, 1),
+        ]
+    );
+    assert!(tags[0]["id"].is_i64());
+
+    let (status, _) = app.get("/tags/manage", Some(AGENT)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = app.get("/tags/manage", None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn validate_splits_real_ids_from_bogus_ones() {
+    let app = TestApp::seeded().await;
+    let bug = app.tag_id("Bug").await;
+    let urgent = app.tag_id("Urgent").await;
+
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/tags/validate",
+            None,
+            Some(json!({ "ids": [9999, urgent, -1, bug, urgent] })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body,
+        json!({ "valid": [urgent, bug], "invalid": [9999, -1] })
+    );
+}
+
+async fn create_as(app: &TestApp, caller: &str, name: &str) -> (StatusCode, serde_json::Value) {
+    app.send(
+        Method::POST,
+        "/tags",
+        Some(caller),
+        Some(json!({ "name": name })),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn tag_names_are_unique_per_owner() {
+    let app = TestApp::seeded_with(|c| c.tag_ownership = true).await;
+
+    // Different owners may share a name, including with a global tag
+    let (status, zoya_bug) = create_as(&app, AGENT, "bug").await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = create_as(&app, OTHER_AGENT, "Bug").await;
+    assert_eq!(status, StatusCode::OK);
+
+    // The same owner may not, whatever the case
+    let (status, _) = create_as(&app, AGENT, "BUG").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _) = create_as(&app, ADMIN, "urgent").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Renames and batches follow the same rule
+    let (_, docs) = create_as(&app, AGENT, "Docs").await;
+    let (status, _) = app
+        .send(
+            Method::PATCH,
+            &format!("/tags/{}", docs["id"]),
+            Some(AGENT),
+            Some(json!({ "name": "Bug" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, renamed) = app
+        .send(
+            Method::PATCH,
+            &format!("/tags/{}", zoya_bug["id"]),
+            Some(AGENT),
+            Some(json!({ "name": "Bug" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(renamed["name"], "Bug");
+
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/tags/batch",
+            Some(OTHER_AGENT),
+            Some(json!({ "names": ["docs", "bug"] })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["created"][0]["name"], "docs");
+    assert_eq!(body["skipped"], json!(["bug"]));
+}
+
+#[tokio::test]
+async fn tag_names_are_unique_across_all_tags_without_ownership() {
+    let app = TestApp::seeded().await;
+
+    // Tags are shared, so another agent's or a global tag's name is taken
+    let (status, docs) = create_as(&app, AGENT, "Docs").await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = create_as(&app, OTHER_AGENT, "docs").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _) = create_as(&app, AGENT, "BUG").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Renames and batches follow the same rule
+    let (status, _) = app
+        .send(
+            Method::PATCH,
+            &format!("/tags/{}", docs["id"]),
+            Some(AGENT),
+            Some(json!({ "name": "urgent" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/tags/batch",
+            Some(OTHER_AGENT),
+            Some(json!({ "names": ["Guides", "DOCS"] })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["created"][0]["name"], "Guides");
+    assert_eq!(body["skipped"], json!(["DOCS"]));
+}
+
+#[tokio::test]
+async fn tag_list_etag_answers_304_until_a_tag_changes() {
+    let app = TestApp::seeded().await;
+    let list = |etag: Option<&str>| {
+        let mut builder = Request::builder().uri("/tags");
+        if let Some(etag) = etag {
+            builder = builder.header(header::IF_NONE_MATCH, etag);
+        }
+        app.request(builder.body(Body::empty()).unwrap())
+    };
+
+    let response = list(None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let etag = response.headers()[header::ETAG]
+        .to_str()
+        .unwrap()
+        .to_owned();
+    assert!(etag.starts_with('"') && etag.ends_with('"'), "{etag}");
+
+    let response = list(Some(&etag)).await;
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(response.headers()[header::ETAG], etag.as_str());
+    assert_eq!(common::read_json(response).await, serde_json::Value::Null);
+
+    let bug = app.tag_id("Bug").await;
+    assert_eq!(rename(&app, bug, "Defect").await.0, StatusCode::OK);
+
+    let response = list(Some(&etag)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_ne!(response.headers()[header::ETAG], etag.as_str());
+}
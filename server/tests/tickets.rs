@@ -0,0 +1,1236 @@
+mod common;
+
+use axum::{
+    body::Body,
+    extract::Query,
+    http::{Method, Request, StatusCode, Uri, header},
+};
+use common::{ADMIN, AGENT, OTHER_AGENT, TestApp, bearer, read_json};
+use sea_orm::{
+    ActiveModelTrait, ConnectionTrait, EntityTrait, PaginatorTrait, Set, TransactionTrait,
+};
+use serde_json::json;
+use tagblaze::{
+    events::{self, TicketEventKind},
+    handlers::ticket::{TicketListQuery, TicketScope},
+    models::ticket,
+    utils::{cursor::Cursor, time},
+};
+
+fn parse(uri: &str) -> Result<TicketListQuery, String> {
+    let uri: Uri = uri.parse().unwrap();
+    Query::<TicketListQuery>::try_from_uri(&uri)
+        .map(|Query(q)| q)
+        .map_err(|e| e.to_string())
+}
+
+#[test]
+fn scope_defaults_to_none() {
+    assert_eq!(parse("/tickets").unwrap().scope, None);
+}
+
+#[test]
+fn scope_assigned_to_me_parses() {
+    let query = parse("/tickets?scope=assigned_to_me").unwrap();
+    assert_eq!(query.scope, Some(TicketScope::AssignedToMe));
+}
+
+#[test]
+fn scope_created_by_me_parses() {
+    let query = parse("/tickets?scope=created_by_me").unwrap();
+    assert_eq!(query.scope, Some(TicketScope::CreatedByMe));
+}
+
+#[test]
+fn unknown_scope_is_rejected() {
+    assert!(parse("/tickets?scope=everything").is_err());
+}
+
+async fn scoped_titles(app: &TestApp, as_user: &str, scope: &str) -> Vec<String> {
+    let (status, tickets) = app
+        .get(&format!("/tickets?scope={scope}"), Some(as_user))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    tickets
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["title"].as_str().unwrap().to_owned())
+        .collect()
+}
+
+#[tokio::test]
+async fn scopes_follow_assignee_and_creator_after_a_reassignment() {
+    let app = TestApp::seeded().await;
+    let (status, _) = app
+        .send(
+            Method::POST,
+            "/tickets",
+            Some(AGENT),
+            Some(json!({ "title": "Handed over" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (from, into) = (app.user_id(AGENT).await, app.user_id(OTHER_AGENT).await);
+    let (status, _) = app
+        .send(
+            Method::POST,
+            &format!("/admin/users/{from}/reassign-to/{into}"),
+            Some(ADMIN),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let handed_over = "Handed over".to_owned();
+    assert!(
+        scoped_titles(&app, OTHER_AGENT, "assigned_to_me")
+            .await
+            .contains(&handed_over)
+    );
+    assert!(
+        !scoped_titles(&app, AGENT, "assigned_to_me")
+            .await
+            .contains(&handed_over)
+    );
+    assert!(
+        scoped_titles(&app, AGENT, "created_by_me")
+            .await
+            .contains(&handed_over)
+    );
+    assert!(
+        !scoped_titles(&app, OTHER_AGENT, "created_by_me")
+            .await
+            .contains(&handed_over)
+    );
+}
+
+fn sample_ticket(id: i32, user_id: i32) -> ticket::Model {
+    ticket::Model {
+        id,
+        title: "Stream me".into(),
+        description: None,
+        status: Some("open".into()),
+        user_id: Some(user_id),
+        created_by: Some(user_id),
+        updated_by: None,
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+#[tokio::test]
+async fn created_ticket_is_delivered_to_subscribers() {
+    use futures::StreamExt;
+
+    let app = TestApp::seeded().await;
+    let stream = app
+        .request(
+            Request::builder()
+                .uri("/tickets/stream")
+                .header(header::AUTHORIZATION, bearer(AGENT))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(stream.status(), StatusCode::OK);
+    let mut frames = stream.into_body().into_data_stream();
+
+    let (status, created) = app
+        .send(
+            Method::POST,
+            "/tickets",
+            Some(AGENT),
+            Some(json!({ "title": "Stream me" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let mut received = String::new();
+    let event = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        loop {
+            let chunk = frames.next().await.unwrap().unwrap();
+            received.push_str(std::str::from_utf8(&chunk).unwrap());
+            if let Some(event) = received
+                .split("\n\n")
+                .find(|e| e.contains("event: ticket_created"))
+            {
+                return event.to_owned();
+            }
+        }
+    })
+    .await
+    .expect("no ticket_created event within 5s");
+
+    let data = event
+        .lines()
+        .find_map(|l| l.strip_prefix("data: "))
+        .unwrap();
+    let ticket: serde_json::Value = serde_json::from_str(data).unwrap();
+    assert_eq!(ticket["id"], created["id"]);
+}
+
+#[test]
+fn events_are_only_visible_to_owner_or_admin() {
+    let event = events::TicketEvent {
+        kind: TicketEventKind::Updated,
+        ticket: sample_ticket(1, 7),
+    };
+
+    assert!(event.visible_to(7, false));
+    assert!(!event.visible_to(8, false));
+    assert!(event.visible_to(8, true));
+}
+
+async fn create_with_status(app: &TestApp, as_user: &str, status: &str) {
+    let (code, _) = app
+        .send(
+            Method::POST,
+            "/tickets",
+            Some(as_user),
+            Some(json!({ "title": format!("{status} ticket"), "status": status })),
+        )
+        .await;
+    assert_eq!(code, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn statuses_are_counted_per_caller_and_globally_for_admins() {
+    let app = TestApp::seeded().await;
+    create_with_status(&app, AGENT, "open").await;
+    create_with_status(&app, AGENT, "open").await;
+    create_with_status(&app, AGENT, "closed").await;
+    create_with_status(&app, OTHER_AGENT, "closed").await;
+    create_with_status(&app, OTHER_AGENT, "pending").await;
+
+    let (status, mine) = app.get("/tickets/statuses", Some(AGENT)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        mine,
+        json!([
+            { "status": "closed", "count": 1 },
+            { "status": "open", "count": 2 },
+            { "status": null, "count": 1 },
+        ])
+    );
+
+    let (_, all) = app.get("/tickets/statuses", Some(ADMIN)).await;
+    assert_eq!(
+        all,
+        json!([
+            { "status": "closed", "count": 2 },
+            { "status": "open", "count": 2 },
+            { "status": "pending", "count": 1 },
+            { "status": null, "count": 2 },
+        ])
+    );
+}
+
+#[tokio::test]
+async fn my_stats_only_count_the_callers_tickets() {
+    let app = TestApp::seeded().await;
+    create_with_status(&app, AGENT, "open").await;
+    create_with_status(&app, AGENT, "open").await;
+    create_with_status(&app, OTHER_AGENT, "closed").await;
+
+    let (status, stats) = app.get("/auth/me/stats", Some(AGENT)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        stats,
+        json!({
+            "total": 3,
+            "by_status": [
+                { "status": "open", "count": 2 },
+                { "status": null, "count": 1 },
+            ]
+        })
+    );
+
+    // Admins get their own numbers here, not the global view
+    let (_, stats) = app.get("/auth/me/stats", Some(ADMIN)).await;
+    assert_eq!(
+        stats,
+        json!({ "total": 1, "by_status": [{ "status": null, "count": 1 }] })
+    );
+}
+
+#[test]
+fn include_tags_is_recognised_among_other_values() {
+    assert!(!parse("/tickets").unwrap().includes_tags());
+    assert!(parse("/tickets?include=tags").unwrap().includes_tags());
+    assert!(
+        parse("/tickets?include=owner,%20tags")
+            .unwrap()
+            .includes_tags()
+    );
+    assert!(!parse("/tickets?include=owner").unwrap().includes_tags());
+}
+
+fn sorted_by_id(tags: &serde_json::Value) -> Vec<serde_json::Value> {
+    let mut tags = tags.as_array().unwrap().clone();
+    tags.sort_by_key(|t| t["id"].as_i64());
+    tags
+}
+
+#[tokio::test]
+async fn inline_tags_match_per_ticket_fetches() {
+    let app = TestApp::seeded().await;
+
+    let (status, list) = app.get("/tickets?include=tags", Some(ADMIN)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let list = list.as_array().unwrap();
+    assert!(!list.is_empty());
+    for ticket in list {
+        let (_, fetched) = app
+            .get(&format!("/relations/{}/tags", ticket["id"]), None)
+            .await;
+        assert_eq!(sorted_by_id(&ticket["tags"]), sorted_by_id(&fetched));
+    }
+    assert!(
+        list.iter()
+            .any(|t| !t["tags"].as_array().unwrap().is_empty())
+    );
+
+    let (_, plain) = app.get("/tickets", Some(ADMIN)).await;
+    assert!(plain[0].get("tags").is_none());
+}
+
+#[tokio::test]
+async fn ticket_download_is_a_json_attachment_with_tags() {
+    let app = TestApp::seeded().await;
+    let (_, tickets) = app.get("/tickets", Some(AGENT)).await;
+    let id = tickets[0]["id"].as_i64().unwrap();
+
+    let response = app
+        .request(
+            Request::builder()
+                .uri(format!("/tickets/{id}/download"))
+                .header(header::AUTHORIZATION, bearer(AGENT))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let headers = response.headers().clone();
+    assert_eq!(headers["content-type"], "application/json");
+    assert_eq!(
+        headers["content-disposition"],
+        format!("attachment; filename=\"ticket-{id}.json\"")
+    );
+
+    let body = read_json(response).await;
+    assert_eq!(body["id"], id);
+    let mut names: Vec<_> = body["tags"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap().to_string())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["Bug", "Urgent"]);
+
+    let (status, _) = app
+        .get(&format!("/tickets/{id}/download"), Some(OTHER_AGENT))
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn available_tags_flag_the_attached_ones() {
+    let app = TestApp::seeded().await;
+    let (_, tickets) = app.get("/tickets", Some(AGENT)).await;
+    let id = tickets[0]["id"].as_i64().unwrap();
+
+    let (status, tags) = app
+        .get(&format!("/tickets/{id}/tags/available"), Some(AGENT))
+        .await;
+
+    assert_eq!(status, StatusCode::OK);
+    let flags: Vec<_> = tags
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| {
+            (
+                t["name"].as_str().unwrap(),
+                t["attached"].as_bool().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(flags, [("Bug", true), ("Feature", false), ("Urgent", true)]);
+
+    let (status, _) = app
+        .get(&format!("/tickets/{id}/tags/available"), Some(OTHER_AGENT))
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn available_tags_leave_out_soft_deleted_ones() {
+    let app = TestApp::seeded().await;
+    let (_, tickets) = app.get("/tickets", Some(AGENT)).await;
+    let id = tickets[0]["id"].as_i64().unwrap();
+    let feature = app.tag_id("Feature").await;
+    let (status, _) = app
+        .send(
+            Method::DELETE,
+            &format!("/tags/{feature}"),
+            Some(ADMIN),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (_, tags) = app
+        .get(&format!("/tickets/{id}/tags/available"), Some(AGENT))
+        .await;
+    let names: Vec<_> = tags
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["Bug", "Urgent"]);
+}
+
+#[tokio::test]
+async fn created_range_is_inclusive_and_rejects_inverted_bounds() {
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, sea_query::Expr};
+
+    let app = TestApp::seeded().await;
+    let day = |d: u32| {
+        chrono::NaiveDate::from_ymd_opt(2024, 1, d)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap()
+    };
+
+    let mut ids = Vec::new();
+    for d in [1, 10, 20] {
+        let (_, created) = app
+            .send(
+                Method::POST,
+                "/tickets",
+                Some(ADMIN),
+                Some(json!({ "title": format!("Jan {d}") })),
+            )
+            .await;
+        let id = created["id"].as_i64().unwrap() as i32;
+        ticket::Entity::update_many()
+            .col_expr(ticket::Column::CreatedAt, Expr::value(day(d)))
+            .filter(ticket::Column::Id.eq(id))
+            .exec(app.db())
+            .await
+            .unwrap();
+        ids.push(id);
+    }
+
+    let (from, to) = (time::to_rfc3339(day(10)), time::to_rfc3339(day(20)));
+    let (status, list) = app
+        .get(
+            &format!("/tickets?created_after={from}&created_before={to}"),
+            Some(ADMIN),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let found: Vec<i32> = list
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["id"].as_i64().unwrap() as i32)
+        .collect();
+    assert_eq!(found, ids[1..]);
+
+    let (status, body) = app
+        .get(
+            &format!("/tickets?created_after={to}&created_before={from}"),
+            Some(ADMIN),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_created_range");
+
+    let (status, _) = app.get("/tags?created_after=yesterday", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn failed_tag_insert_rolls_back_the_ticket() {
+    let app = TestApp::seeded().await;
+    let bug = app.tag_id("Bug").await;
+    let before = ticket::Entity::find().count(app.db()).await.unwrap();
+
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/tickets",
+            Some(AGENT),
+            Some(json!({ "title": "Half written", "tag_ids": [bug, 999_999] })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "unknown_tag");
+    assert_eq!(
+        ticket::Entity::find().count(app.db()).await.unwrap(),
+        before
+    );
+
+    let (status, created) = app
+        .send(
+            Method::POST,
+            "/tickets",
+            Some(AGENT),
+            Some(json!({ "title": "Tagged", "tag_ids": [bug, bug] })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, tags) = app
+        .get(&format!("/relations/{}/tags", created["id"]), None)
+        .await;
+    // A repeated tag is attached once
+    assert_eq!(tags.as_array().unwrap().len(), 1);
+    assert_eq!(tags[0]["name"], "Bug");
+}
+
+async fn write_description(
+    app: &TestApp,
+    method: Method,
+    uri: &str,
+    description: &str,
+) -> (StatusCode, serde_json::Value) {
+    app.send(
+        method,
+        uri,
+        Some(AGENT),
+        Some(json!({ "title": "Long", "description": description })),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn overlong_descriptions_are_rejected_by_default() {
+    let app = TestApp::seeded_with(|c| c.max_description_len = 5).await;
+
+    let (status, at_limit) = write_description(&app, Method::POST, "/tickets", "abcde").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(at_limit["description"], "abcde");
+    assert!(at_limit.get("description_truncated").is_none());
+
+    for uri in ["/tickets", "/tickets?on_overflow=reject"] {
+        let (status, body) = write_description(&app, Method::POST, uri, "abcdef").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body,
+            json!({ "error": "description_too_long", "max_len": 5 })
+        );
+    }
+
+    let uri = format!("/tickets/{}", at_limit["id"]);
+    let (status, _) = write_description(&app, Method::PUT, &uri, "abcdef").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (_, unchanged) = app.get(&uri, Some(AGENT)).await;
+    assert_eq!(unchanged["description"], "abcde");
+}
+
+#[tokio::test]
+async fn overlong_descriptions_can_be_truncated() {
+    let app = TestApp::seeded_with(|c| c.max_description_len = 5).await;
+
+    let (status, created) = write_description(
+        &app,
+        Method::POST,
+        "/tickets?on_overflow=truncate",
+        "abcdef",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(created["description"], "abcde");
+    assert_eq!(created["description_truncated"], true);
+
+    // Characters, not bytes
+    let uri = format!("/tickets/{}?on_overflow=truncate", created["id"]);
+    let (status, updated) = write_description(&app, Method::PUT, &uri, "héllo wörld").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["description"], "héllo");
+    assert_eq!(updated["description_truncated"], true);
+
+    let (_, fits) = write_description(&app, Method::PUT, &uri, "short").await;
+    assert!(fits.get("description_truncated").is_none());
+}
+
+#[tokio::test]
+async fn an_empty_update_is_rejected_without_touching_the_ticket() {
+    let app = TestApp::seeded().await;
+    let (_, tickets) = app.get("/tickets", Some(AGENT)).await;
+    let uri = format!("/tickets/{}", tickets[0]["id"]);
+    let (_, before) = app.get(&uri, Some(AGENT)).await;
+
+    let (status, _) = app
+        .send(Method::PUT, &uri, Some(AGENT), Some(json!({})))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (_, after) = app.get(&uri, Some(AGENT)).await;
+    assert_eq!(after["updated_at"], before["updated_at"]);
+    assert_eq!(after["updated_by"], before["updated_by"]);
+}
+
+#[test]
+fn fields_are_checked_against_the_allowlist() {
+    assert!(parse("/tickets").unwrap().fields().unwrap().is_none());
+    let fields = parse("/tickets?fields=id,%20title,id").unwrap().fields();
+    assert_eq!(fields.unwrap().unwrap().len(), 2);
+
+    let err = parse("/tickets?fields=id,password").unwrap().fields();
+    assert_eq!(err.unwrap_err(), "unknown field 'password'");
+    assert!(parse("/tickets?fields=,").unwrap().fields().is_err());
+}
+
+#[tokio::test]
+async fn sparse_fieldsets_return_only_requested_columns() {
+    let app = TestApp::seeded().await;
+
+    let (status, tickets) = app
+        .get("/tickets?fields=id,title,status", Some(AGENT))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let ticket = tickets[0].as_object().unwrap();
+    let mut keys: Vec<&str> = ticket.keys().map(String::as_str).collect();
+    keys.sort();
+    assert_eq!(keys, ["id", "status", "title"]);
+
+    let (status, body) = app.get("/tickets?fields=id,secret", Some(AGENT)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_fields");
+
+    let (status, _) = app
+        .get("/tickets?fields=id&include=tags", Some(AGENT))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn timestamps_format_as_utc_rfc3339() {
+    let at = chrono::NaiveDate::from_ymd_opt(2024, 1, 1)
+        .unwrap()
+        .and_hms_milli_opt(9, 30, 0, 250)
+        .unwrap();
+
+    assert_eq!(time::to_rfc3339(at), "2024-01-01T09:30:00.250Z");
+    assert_eq!(time::parse("2024-01-01T11:30:00.25+02:00"), Some(at));
+    assert_eq!(time::parse("2024-01-01T09:30:00.250"), Some(at));
+}
+
+#[tokio::test]
+async fn responses_carry_utc_timestamps() {
+    let app = TestApp::seeded().await;
+
+    let (_, created) = app
+        .send(
+            Method::POST,
+            "/tickets",
+            Some(AGENT),
+            Some(json!({ "title": "When?" })),
+        )
+        .await;
+    let created_at = created["created_at"].as_str().unwrap();
+    assert!(created_at.ends_with('Z'), "{created_at}");
+    let parsed = chrono::DateTime::parse_from_rfc3339(created_at).unwrap();
+    assert!((chrono::Utc::now() - parsed.to_utc()).num_seconds().abs() < 60);
+
+    let (_, projected) = app.get("/tickets?fields=created_at", Some(AGENT)).await;
+    assert!(projected[0]["created_at"].as_str().unwrap().ends_with('Z'));
+}
+
+async fn create_titled(app: &TestApp, as_user: &str, title: &str, tag_ids: &[i32]) {
+    let (status, _) = app
+        .send(
+            Method::POST,
+            "/tickets",
+            Some(as_user),
+            Some(json!({ "title": title, "tag_ids": tag_ids })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+fn titles(list: &serde_json::Value) -> Vec<&str> {
+    let mut titles: Vec<&str> = list
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["title"].as_str().unwrap())
+        .collect();
+    titles.sort();
+    titles
+}
+
+#[tokio::test]
+async fn untagged_filter_returns_only_visible_tickets_without_tags() {
+    let app = TestApp::seeded().await;
+    let bug = app.tag_id("Bug").await;
+    create_titled(&app, AGENT, "bare one", &[]).await;
+    create_titled(&app, AGENT, "bare two", &[]).await;
+    create_titled(&app, AGENT, "tagged", &[bug]).await;
+    create_titled(&app, OTHER_AGENT, "someone else's", &[]).await;
+
+    let (status, mine) = app.get("/tickets?untagged=true", Some(AGENT)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(titles(&mine), ["bare one", "bare two"]);
+
+    let (_, all) = app.get("/tickets?untagged=true", Some(ADMIN)).await;
+    assert_eq!(titles(&all), ["bare one", "bare two", "someone else's"]);
+
+    // Composes with paging and projection
+    let (_, page) = app
+        .get(
+            "/tickets?untagged=true&fields=title&per_page=1",
+            Some(AGENT),
+        )
+        .await;
+    assert_eq!(page, json!([{ "title": "bare one" }]));
+}
+
+#[tokio::test]
+async fn unassigned_filter_is_admin_only_and_returns_ownerless_tickets() {
+    let app = TestApp::seeded().await;
+    create_titled(&app, AGENT, "assigned", &[]).await;
+    ticket::ActiveModel {
+        title: Set("needs triage".into()),
+        ..Default::default()
+    }
+    .insert(app.db())
+    .await
+    .unwrap();
+
+    let (status, _) = app.get("/tickets?unassigned=true", Some(AGENT)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, unassigned) = app.get("/tickets?unassigned=true", Some(ADMIN)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(titles(&unassigned), ["needs triage"]);
+    assert!(unassigned[0]["user_id"].is_null());
+}
+
+#[tokio::test]
+async fn feed_lists_recent_updates_newest_first() {
+    use sea_orm::{ColumnTrait, QueryFilter, sea_query::Expr};
+
+    // Users only, so the feed holds nothing but the tickets below
+    let app = TestApp::new().await;
+    tagblaze::db::seed::seed_users(app.db()).await.unwrap();
+    let now = chrono::Utc::now().naive_utc();
+
+    for (title, as_user, hours_ago) in [
+        ("two days ago", AGENT, 48),
+        ("two hours ago", AGENT, 2),
+        ("an hour ago", AGENT, 1),
+        ("not mine", OTHER_AGENT, 1),
+    ] {
+        let (_, created) = app
+            .send(
+                Method::POST,
+                "/tickets",
+                Some(as_user),
+                Some(json!({ "title": title })),
+            )
+            .await;
+        ticket::Entity::update_many()
+            .col_expr(
+                ticket::Column::UpdatedAt,
+                Expr::value(now - chrono::Duration::hours(hours_ago)),
+            )
+            .filter(ticket::Column::Id.eq(created["id"].as_i64().unwrap() as i32))
+            .exec(app.db())
+            .await
+            .unwrap();
+    }
+
+    let in_order = |list: &serde_json::Value| -> Vec<String> {
+        list.as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["title"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let (status, feed) = app.get("/tickets/feed", Some(AGENT)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(in_order(&feed), ["an hour ago", "two hours ago"]);
+
+    let since = time::to_rfc3339(now - chrono::Duration::minutes(90));
+    let (_, feed) = app
+        .get(&format!("/tickets/feed?since={since}"), Some(AGENT))
+        .await;
+    assert_eq!(in_order(&feed), ["an hour ago"]);
+
+    let since = time::to_rfc3339(now - chrono::Duration::days(3));
+    let (_, feed) = app
+        .get(
+            &format!("/tickets/feed?since={since}&per_page=2"),
+            Some(ADMIN),
+        )
+        .await;
+    assert_eq!(feed.as_array().unwrap().len(), 2);
+
+    let (status, body) = app.get("/tickets/feed?since=yesterday", Some(AGENT)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_since");
+}
+
+#[tokio::test]
+async fn exhausted_pool_returns_503_with_retry_after() {
+    let app = TestApp::seeded_with(|c| {
+        c.db_max_connections = Some(1);
+        c.db_acquire_timeout_secs = 1;
+    })
+    .await;
+
+    let (_, tickets) = app.get("/tickets", Some(AGENT)).await;
+    let id = tickets[0]["id"].as_i64().unwrap();
+
+    let requests = [
+        Request::get("/tickets")
+            .header(header::AUTHORIZATION, bearer(AGENT))
+            .body(Body::empty())
+            .unwrap(),
+        Request::get(format!("/tickets/{id}"))
+            .header(header::AUTHORIZATION, bearer(AGENT))
+            .body(Body::empty())
+            .unwrap(),
+        Request::post("/auth/login")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                json!({ "email": AGENT, "password": "devpass123" }).to_string(),
+            ))
+            .unwrap(),
+    ];
+    for request in requests {
+        let uri = request.uri().clone();
+
+        // Hold the only pooled connection so the handler can't get one
+        let held = app.db().begin().await.unwrap();
+        let response = app.request(request).await;
+        drop(held);
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE, "{uri}");
+        assert_eq!(response.headers()[header::RETRY_AFTER], "5", "{uri}");
+        assert_eq!(
+            read_json(response).await,
+            json!({ "error": "db_unavailable" }),
+            "{uri}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn titles_are_trimmed_and_blank_titles_rejected() {
+    let app = TestApp::seeded().await;
+
+    let (status, ticket) = app
+        .send(
+            Method::POST,
+            "/tickets",
+            Some(AGENT),
+            Some(json!({ "title": "  Printer on fire  " })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(ticket["title"], "Printer on fire");
+
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/tickets",
+            Some(AGENT),
+            Some(json!({ "title": " \t " })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body, json!({ "errors": { "title": "must not be blank" } }));
+}
+
+#[tokio::test]
+async fn every_invalid_ticket_field_is_reported_at_once() {
+    let app = TestApp::seeded().await;
+
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/tickets",
+            Some(AGENT),
+            Some(json!({ "title": "", "status": "  " })),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        body,
+        json!({ "errors": {
+            "title": "must not be blank",
+            "status": "must not be blank",
+        } })
+    );
+}
+
+#[test]
+fn cursor_round_trips_and_rejects_garbage() {
+    let cursor = Cursor {
+        created_at: time::parse("2025-03-01T12:30:00.123456Z").unwrap(),
+        id: 42,
+    };
+    assert_eq!(Cursor::decode(&cursor.encode()), Some(cursor));
+    assert_eq!(Cursor::decode("not a cursor"), None);
+}
+
+#[tokio::test]
+async fn cursor_scroll_is_stable_when_tickets_are_inserted_mid_scroll() {
+    let app = TestApp::seeded().await;
+    for title in ["one", "two", "three", "four", "five"] {
+        create_titled(&app, AGENT, title, &[]).await;
+    }
+    let (_, before) = app.get("/tickets?per_page=100", Some(AGENT)).await;
+    let expected = titles(&before);
+
+    let (status, first) = app.get("/tickets?limit=2", Some(AGENT)).await;
+    assert_eq!(status, StatusCode::OK);
+    let mut seen: Vec<String> = first["tickets"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["title"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(seen.len(), 2);
+
+    // An older ticket lands before the cursor; an offset would now repeat a row
+    let agent = app.user_id(AGENT).await;
+    ticket::ActiveModel {
+        title: Set("backdated".into()),
+        user_id: Set(Some(agent)),
+        created_at: Set(time::parse("2000-01-01T00:00:00Z")),
+        ..Default::default()
+    }
+    .insert(app.db())
+    .await
+    .unwrap();
+
+    let mut next = first["next_cursor"].as_str().map(String::from);
+    while let Some(cursor) = next {
+        let (status, page) = app
+            .get(&format!("/tickets?cursor={cursor}&limit=2"), Some(AGENT))
+            .await;
+        assert_eq!(status, StatusCode::OK);
+        seen.extend(
+            page["tickets"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|t| t["title"].as_str().unwrap().to_string()),
+        );
+        next = page["next_cursor"].as_str().map(String::from);
+    }
+
+    seen.sort();
+    assert_eq!(seen, expected);
+
+    let (status, body) = app.get("/tickets?cursor=bogus", Some(AGENT)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_cursor");
+}
+
+#[tokio::test]
+async fn bulk_status_update_reports_mixed_outcomes_as_multi_status() {
+    let app = TestApp::seeded().await;
+    create_titled(&app, OTHER_AGENT, "not yours", &[]).await;
+    let (_, mine) = app.get("/tickets", Some(AGENT)).await;
+    let (_, theirs) = app.get("/tickets", Some(OTHER_AGENT)).await;
+    let mine = mine[0]["id"].as_i64().unwrap();
+    let theirs = theirs[0]["id"].as_i64().unwrap();
+
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/tickets/bulk-status",
+            Some(AGENT),
+            Some(json!({ "ids": [mine, theirs, 999999], "status": "closed" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::MULTI_STATUS);
+    assert_eq!(
+        body,
+        json!([
+            { "id": mine, "status": 200 },
+            { "id": theirs, "status": 403, "error": "forbidden" },
+            { "id": 999999, "status": 404, "error": "not_found" },
+        ])
+    );
+    let (_, ticket) = app.get(&format!("/tickets/{mine}"), Some(AGENT)).await;
+    assert_eq!(ticket["status"], "closed");
+
+    // All good: plain 200 with the same shape
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/tickets/bulk-status",
+            Some(ADMIN),
+            Some(json!({ "ids": [mine, theirs], "status": "open" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body,
+        json!([{ "id": mine, "status": 200 }, { "id": theirs, "status": 200 }])
+    );
+}
+
+#[tokio::test]
+async fn bulk_status_update_reports_a_failed_write_and_keeps_going() {
+    let app = TestApp::seeded().await;
+    for title in ["first", "second"] {
+        create_titled(&app, AGENT, title, &[]).await;
+    }
+    let (_, mine) = app.get("/tickets", Some(AGENT)).await;
+    let ids: Vec<i64> = mine
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["id"].as_i64().unwrap())
+        .collect();
+    let [first, broken, last] = ids[..] else {
+        panic!("expected three tickets, got {mine}");
+    };
+
+    // Make the database refuse the middle ticket's update
+    app.db()
+        .execute_unprepared(&format!(
+            "CREATE FUNCTION refuse_update() RETURNS trigger AS $$ \
+             BEGIN RAISE EXCEPTION 'refused'; END $$ LANGUAGE plpgsql; \
+             CREATE TRIGGER refuse_update BEFORE UPDATE ON ticket \
+             FOR EACH ROW WHEN (OLD.id = {broken}) EXECUTE FUNCTION refuse_update();"
+        ))
+        .await
+        .unwrap();
+
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/tickets/bulk-status",
+            Some(AGENT),
+            Some(json!({ "ids": [first, broken, last], "status": "closed" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::MULTI_STATUS);
+    assert_eq!(
+        body,
+        json!([
+            { "id": first, "status": 200 },
+            { "id": broken, "status": 500, "error": "db_error" },
+            { "id": last, "status": 200 },
+        ])
+    );
+    for id in [first, last] {
+        let (_, ticket) = app.get(&format!("/tickets/{id}"), Some(AGENT)).await;
+        assert_eq!(ticket["status"], "closed");
+    }
+    let (_, ticket) = app.get(&format!("/tickets/{broken}"), Some(AGENT)).await;
+    assert_ne!(ticket["status"], "closed");
+}
+
+#[tokio::test]
+async fn ordered_fetch_follows_the_requested_ids_and_skips_hidden_ones() {
+    let app = TestApp::seeded().await;
+    for title in ["first", "second", "third"] {
+        create_titled(&app, AGENT, title, &[]).await;
+    }
+    create_titled(&app, OTHER_AGENT, "not yours", &[]).await;
+    let (_, mine) = app.get("/tickets", Some(AGENT)).await;
+    let (_, theirs) = app.get("/tickets", Some(OTHER_AGENT)).await;
+    let id_of = |title: &str| {
+        mine.as_array()
+            .unwrap()
+            .iter()
+            .find(|t| t["title"] == title)
+            .unwrap()["id"]
+            .as_i64()
+            .unwrap()
+    };
+    let (first, second, third) = (id_of("first"), id_of("second"), id_of("third"));
+    let theirs = theirs[0]["id"].as_i64().unwrap();
+
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/tickets/ordered",
+            Some(AGENT),
+            Some(json!({ "ids": [third, theirs, first, 999999, second, third] })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let ids: Vec<i64> = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["id"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, [third, first, second]);
+
+    let (_, body) = app
+        .send(
+            Method::POST,
+            "/tickets/ordered",
+            Some(ADMIN),
+            Some(json!({ "ids": [theirs, second] })),
+        )
+        .await;
+    assert_eq!(body[0]["id"], theirs);
+    assert_eq!(body[1]["id"], second);
+}
+
+#[tokio::test]
+async fn non_numeric_ticket_id_is_a_json_400() {
+    let app = TestApp::seeded().await;
+
+    let (status, body) = app.get("/tickets/abc", Some(AGENT)).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_path_param");
+    assert!(body["detail"].as_str().unwrap().contains("abc"), "{body}");
+}
+
+async fn create_ticket_status(app: &TestApp, as_user: &str) -> (StatusCode, serde_json::Value) {
+    app.send(
+        Method::POST,
+        "/tickets",
+        Some(as_user),
+        Some(json!({ "title": "Quota check" })),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn agents_are_held_to_the_ticket_quota_but_admins_are_not() {
+    // The seed assigns one ticket to each of AGENT and ADMIN
+    let app = TestApp::seeded_with(|c| c.max_tickets_per_user = Some(3)).await;
+
+    for _ in 0..2 {
+        let (status, _) = create_ticket_status(&app, AGENT).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+    let (status, body) = create_ticket_status(&app, AGENT).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body, json!({ "error": "ticket_quota_exceeded", "max": 3 }));
+
+    // Another agent has their own allowance
+    let (status, _) = create_ticket_status(&app, OTHER_AGENT).await;
+    assert_eq!(status, StatusCode::OK);
+
+    for _ in 0..3 {
+        let (status, _) = create_ticket_status(&app, ADMIN).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+}
+
+#[tokio::test]
+async fn bundle_round_trips_through_import_to_the_importer() {
+    let app = TestApp::seeded().await;
+    let (_, tickets) = app.get("/tickets", Some(AGENT)).await;
+    let id = tickets[0]["id"].as_i64().unwrap();
+    let feature = app.tag_id("Feature").await;
+    let link = format!("/relations/{id}/tags/{feature}");
+    app.send(Method::POST, &link, Some(AGENT), None).await;
+    app.send(Method::DELETE, &link, Some(AGENT), None).await;
+
+    let (status, _) = app
+        .get(&format!("/tickets/{id}/bundle"), Some(OTHER_AGENT))
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, bundle) = app.get(&format!("/tickets/{id}/bundle"), Some(AGENT)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(bundle["tags"], json!(["Bug", "Urgent"]));
+    assert_eq!(bundle["activity"][0]["action"], "attached");
+    assert_eq!(bundle["activity"][1]["action"], "detached");
+    assert_eq!(bundle["activity"][1]["tag"], "Feature");
+    assert_eq!(bundle["activity"][1]["actor_email"], AGENT);
+
+    let (status, imported) = app
+        .send(
+            Method::POST,
+            "/tickets/import",
+            Some(OTHER_AGENT),
+            Some(bundle.clone()),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_ne!(imported["id"], id);
+    assert_eq!(imported["user_id"], app.user_id(OTHER_AGENT).await);
+
+    // Exporting the copy gives back the same bundle
+    let (_, copy) = app
+        .get(
+            &format!("/tickets/{}/bundle", imported["id"]),
+            Some(OTHER_AGENT),
+        )
+        .await;
+    assert_eq!(copy, bundle);
+}
+
+#[tokio::test]
+async fn importing_a_bundle_counts_against_the_ticket_quota() {
+    // The seed assigns one ticket to AGENT
+    let app = TestApp::seeded_with(|c| c.max_tickets_per_user = Some(2)).await;
+    let bundle = json!({ "format": "tagblaze.ticket.v1", "ticket": { "title": "Imported" } });
+    let import = || {
+        app.send(
+            Method::POST,
+            "/tickets/import",
+            Some(AGENT),
+            Some(bundle.clone()),
+        )
+    };
+
+    let (status, _) = import().await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, body) = import().await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body, json!({ "error": "ticket_quota_exceeded", "max": 2 }));
+}
+
+#[tokio::test]
+async fn rejected_import_leaves_nothing_behind() {
+    let app = TestApp::seeded().await;
+    let before = ticket::Entity::find().count(app.db()).await.unwrap();
+
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/tickets/import",
+            Some(AGENT),
+            Some(json!({
+                "format": "tagblaze.ticket.v1",
+                "ticket": { "title": "Imported" },
+                "tags": ["Brand new", " "],
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_tag_name");
+
+    let (status, body) = app
+        .send(
+            Method::POST,
+            "/tickets/import",
+            Some(AGENT),
+            Some(json!({ "format": "other", "ticket": { "title": "Imported" } })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "unsupported_format");
+
+    assert_eq!(
+        ticket::Entity::find().count(app.db()).await.unwrap(),
+        before
+    );
+}
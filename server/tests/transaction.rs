@@ -0,0 +1,108 @@
+mod common;
+
+use axum::{
+    Router,
+    body::Body,
+    extract::State,
+    http::{Request, StatusCode},
+    middleware::from_fn,
+    response::{IntoResponse, Response},
+    routing::post,
+};
+use common::TestApp;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter, Set,
+};
+use tagblaze::{
+    middleware::transaction::{Tx, request_transaction},
+    models::tag,
+    state::AppState,
+};
+use tower::ServiceExt;
+
+async fn insert_tag(db: &impl ConnectionTrait, name: &str) {
+    tag::ActiveModel {
+        name: Set(name.into()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .unwrap();
+}
+
+/// Inserts one tag and queues an announcement, then fails before the second write.
+async fn fail_after_first_insert(State(state): State<AppState>, tx: Tx) -> Response {
+    insert_tag(&*tx, "First").await;
+    tx.after_commit(async move { insert_tag(&state.db, "Announced").await });
+    StatusCode::UNPROCESSABLE_ENTITY.into_response()
+}
+
+/// Inserts two tags through separately extracted handles, then queues a
+/// check of what the rest of the app can see of them.
+async fn insert_two(State(state): State<AppState>, first: Tx, second: Tx) -> Response {
+    insert_tag(&*first, "One").await;
+    insert_tag(&*second, "Two").await;
+    second.after_commit(async move {
+        let seen = tag::Entity::find()
+            .filter(tag::Column::Name.is_in(["One", "Two"]))
+            .count(&state.db)
+            .await
+            .unwrap();
+        insert_tag(&state.db, &format!("Saw {seen}")).await;
+    });
+    StatusCode::CREATED.into_response()
+}
+
+async fn post_to(app: &TestApp, uri: &str) -> StatusCode {
+    let router = Router::new()
+        .route("/fail", post(fail_after_first_insert))
+        .route("/ok", post(insert_two))
+        .route_layer(from_fn(request_transaction))
+        .with_state(app.state.clone());
+    let request = Request::post(uri).body(Body::empty()).unwrap();
+    router.oneshot(request).await.unwrap().status()
+}
+
+async fn tag_count(app: &TestApp, name: &str) -> u64 {
+    tag::Entity::find()
+        .filter(tag::Column::Name.eq(name))
+        .count(app.db())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn error_response_rolls_back_earlier_writes() {
+    let app = TestApp::new().await;
+
+    assert_eq!(
+        post_to(&app, "/fail").await,
+        StatusCode::UNPROCESSABLE_ENTITY
+    );
+    assert_eq!(tag_count(&app, "First").await, 0);
+}
+
+#[tokio::test]
+async fn success_commits_every_write_in_one_transaction() {
+    let app = TestApp::new().await;
+
+    assert_eq!(post_to(&app, "/ok").await, StatusCode::CREATED);
+    assert_eq!(tag_count(&app, "One").await, 1);
+    assert_eq!(tag_count(&app, "Two").await, 1);
+}
+
+#[tokio::test]
+async fn after_commit_effects_are_dropped_on_rollback() {
+    let app = TestApp::new().await;
+
+    post_to(&app, "/fail").await;
+    assert_eq!(tag_count(&app, "Announced").await, 0);
+}
+
+#[tokio::test]
+async fn after_commit_effects_see_the_committed_writes() {
+    let app = TestApp::new().await;
+
+    post_to(&app, "/ok").await;
+    assert_eq!(tag_count(&app, "Saw 2").await, 1);
+}